@@ -1,4 +1,7 @@
-use crate::app_builder::AppBuilder;
+use crate::{
+    app_builder::AppBuilder,
+    event::{Events, ManualEventReader},
+};
 use bevy_ecs::{
     schedule::{Schedule, Stage},
     world::World,
@@ -31,8 +34,10 @@ use bevy_utils::tracing::info_span;
 /// ```
 pub struct App {
     pub world: World,
-    pub runner: Box<dyn Fn(App)>,
+    pub runner: Box<dyn Fn(App) -> AppExit>,
     pub schedule: Schedule,
+    /// Runs once when the app exits. See [App::shutdown].
+    pub shutdown_schedule: Schedule,
 }
 
 impl Default for App {
@@ -40,13 +45,26 @@ impl Default for App {
         Self {
             world: Default::default(),
             schedule: Default::default(),
+            shutdown_schedule: Default::default(),
             runner: Box::new(run_once),
         }
     }
 }
 
-fn run_once(mut app: App) {
+fn run_once(mut app: App) -> AppExit {
     app.update();
+    let app_exit = app
+        .world
+        .get_resource::<Events<AppExit>>()
+        .and_then(|app_exit_events| {
+            ManualEventReader::<AppExit>::default()
+                .iter(app_exit_events)
+                .last()
+                .cloned()
+        })
+        .unwrap_or_default();
+    app.shutdown();
+    app_exit
 }
 
 impl App {
@@ -58,17 +76,61 @@ impl App {
         self.schedule.run(&mut self.world);
     }
 
-    pub fn run(mut self) {
+    /// Runs the app using its runner, and returns the [AppExit] that stopped it. Runners that
+    /// cannot return on the current platform, such as `winit`'s, never return.
+    pub fn run(mut self) -> AppExit {
         #[cfg(feature = "trace")]
         let bevy_app_run_span = info_span!("bevy_app");
         #[cfg(feature = "trace")]
         let _bevy_app_run_guard = bevy_app_run_span.enter();
 
         let runner = std::mem::replace(&mut self.runner, Box::new(run_once));
-        (runner)(self);
+        (runner)(self)
+    }
+
+    /// Runs the shutdown systems added with
+    /// [AppBuilder::add_shutdown_system](crate::AppBuilder::add_shutdown_system). Runners call
+    /// this once after the app has stopped updating, before the [World] is dropped. Calling it
+    /// again does nothing.
+    pub fn shutdown(&mut self) {
+        let mut shutdown_schedule = std::mem::take(&mut self.shutdown_schedule);
+        shutdown_schedule.run(&mut self.world);
     }
 }
 
 /// An event that indicates the app should exit. This will fully exit the app process.
-#[derive(Debug, Clone)]
-pub struct AppExit;
+///
+/// The exit code of the last [AppExit] sent is returned by [App::run], so it can be passed on to
+/// the process:
+/// ```no_run
+/// # use bevy_app::prelude::*;
+/// let app_exit = App::build().run();
+/// std::process::exit(app_exit.code);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppExit {
+    /// The process exit code. 0 means success.
+    pub code: i32,
+    /// Why the app exited, if known
+    pub reason: Option<String>,
+}
+
+impl AppExit {
+    /// An exit with code 0
+    pub fn success() -> Self {
+        AppExit::default()
+    }
+
+    /// An exit with the given non-zero `code`
+    pub fn error(code: i32, reason: impl Into<String>) -> Self {
+        AppExit {
+            code,
+            reason: Some(reason.into()),
+        }
+    }
+
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        self.code == 0
+    }
+}
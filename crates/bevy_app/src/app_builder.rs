@@ -1,8 +1,8 @@
 use crate::{
     app::{App, AppExit},
     event::Events,
-    plugin::Plugin,
-    CoreStage, PluginGroup, PluginGroupBuilder, StartupStage,
+    plugin::{Plugin, PluginError, PluginId},
+    CoreStage, PluginGroup, PluginGroupBuilder, ShutdownStage, StartupStage,
 };
 use bevy_ecs::{
     component::{Component, ComponentDescriptor},
@@ -12,18 +12,27 @@ use bevy_ecs::{
     system::{IntoExclusiveSystem, IntoSystem},
     world::{FromWorld, World},
 };
-use bevy_utils::tracing::debug;
-use std::{fmt::Debug, hash::Hash};
+use bevy_utils::{tracing::debug, HashSet};
+use std::{
+    any::{Any, TypeId},
+    fmt::Debug,
+    hash::Hash,
+};
 
 /// Configure [App]s using the builder pattern
 pub struct AppBuilder {
     pub app: App,
+    plugins: HashSet<TypeId>,
+    /// The name and dependencies of every added plugin that has dependencies
+    plugin_dependencies: Vec<(String, Vec<PluginId>)>,
 }
 
 impl Default for AppBuilder {
     fn default() -> Self {
         let mut app_builder = AppBuilder {
             app: App::default(),
+            plugins: Default::default(),
+            plugin_dependencies: Default::default(),
         };
 
         #[cfg(feature = "bevy_reflect")]
@@ -41,12 +50,19 @@ impl AppBuilder {
     pub fn empty() -> AppBuilder {
         AppBuilder {
             app: App::default(),
+            plugins: Default::default(),
+            plugin_dependencies: Default::default(),
         }
     }
 
-    pub fn run(&mut self) {
+    /// Runs the [App]. This will panic if a plugin's dependencies were not added, see
+    /// [AppBuilder::check_plugin_dependencies].
+    pub fn run(&mut self) -> AppExit {
+        if let Err(err) = self.check_plugin_dependencies() {
+            panic!("{}", err);
+        }
         let app = std::mem::take(&mut self.app);
-        app.run();
+        app.run()
     }
 
     pub fn world(&mut self) -> &World {
@@ -204,6 +220,7 @@ impl AppBuilder {
     }
 
     pub fn add_default_stages(&mut self) -> &mut Self {
+        self.add_default_shutdown_stages();
         self.add_stage(CoreStage::First, SystemStage::parallel())
             .add_stage(
                 CoreStage::Startup,
@@ -275,18 +292,128 @@ impl AppBuilder {
         self
     }
 
-    pub fn set_runner(&mut self, run_fn: impl Fn(App) + 'static) -> &mut Self {
+    /// Adds a system that runs once when the app exits, e.g. to flush saves or close connections.
+    /// See [App::shutdown].
+    pub fn add_shutdown_system(&mut self, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.add_shutdown_system_to_stage(ShutdownStage::Shutdown, system)
+    }
+
+    /// Adds a shutdown system to the given stage. The default shutdown stages are created if they
+    /// don't exist yet, e.g. on an [AppBuilder::empty] app.
+    pub fn add_shutdown_system_to_stage(
+        &mut self,
+        stage_label: impl StageLabel,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        if self
+            .app
+            .shutdown_schedule
+            .get_stage::<SystemStage>(&ShutdownStage::Shutdown)
+            .is_none()
+        {
+            self.add_default_shutdown_stages();
+        }
+        self.app
+            .shutdown_schedule
+            .add_system_to_stage(stage_label, system);
+        self
+    }
+
+    fn add_default_shutdown_stages(&mut self) -> &mut Self {
+        let schedule = &mut self.app.shutdown_schedule;
+        for label in [
+            ShutdownStage::PreShutdown,
+            ShutdownStage::Shutdown,
+            ShutdownStage::PostShutdown,
+        ]
+        .iter()
+        {
+            if schedule.get_stage::<SystemStage>(label).is_none() {
+                schedule.add_stage(label.clone(), SystemStage::parallel());
+            }
+        }
+        self
+    }
+
+    pub fn set_runner(&mut self, run_fn: impl Fn(App) -> AppExit + 'static) -> &mut Self {
         self.app.runner = Box::new(run_fn);
         self
     }
 
+    /// Adds a [Plugin] and runs its [Plugin::build] function. This will panic if the plugin is
+    /// unique and was already added. Use [AppBuilder::try_add_plugin] to handle this error
+    /// instead. The plugin's dependencies may be added after it, they are checked when the app
+    /// runs.
     pub fn add_plugin<T>(&mut self, plugin: T) -> &mut Self
     where
         T: Plugin,
     {
+        self.add_boxed_plugin(Box::new(plugin))
+    }
+
+    /// Same as [AppBuilder::add_plugin], but for a plugin that has already been boxed, such as
+    /// a dynamically loaded plugin
+    pub fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
+        if let Err(err) = self.try_add_boxed_plugin(plugin) {
+            panic!("{}", err);
+        }
+        self
+    }
+
+    pub fn try_add_plugin<T>(&mut self, plugin: T) -> Result<&mut Self, PluginError>
+    where
+        T: Plugin,
+    {
+        self.try_add_boxed_plugin(Box::new(plugin))
+    }
+
+    pub fn try_add_boxed_plugin(
+        &mut self,
+        plugin: Box<dyn Plugin>,
+    ) -> Result<&mut Self, PluginError> {
+        let plugin_type = Any::type_id(plugin.as_ref());
+        if plugin.is_unique() && self.plugins.contains(&plugin_type) {
+            return Err(PluginError::Duplicate {
+                plugin: plugin.name().to_string(),
+            });
+        }
+        let dependencies = plugin.dependencies();
+        if !dependencies.is_empty() {
+            self.plugin_dependencies
+                .push((plugin.name().to_string(), dependencies));
+        }
+
         debug!("added plugin: {}", plugin.name());
+        self.plugins.insert(plugin_type);
         plugin.build(self);
-        self
+        Ok(self)
+    }
+
+    /// Checks that the dependencies of every added plugin were added, in any order.
+    /// [AppBuilder::run] panics with this error.
+    pub fn check_plugin_dependencies(&self) -> Result<(), PluginError> {
+        for (plugin, dependencies) in self.plugin_dependencies.iter() {
+            let missing = dependencies
+                .iter()
+                .filter(|&dependency| !self.plugins.contains(&dependency.type_id()))
+                .map(|dependency| dependency.name())
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                return Err(PluginError::MissingDependencies {
+                    plugin: plugin.clone(),
+                    missing,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if a plugin of type `T` has been added
+    pub fn is_plugin_added<T>(&self) -> bool
+    where
+        T: Plugin,
+    {
+        self.plugins.contains(&TypeId::of::<T>())
     }
 
     pub fn add_plugins<T: PluginGroup>(&mut self, mut group: T) -> &mut Self {
@@ -0,0 +1,215 @@
+use bevy_utils::{Duration, Instant};
+
+/// Configures how app runners pace frames. Insert this resource before adding the runner's plugin
+/// (e.g. [ScheduleRunnerPlugin](crate::ScheduleRunnerPlugin) or `WinitPlugin`).
+#[derive(Debug, Clone)]
+pub struct FramePacingSettings {
+    /// The minimum time between the start of two frames. `None` runs frames as fast as possible.
+    pub target_frame_time: Option<Duration>,
+    /// The minimum time between the start of two frames while the app is not focused, e.g. to
+    /// save power while the window is in the background. `None` uses `target_frame_time`.
+    pub unfocused_frame_time: Option<Duration>,
+    /// How long before a frame's deadline the pacer stops sleeping and starts spinning. Sleeping
+    /// is only accurate to the OS scheduler's granularity, so spinning for the last part of the
+    /// wait makes frame times more consistent, at the cost of some CPU time.
+    pub spin_threshold: Duration,
+}
+
+impl Default for FramePacingSettings {
+    fn default() -> Self {
+        FramePacingSettings {
+            target_frame_time: None,
+            unfocused_frame_time: None,
+            spin_threshold: Duration::from_millis(2),
+        }
+    }
+}
+
+impl FramePacingSettings {
+    /// Limits the app to `fps` frames per second
+    pub fn from_fps(fps: f64) -> Self {
+        FramePacingSettings {
+            target_frame_time: Some(Duration::from_secs_f64(1.0 / fps)),
+            ..Default::default()
+        }
+    }
+
+    /// Limits the app to `fps` frames per second while it is not focused
+    pub fn with_unfocused_fps(mut self, fps: f64) -> Self {
+        self.unfocused_frame_time = Some(Duration::from_secs_f64(1.0 / fps));
+        self
+    }
+
+    pub fn with_spin_threshold(mut self, spin_threshold: Duration) -> Self {
+        self.spin_threshold = spin_threshold;
+        self
+    }
+
+    /// The minimum frame time that applies, depending on whether the app is `focused`
+    pub fn frame_time(&self, focused: bool) -> Option<Duration> {
+        if focused {
+            self.target_frame_time
+        } else {
+            self.unfocused_frame_time.or(self.target_frame_time)
+        }
+    }
+}
+
+/// Frame pacing statistics, updated by the runner after each frame. `bevy_diagnostic`'s
+/// `FramePacingDiagnosticsPlugin` reports them through `Diagnostics`.
+#[derive(Debug, Clone, Default)]
+pub struct FramePacingStats {
+    /// The number of frames that were paced
+    pub frames: u64,
+    /// The number of frames that took longer than the target frame time
+    pub late_frames: u64,
+    /// The number of target frame intervals that were skipped because frames were late
+    pub dropped_frames: u64,
+    /// Whether the last frame took longer than the target frame time
+    pub last_frame_late: bool,
+    /// The number of target frame intervals skipped by the last frame
+    pub last_frame_dropped: u32,
+    /// How long the last frame took, excluding the time spent waiting for its deadline
+    pub last_frame_time: Duration,
+}
+
+/// Waits between frames to hit a target frame rate. This is shared by app runners: call
+/// [FramePacer::pace] once after each update.
+///
+/// ```
+/// # use bevy_app::{FramePacer, FramePacingSettings};
+/// let settings = FramePacingSettings::from_fps(120.0);
+/// let mut pacer = FramePacer::default();
+/// for _ in 0..3 {
+///     // app.update();
+///     pacer.pace(&settings, true);
+/// }
+/// assert_eq!(pacer.stats().frames, 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct FramePacer {
+    frame_start: Option<Instant>,
+    stats: FramePacingStats,
+}
+
+impl FramePacer {
+    #[inline]
+    pub fn stats(&self) -> &FramePacingStats {
+        &self.stats
+    }
+
+    /// Records the end of a frame, and returns the [Instant] the next frame should start at, or
+    /// `None` if it should start immediately. Runners that cannot block, like the web runner,
+    /// use this instead of [FramePacer::pace] to schedule the next frame.
+    pub fn frame_finished(
+        &mut self,
+        settings: &FramePacingSettings,
+        focused: bool,
+    ) -> Option<Instant> {
+        let now = Instant::now();
+        let frame_start = self.frame_start.unwrap_or(now);
+        let frame_time = now - frame_start;
+        self.stats.frames += 1;
+        self.stats.last_frame_time = frame_time;
+        self.stats.last_frame_late = false;
+        self.stats.last_frame_dropped = 0;
+
+        let target_frame_time = match settings.frame_time(focused) {
+            Some(target_frame_time) if self.frame_start.is_some() => target_frame_time,
+            _ => {
+                self.frame_start = Some(now);
+                return None;
+            }
+        };
+
+        if frame_time > target_frame_time {
+            let dropped = (frame_time.as_nanos() / target_frame_time.as_nanos().max(1)) as u32;
+            self.stats.late_frames += 1;
+            self.stats.dropped_frames += dropped as u64;
+            self.stats.last_frame_late = true;
+            self.stats.last_frame_dropped = dropped;
+            self.frame_start = Some(now);
+            None
+        } else {
+            // start the next frame exactly on the deadline, so waiting errors don't accumulate
+            let deadline = frame_start + target_frame_time;
+            self.frame_start = Some(deadline);
+            Some(deadline)
+        }
+    }
+
+    /// Records the end of a frame, then blocks until the next frame should start
+    pub fn pace(&mut self, settings: &FramePacingSettings, focused: bool) {
+        if let Some(deadline) = self.frame_finished(settings, focused) {
+            wait_until(deadline, settings.spin_threshold);
+        }
+    }
+}
+
+/// Sleeps until shortly before `deadline`, then spins until it is reached
+pub fn wait_until(deadline: Instant, spin_threshold: Duration) {
+    let now = Instant::now();
+    if deadline <= now {
+        return;
+    }
+    let remaining = deadline - now;
+    if remaining > spin_threshold {
+        std::thread::sleep(remaining - spin_threshold);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FramePacer, FramePacingSettings};
+    use bevy_utils::{Duration, Instant};
+
+    #[test]
+    fn target_frame_time() {
+        let settings = FramePacingSettings {
+            target_frame_time: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let mut pacer = FramePacer::default();
+        let start = Instant::now();
+        for _ in 0..6 {
+            pacer.pace(&settings, true);
+        }
+        // the first frame has nothing to wait for
+        assert!(Instant::now() - start >= Duration::from_millis(50));
+        assert_eq!(pacer.stats().frames, 6);
+    }
+
+    #[test]
+    fn late_frames() {
+        let settings = FramePacingSettings {
+            target_frame_time: Some(Duration::from_millis(5)),
+            ..Default::default()
+        };
+        let mut pacer = FramePacer::default();
+        pacer.pace(&settings, true);
+        std::thread::sleep(Duration::from_millis(12));
+        assert_eq!(pacer.frame_finished(&settings, true), None);
+        let stats = pacer.stats();
+        assert!(stats.last_frame_late);
+        assert!(stats.last_frame_dropped >= 2);
+        assert_eq!(stats.late_frames, 1);
+        assert_eq!(stats.dropped_frames, stats.last_frame_dropped as u64);
+    }
+
+    #[test]
+    fn unfocused_frame_time() {
+        let settings = FramePacingSettings::from_fps(100.0).with_unfocused_fps(10.0);
+        assert_eq!(
+            settings.frame_time(true),
+            Some(Duration::from_secs_f64(0.01))
+        );
+        assert_eq!(
+            settings.frame_time(false),
+            Some(Duration::from_secs_f64(0.1))
+        );
+        assert_eq!(FramePacingSettings::default().frame_time(false), None);
+    }
+}
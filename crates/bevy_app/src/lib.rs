@@ -1,24 +1,34 @@
 mod app;
 mod app_builder;
 mod event;
+mod frame_pacing;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
+mod test_app;
+
+#[cfg(feature = "bevy_reflect")]
+mod settings;
 
 pub use app::*;
 pub use app_builder::*;
 pub use bevy_derive::DynamicPlugin;
 pub use event::*;
+pub use frame_pacing::*;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
+pub use test_app::*;
+
+#[cfg(feature = "bevy_reflect")]
+pub use settings::*;
 
 pub mod prelude {
     pub use crate::{
         app::App,
         app_builder::AppBuilder,
         event::{EventReader, EventWriter},
-        CoreStage, DynamicPlugin, Plugin, PluginGroup, StartupStage,
+        AppExit, CoreStage, DynamicPlugin, Plugin, PluginGroup, ShutdownStage, StartupStage,
     };
 }
 
@@ -51,3 +61,14 @@ pub enum StartupStage {
     /// Name of app stage that runs once after the startup stage
     PostStartup,
 }
+
+/// The names of the default App shutdown stages
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub enum ShutdownStage {
+    /// Name of app stage that runs once before the shutdown stage
+    PreShutdown,
+    /// Name of app stage that runs once when an app exits
+    Shutdown,
+    /// Name of app stage that runs once after the shutdown stage
+    PostShutdown,
+}
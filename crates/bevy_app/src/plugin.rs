@@ -1,5 +1,9 @@
 use crate::AppBuilder;
-use std::any::Any;
+use std::{
+    any::{Any, TypeId},
+    fmt,
+};
+use thiserror::Error;
 
 /// A collection of Bevy App logic and configuration
 ///
@@ -10,6 +14,72 @@ pub trait Plugin: Any + Send + Sync {
     fn name(&self) -> &str {
         std::any::type_name::<Self>()
     }
+
+    /// The plugins that must also be added to an [App](crate::App) for this one to work. They are
+    /// checked when the app runs. When added through a [PluginGroup](crate::PluginGroup), plugins
+    /// are reordered to be built after their dependencies.
+    fn dependencies(&self) -> Vec<PluginId> {
+        Vec::new()
+    }
+
+    /// If this returns true, adding this plugin to an [App](crate::App) more than once is an error
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// Identifies a [Plugin] type, e.g. in [Plugin::dependencies]
+#[derive(Clone, Copy)]
+pub struct PluginId {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl PluginId {
+    pub fn of<T: Plugin>() -> Self {
+        PluginId {
+            type_id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for PluginId {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id
+    }
+}
+
+impl Eq for PluginId {}
+
+impl fmt::Debug for PluginId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+/// An error that occurs when adding a [Plugin] to an [AppBuilder]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    #[error("Plugin {plugin} was added more than once, but it is unique.")]
+    Duplicate { plugin: String },
+    #[error("Plugin {plugin} is missing dependencies: {}. Add them to the app.", .missing.join(", "))]
+    MissingDependencies {
+        plugin: String,
+        missing: Vec<&'static str>,
+    },
+    #[error("Plugins have a dependency cycle: {}.", .plugins.join(" -> "))]
+    DependencyCycle { plugins: Vec<String> },
 }
 
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
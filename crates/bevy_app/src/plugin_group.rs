@@ -1,5 +1,5 @@
-use crate::{AppBuilder, Plugin};
-use bevy_utils::{tracing::debug, HashMap};
+use crate::{AppBuilder, Plugin, PluginError};
+use bevy_utils::HashMap;
 use std::any::TypeId;
 
 pub trait PluginGroup {
@@ -96,14 +96,199 @@ impl PluginGroupBuilder {
         self
     }
 
-    pub fn finish(self, app: &mut AppBuilder) {
+    /// Adds the enabled plugins to `app`. Plugins keep the order they were added in, except that
+    /// a plugin is moved after any plugins in this group that it depends on.
+    pub fn finish(mut self, app: &mut AppBuilder) {
+        let order = self.sorted_order().unwrap_or_else(|err| panic!("{}", err));
+        for ty in order {
+            if let Some(entry) = self.plugins.remove(&ty) {
+                app.add_boxed_plugin(entry.plugin);
+            }
+        }
+    }
+
+    fn sorted_order(&self) -> Result<Vec<TypeId>, PluginError> {
+        let mut sorted = Vec::with_capacity(self.order.len());
+        let mut visiting = Vec::new();
         for ty in self.order.iter() {
-            if let Some(entry) = self.plugins.get(ty) {
-                if entry.enabled {
-                    debug!("added plugin: {}", entry.plugin.name());
-                    entry.plugin.build(app);
-                }
+            self.visit(*ty, &mut visiting, &mut sorted)?;
+        }
+        Ok(sorted)
+    }
+
+    fn visit(
+        &self,
+        ty: TypeId,
+        visiting: &mut Vec<TypeId>,
+        sorted: &mut Vec<TypeId>,
+    ) -> Result<(), PluginError> {
+        let entry = match self.plugins.get(&ty) {
+            Some(entry) if entry.enabled => entry,
+            // dependencies outside of this group are checked when the app runs
+            _ => return Ok(()),
+        };
+        if sorted.contains(&ty) {
+            return Ok(());
+        }
+        if let Some(index) = visiting.iter().position(|visiting_ty| *visiting_ty == ty) {
+            let plugins = visiting[index..]
+                .iter()
+                .chain(std::iter::once(&ty))
+                .map(|ty| self.plugins[ty].plugin.name().to_string())
+                .collect();
+            return Err(PluginError::DependencyCycle { plugins });
+        }
+
+        visiting.push(ty);
+        for dependency in entry.plugin.dependencies() {
+            self.visit(dependency.type_id(), visiting, sorted)?;
+        }
+        visiting.pop();
+        sorted.push(ty);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PluginGroup, PluginGroupBuilder};
+    use crate::{AppBuilder, Plugin, PluginError, PluginId};
+
+    #[derive(Default)]
+    struct Order(Vec<&'static str>);
+
+    struct A;
+    struct B;
+    struct C;
+
+    impl Plugin for A {
+        fn build(&self, app: &mut AppBuilder) {
+            app.world_mut()
+                .get_resource_mut::<Order>()
+                .unwrap()
+                .0
+                .push("A");
+        }
+
+        fn is_unique(&self) -> bool {
+            true
+        }
+    }
+
+    impl Plugin for B {
+        fn build(&self, app: &mut AppBuilder) {
+            app.world_mut()
+                .get_resource_mut::<Order>()
+                .unwrap()
+                .0
+                .push("B");
+        }
+
+        fn dependencies(&self) -> Vec<PluginId> {
+            vec![PluginId::of::<A>()]
+        }
+    }
+
+    impl Plugin for C {
+        fn build(&self, app: &mut AppBuilder) {
+            app.world_mut()
+                .get_resource_mut::<Order>()
+                .unwrap()
+                .0
+                .push("C");
+        }
+
+        fn dependencies(&self) -> Vec<PluginId> {
+            vec![PluginId::of::<B>()]
+        }
+    }
+
+    struct Plugins;
+
+    impl PluginGroup for Plugins {
+        fn build(&mut self, group: &mut PluginGroupBuilder) {
+            group.add(C).add(B).add(A);
+        }
+    }
+
+    fn app() -> AppBuilder {
+        let mut app = AppBuilder::empty();
+        app.init_resource::<Order>();
+        app
+    }
+
+    #[test]
+    fn group_dependency_order() {
+        let mut app = app();
+        app.add_plugins(Plugins);
+        assert_eq!(
+            app.world().get_resource::<Order>().unwrap().0,
+            ["A", "B", "C"]
+        );
+        assert!(app.is_plugin_added::<B>());
+    }
+
+    #[test]
+    fn duplicate_plugin() {
+        let mut app = app();
+        app.add_plugin(A);
+        assert_eq!(
+            app.try_add_plugin(A).err(),
+            Some(PluginError::Duplicate {
+                plugin: std::any::type_name::<A>().to_string()
+            })
+        );
+        app.add_plugin(B).add_plugin(C).add_plugin(C);
+        assert_eq!(
+            app.world().get_resource::<Order>().unwrap().0,
+            ["A", "B", "C", "C"]
+        );
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let mut app = app();
+        app.add_plugin(C);
+        let err = app.check_plugin_dependencies().err().unwrap();
+        assert_eq!(
+            err,
+            PluginError::MissingDependencies {
+                plugin: std::any::type_name::<C>().to_string(),
+                missing: vec![std::any::type_name::<B>()],
+            }
+        );
+        assert!(err.to_string().contains(std::any::type_name::<B>()));
+
+        // dependencies may be added after the plugins that need them
+        app.add_plugin(B).add_plugin(A);
+        assert_eq!(app.check_plugin_dependencies(), Ok(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "is missing dependencies")]
+    fn missing_dependency_panic() {
+        app().add_plugin(B).run();
+    }
+
+    #[test]
+    #[should_panic(expected = "dependency cycle")]
+    fn dependency_cycle() {
+        struct X;
+        struct Y;
+        impl Plugin for X {
+            fn build(&self, _app: &mut AppBuilder) {}
+            fn dependencies(&self) -> Vec<PluginId> {
+                vec![PluginId::of::<Y>()]
+            }
+        }
+        impl Plugin for Y {
+            fn build(&self, _app: &mut AppBuilder) {}
+            fn dependencies(&self) -> Vec<PluginId> {
+                vec![PluginId::of::<X>()]
             }
         }
+        let mut group = PluginGroupBuilder::default();
+        group.add(X).add(Y);
+        group.finish(&mut app());
     }
 }
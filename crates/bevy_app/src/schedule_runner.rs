@@ -1,5 +1,8 @@
 use super::{App, AppBuilder};
-use crate::{app::AppExit, event::Events, plugin::Plugin, ManualEventReader};
+use crate::{
+    app::AppExit, event::Events, plugin::Plugin, FramePacer, FramePacingSettings, FramePacingStats,
+    ManualEventReader,
+};
 use bevy_utils::{Duration, Instant};
 
 #[cfg(target_arch = "wasm32")]
@@ -10,7 +13,11 @@ use wasm_bindgen::{prelude::*, JsCast};
 /// Determines the method used to run an [App]'s `Schedule`
 #[derive(Copy, Clone, Debug)]
 pub enum RunMode {
-    Loop { wait: Option<Duration> },
+    /// Updates the app in a loop. If `wait` is set, frames start at least `wait` apart, unless a
+    /// [FramePacingSettings] resource was inserted, which takes precedence.
+    Loop {
+        wait: Option<Duration>,
+    },
     Once,
 }
 
@@ -52,18 +59,31 @@ impl Plugin for ScheduleRunnerPlugin {
             .world_mut()
             .get_resource_or_insert_with(ScheduleRunnerSettings::default)
             .to_owned();
+        if let RunMode::Loop { wait } = settings.run_mode {
+            app.world_mut()
+                .get_resource_or_insert_with(|| FramePacingSettings {
+                    target_frame_time: wait,
+                    ..Default::default()
+                });
+        }
         app.set_runner(move |mut app: App| {
             let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
             match settings.run_mode {
                 RunMode::Once => {
                     app.update();
+                    let app_exit = app
+                        .world
+                        .get_resource::<Events<AppExit>>()
+                        .and_then(|app_exit_events| {
+                            app_exit_event_reader.iter(app_exit_events).last().cloned()
+                        })
+                        .unwrap_or_default();
+                    app.shutdown();
+                    app_exit
                 }
-                RunMode::Loop { wait } => {
-                    let mut tick = move |app: &mut App,
-                                         wait: Option<Duration>|
-                          -> Result<Option<Duration>, AppExit> {
-                        let start_time = Instant::now();
-
+                RunMode::Loop { .. } => {
+                    let mut frame_pacer = FramePacer::default();
+                    let mut tick = move |app: &mut App| -> Result<Option<Instant>, AppExit> {
                         if let Some(app_exit_events) =
                             app.world.get_resource_mut::<Events<AppExit>>()
                         {
@@ -84,25 +104,36 @@ impl Plugin for ScheduleRunnerPlugin {
                             }
                         }
 
-                        let end_time = Instant::now();
-
-                        if let Some(wait) = wait {
-                            let exe_time = end_time - start_time;
-                            if exe_time < wait {
-                                return Ok(Some(wait - exe_time));
-                            }
-                        }
-
-                        Ok(None)
+                        let frame_pacing = app
+                            .world
+                            .get_resource_or_insert_with(FramePacingSettings::default)
+                            .clone();
+                        // headless apps are always focused
+                        let next_frame = frame_pacer.frame_finished(&frame_pacing, true);
+                        app.world
+                            .insert_resource::<FramePacingStats>(frame_pacer.stats().clone());
+                        Ok(next_frame)
                     };
 
                     #[cfg(not(target_arch = "wasm32"))]
+                    #[allow(clippy::needless_return)]
                     {
-                        while let Ok(delay) = tick(&mut app, wait) {
-                            if let Some(delay) = delay {
-                                std::thread::sleep(delay);
+                        let app_exit = loop {
+                            match tick(&mut app) {
+                                Ok(Some(next_frame)) => {
+                                    let spin_threshold = app
+                                        .world
+                                        .get_resource::<FramePacingSettings>()
+                                        .unwrap()
+                                        .spin_threshold;
+                                    crate::wait_until(next_frame, spin_threshold);
+                                }
+                                Ok(None) => {}
+                                Err(app_exit) => break app_exit,
                             }
-                        }
+                        };
+                        app.shutdown();
+                        return app_exit;
                     }
 
                     #[cfg(target_arch = "wasm32")]
@@ -124,19 +155,105 @@ impl Plugin for ScheduleRunnerPlugin {
 
                         let c = move || {
                             let mut app = Rc::get_mut(&mut rc).unwrap();
-                            let delay = tick(&mut app, wait);
-                            match delay {
-                                Ok(delay) => {
-                                    set_timeout(f.borrow().as_ref().unwrap(), delay.unwrap_or(asap))
-                                }
-                                Err(_) => {}
+                            let next_frame = tick(&mut app);
+                            match next_frame {
+                                Ok(next_frame) => set_timeout(
+                                    f.borrow().as_ref().unwrap(),
+                                    next_frame
+                                        .map(|next_frame| {
+                                            next_frame.saturating_duration_since(Instant::now())
+                                        })
+                                        .unwrap_or(asap),
+                                ),
+                                Err(_) => app.shutdown(),
                             }
                         };
                         *g.borrow_mut() = Some(Closure::wrap(Box::new(c) as Box<dyn FnMut()>));
                         set_timeout(g.borrow().as_ref().unwrap(), asap);
-                    };
+                        // the browser drives the loop, so the app has not exited yet
+                        return AppExit::success();
+                    }
                 }
             }
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ScheduleRunnerPlugin, ScheduleRunnerSettings};
+    use crate::{prelude::*, ShutdownStage};
+    use bevy_ecs::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Frames(u32);
+
+    #[derive(Clone, Default)]
+    struct ShutdownLog(Arc<Mutex<Vec<&'static str>>>);
+
+    fn count_frames(mut frames: ResMut<Frames>, mut app_exit_events: EventWriter<AppExit>) {
+        frames.0 += 1;
+        if frames.0 == 3 {
+            app_exit_events.send(AppExit::error(2, "failed after 3 frames"));
+        }
+    }
+
+    fn app(log: &ShutdownLog) -> AppBuilder {
+        let mut app = App::build();
+        let post_shutdown_log = log.clone();
+        app.init_resource::<Frames>()
+            .insert_resource(log.clone())
+            .add_shutdown_system(
+                (|log: Res<ShutdownLog>| log.0.lock().unwrap().push("shutdown")).system(),
+            )
+            .add_shutdown_system_to_stage(
+                ShutdownStage::PostShutdown,
+                (move || post_shutdown_log.0.lock().unwrap().push("post_shutdown")).system(),
+            );
+        app
+    }
+
+    #[test]
+    fn loop_exit_code() {
+        let log = ShutdownLog::default();
+        let app_exit = app(&log)
+            .add_plugin(ScheduleRunnerPlugin::default())
+            .add_system(count_frames.system())
+            .run();
+        assert_eq!(app_exit, AppExit::error(2, "failed after 3 frames"));
+        assert!(!app_exit.is_success());
+        assert_eq!(*log.0.lock().unwrap(), vec!["shutdown", "post_shutdown"]);
+    }
+
+    #[test]
+    fn run_once() {
+        let log = ShutdownLog::default();
+        let app_exit = app(&log)
+            .insert_resource(ScheduleRunnerSettings::run_once())
+            .add_plugin(ScheduleRunnerPlugin::default())
+            .run();
+        assert!(app_exit.is_success());
+        assert_eq!(*log.0.lock().unwrap(), vec!["shutdown", "post_shutdown"]);
+    }
+
+    #[test]
+    fn shutdown_runs_once() {
+        let log = ShutdownLog::default();
+        let mut app = std::mem::take(&mut app(&log).app);
+        app.update();
+        app.shutdown();
+        app.shutdown();
+        assert_eq!(log.0.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn shutdown_system_on_empty_app() {
+        let log = ShutdownLog::default();
+        let shutdown_log = log.clone();
+        let mut app = AppBuilder::empty();
+        app.add_shutdown_system((move || shutdown_log.0.lock().unwrap().push("shutdown")).system());
+        app.app.shutdown();
+        assert_eq!(*log.0.lock().unwrap(), vec!["shutdown"]);
+    }
+}
@@ -0,0 +1,647 @@
+use crate::{AppBuilder, Plugin};
+use bevy_ecs::{
+    component::Component,
+    reflect::ReflectResource,
+    world::{FromWorld, World},
+};
+use bevy_reflect::{
+    GetPath, GetTypeRegistration, Reflect, ReflectDeserialize, ReflectMut, TypeRegistration,
+    TypeRegistry, TypeRegistryArc,
+};
+use bevy_utils::tracing::debug;
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// An error that occurs when loading or applying [Settings]
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    #[error("Failed to read settings file {path}: {error}")]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Failed to parse settings from {source_name}: {error}")]
+    Parse {
+        source_name: String,
+        error: ron::Error,
+    },
+    #[error("Invalid setting override '{0}'. Expected 'Resource.field=value'.")]
+    InvalidOverride(String),
+    #[error("Setting {path} of resource {resource} does not exist.")]
+    UnknownField { resource: String, path: String },
+    #[error("Type {0} cannot be deserialized. Register it with ReflectDeserialize type data.")]
+    NotDeserializable(String),
+}
+
+#[derive(Debug, Clone)]
+enum SettingsLayer {
+    /// A RON map from resource type names to the values of their fields
+    Ron { source_name: String, ron: String },
+    /// A single field of a resource, with its value in RON
+    Override {
+        resource: String,
+        path: String,
+        value: String,
+    },
+}
+
+/// Overrides for the fields of reflected resources.
+///
+/// Settings are made of layers: RON files, environment variables and command-line arguments. When
+/// applied, each layer overrides the fields set by the layers added before it. Settings files map
+/// resource type names to the fields that should be overridden. Fields that are not listed keep
+/// their default values:
+/// ```ron
+/// {
+///     "WindowDescriptor": (
+///         title: "QA build",
+///         vsync: false,
+///     ),
+///     "Msaa": (samples: 4),
+/// }
+/// ```
+///
+/// Settings are applied to a resource when a plugin calls [AppBuilder::init_settings] for it.
+/// This happens before the plugin reads the resource, so a [SettingsPlugin] must be added before
+/// the plugins it configures.
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    layers: Vec<SettingsLayer>,
+}
+
+impl Settings {
+    /// Adds a layer from a RON settings file
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, SettingsError> {
+        let path = path.as_ref();
+        let ron = std::fs::read_to_string(path).map_err(|error| SettingsError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        self.add_ron(path.display().to_string(), ron)
+    }
+
+    /// Adds a layer from a RON string. `source_name` is used in error messages.
+    pub fn add_ron(
+        &mut self,
+        source_name: impl Into<String>,
+        ron: impl Into<String>,
+    ) -> Result<&mut Self, SettingsError> {
+        let source_name = source_name.into();
+        let ron = ron.into();
+        // validate the syntax up front, as resources are only deserialized once they are used
+        ron::from_str::<IgnoredAny>(&ron).map_err(|error| SettingsError::Parse {
+            source_name: source_name.clone(),
+            error,
+        })?;
+        self.layers.push(SettingsLayer::Ron { source_name, ron });
+        Ok(self)
+    }
+
+    /// Adds a layer that sets a single field. `key` is the resource type name, followed by the
+    /// path of the field, e.g. `WindowDescriptor.width`. `value` is parsed as RON. Values of
+    /// [String] fields may also be given without quotes.
+    pub fn add_override(&mut self, key: &str, value: &str) -> Result<&mut Self, SettingsError> {
+        let (resource, path) = match key.find('.') {
+            Some(index) => (&key[..index], &key[index + 1..]),
+            None => (key, ""),
+        };
+        if resource.is_empty() {
+            return Err(SettingsError::InvalidOverride(format!("{}={}", key, value)));
+        }
+        self.layers.push(SettingsLayer::Override {
+            resource: resource.to_string(),
+            path: path.to_string(),
+            value: value.to_string(),
+        });
+        Ok(self)
+    }
+
+    /// Adds a layer from the environment variables that start with `prefix`, followed by the
+    /// resource type name and field path separated by double underscores, e.g.
+    /// `BEVY_SETTINGS__WindowDescriptor__width=800`.
+    pub fn add_env(&mut self, prefix: &str) -> Result<&mut Self, SettingsError> {
+        self.add_env_vars(prefix, std::env::vars())
+    }
+
+    fn add_env_vars(
+        &mut self,
+        prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<&mut Self, SettingsError> {
+        let prefix = format!("{}__", prefix);
+        let mut vars = vars
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .collect::<Vec<_>>();
+        // environment variable order is unspecified, so sort for deterministic results
+        vars.sort();
+        for (name, value) in vars {
+            let key = name[prefix.len()..].replace("__", ".");
+            self.add_override(&key, &value)?;
+        }
+        Ok(self)
+    }
+
+    /// Adds a layer from command-line arguments of the form `--set Resource.field=value` or
+    /// `--set=Resource.field=value`. Other arguments are ignored.
+    pub fn add_args(
+        &mut self,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<&mut Self, SettingsError> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let setting = if arg == "--set" {
+                args.next()
+                    .ok_or_else(|| SettingsError::InvalidOverride(arg.clone()))?
+            } else if let Some(setting) = arg.strip_prefix("--set=") {
+                setting.to_string()
+            } else {
+                continue;
+            };
+            let index = setting
+                .find('=')
+                .ok_or_else(|| SettingsError::InvalidOverride(setting.clone()))?;
+            self.add_override(&setting[..index], &setting[index + 1..])?;
+        }
+        Ok(self)
+    }
+
+    /// Applies every layer that sets the resource described by `registration`. The resource is
+    /// initialized with [FromWorld] if it does not exist. Does nothing if `registration` does not
+    /// have [ReflectResource] type data.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        registration: &TypeRegistration,
+    ) -> Result<(), SettingsError> {
+        let reflect_resource = match registration.data::<ReflectResource>() {
+            Some(reflect_resource) => reflect_resource,
+            None => return Ok(()),
+        };
+        let matches = |name: &str| name == registration.short_name() || name == registration.name();
+
+        for layer in self.layers.iter() {
+            match layer {
+                SettingsLayer::Ron { source_name, ron } => {
+                    let mut deserializer =
+                        ron::Deserializer::from_str(ron).map_err(|error| SettingsError::Parse {
+                            source_name: source_name.clone(),
+                            error,
+                        })?;
+                    let seed = ResourcesSeed {
+                        world: &mut *world,
+                        registry,
+                        reflect_resource,
+                        matches: &matches,
+                    };
+                    seed.deserialize(&mut deserializer)
+                        .map_err(|error| SettingsError::Parse {
+                            source_name: source_name.clone(),
+                            error,
+                        })?;
+                }
+                SettingsLayer::Override {
+                    resource,
+                    path,
+                    value,
+                } if matches(resource) => {
+                    reflect_resource.init_resource(world);
+                    let target = reflect_resource.reflect_resource_mut(world).unwrap();
+                    let field = if path.is_empty() {
+                        target
+                    } else {
+                        target
+                            .path_mut(path)
+                            .map_err(|_| SettingsError::UnknownField {
+                                resource: resource.clone(),
+                                path: path.clone(),
+                            })?
+                    };
+                    apply_override(field, value, registry).map_err(|error| {
+                        SettingsError::Parse {
+                            source_name: format!("{}.{}", resource, path),
+                            error,
+                        }
+                    })?;
+                }
+                SettingsLayer::Override { .. } => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+fn apply_override(
+    field: &mut dyn Reflect,
+    value: &str,
+    registry: &TypeRegistry,
+) -> Result<(), ron::Error> {
+    let mut deserializer = ron::Deserializer::from_str(value)?;
+    match (ApplySeed {
+        target: field,
+        registry,
+    })
+    .deserialize(&mut deserializer)
+    {
+        Ok(()) => Ok(()),
+        // allow unquoted strings, which are convenient on the command line
+        Err(_) if field.any().is::<String>() => {
+            field.apply(&value.to_string());
+            Ok(())
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// Deserializes a map of resource names to resource fields, applying the fields of the resources
+/// that match
+struct ResourcesSeed<'a, F> {
+    world: &'a mut World,
+    registry: &'a TypeRegistry,
+    reflect_resource: &'a ReflectResource,
+    matches: &'a F,
+}
+
+impl<'a, 'de, F: Fn(&str) -> bool> DeserializeSeed<'de> for ResourcesSeed<'a, F> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de, F: Fn(&str) -> bool> Visitor<'de> for ResourcesSeed<'a, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of resource names to resource settings")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(name) = map.next_key::<String>()? {
+            if (self.matches)(&name) {
+                self.reflect_resource.init_resource(self.world);
+                let target = self
+                    .reflect_resource
+                    .reflect_resource_mut(self.world)
+                    .unwrap();
+                map.next_value_seed(ApplySeed {
+                    target,
+                    registry: self.registry,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Deserializes a value from `deserializer` and applies it to `target`, the way settings files
+/// are applied to resources: struct fields are applied one at a time, so fields that are not
+/// present keep their current values, and other values are deserialized with their
+/// [ReflectDeserialize] type data from `registry`.
+pub fn apply_deserialized<'de, D: de::Deserializer<'de>>(
+    target: &mut dyn Reflect,
+    registry: &TypeRegistry,
+    deserializer: D,
+) -> Result<(), D::Error> {
+    ApplySeed { target, registry }.deserialize(deserializer)
+}
+
+/// Deserializes a value and applies it to `target`, see [apply_deserialized]
+struct ApplySeed<'a> {
+    target: &'a mut dyn Reflect,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ApplySeed<'a> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        if let ReflectMut::Struct(_) = self.target.reflect_mut() {
+            return deserializer.deserialize_any(self);
+        }
+
+        let type_name = self.target.type_name().to_string();
+        let reflect_deserialize = self
+            .registry
+            .get_with_name(&type_name)
+            .and_then(|registration| registration.data::<ReflectDeserialize>())
+            .ok_or_else(|| {
+                de::Error::custom(SettingsError::NotDeserializable(type_name.clone()))
+            })?;
+        let value = reflect_deserialize.deserialize(deserializer)?;
+        self.target.apply(&*value);
+        Ok(())
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ApplySeed<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "fields of {}", self.target.type_name())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let type_name = self.target.type_name().to_string();
+        let target = match self.target.reflect_mut() {
+            ReflectMut::Struct(target) => target,
+            _ => unreachable!("ApplySeed only visits maps for structs"),
+        };
+        while let Some(FieldName(name)) = map.next_key()? {
+            let field = target.field_mut(&name).ok_or_else(|| {
+                de::Error::custom(format!(
+                    "{} does not have a field named {}",
+                    type_name, name
+                ))
+            })?;
+            map.next_value_seed(ApplySeed {
+                target: field,
+                registry: self.registry,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// The name of a struct field. RON only deserializes struct keys as identifiers.
+struct FieldName(String);
+
+impl<'de> de::Deserialize<'de> for FieldName {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldNameVisitor;
+
+        impl<'de> Visitor<'de> for FieldNameVisitor {
+            type Value = FieldName;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("field name")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<FieldName, E> {
+                Ok(FieldName(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldNameVisitor)
+    }
+}
+
+/// Loads [Settings] and applies them to reflected resources as they are initialized with
+/// [AppBuilder::init_settings]. Add this plugin before the plugins it should configure.
+///
+/// By default, settings are only read from environment variables starting with
+/// `BEVY_SETTINGS__`. Use [SettingsPlugin::new] to read a file first, and
+/// [SettingsPlugin::with_args] to read `--set` command-line arguments last.
+pub struct SettingsPlugin {
+    pub path: Option<PathBuf>,
+    pub env_prefix: Option<String>,
+    pub read_args: bool,
+}
+
+impl Default for SettingsPlugin {
+    fn default() -> Self {
+        SettingsPlugin {
+            path: None,
+            env_prefix: Some("BEVY_SETTINGS".to_string()),
+            read_args: false,
+        }
+    }
+}
+
+impl SettingsPlugin {
+    /// Reads settings from the file at `path` if it exists, followed by the environment
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        SettingsPlugin {
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Also reads settings from `--set` command-line arguments, which override the others
+    pub fn with_args(mut self) -> Self {
+        self.read_args = true;
+        self
+    }
+
+    fn load(&self) -> Result<Settings, SettingsError> {
+        let mut settings = Settings::default();
+        if let Some(path) = &self.path {
+            if path.exists() {
+                settings.add_file(path)?;
+            } else {
+                debug!("settings file {} does not exist", path.display());
+            }
+        }
+        if let Some(env_prefix) = &self.env_prefix {
+            settings.add_env(env_prefix)?;
+        }
+        if self.read_args {
+            settings.add_args(std::env::args().skip(1))?;
+        }
+        Ok(settings)
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let settings = self.load().unwrap_or_else(|err| panic!("{}", err));
+
+        // settings are usually applied before CorePlugin registers these
+        app.register_type::<bool>()
+            .register_type::<u8>()
+            .register_type::<u16>()
+            .register_type::<u32>()
+            .register_type::<u64>()
+            .register_type::<usize>()
+            .register_type::<i8>()
+            .register_type::<i16>()
+            .register_type::<i32>()
+            .register_type::<i64>()
+            .register_type::<isize>()
+            .register_type::<f32>()
+            .register_type::<f64>()
+            .register_type::<String>()
+            .register_type::<Option<String>>();
+
+        // apply settings to resources that were registered before this plugin was added
+        let registry = app
+            .world()
+            .get_resource::<TypeRegistryArc>()
+            .unwrap()
+            .clone();
+        let registry = registry.read();
+        for registration in registry.iter() {
+            settings
+                .apply(app.world_mut(), &registry, registration)
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
+        drop(registry);
+
+        app.insert_resource(settings);
+    }
+}
+
+impl AppBuilder {
+    /// Registers the resource `T` and initializes it with [FromWorld] if it does not exist. If a
+    /// [SettingsPlugin] was added, its [Settings] are then applied to `T`. Plugins should call
+    /// this before reading their settings resources.
+    pub fn init_settings<T>(&mut self) -> &mut Self
+    where
+        T: Component + Reflect + FromWorld + GetTypeRegistration,
+    {
+        self.register_type::<T>().init_resource::<T>();
+
+        let world = self.world_mut();
+        let settings = match world.remove_resource::<Settings>() {
+            Some(settings) => settings,
+            None => return self,
+        };
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let result = {
+            let registry = registry.read();
+            let registration = registry.get(std::any::TypeId::of::<T>()).unwrap();
+            settings.apply(world, &registry, registration)
+        };
+        world.insert_resource(settings);
+        if let Err(err) = result {
+            panic!("{}", err);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+    use crate::AppBuilder;
+    use bevy_ecs::reflect::ReflectResource;
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct WindowSettings {
+        title: String,
+        width: f32,
+        vsync: bool,
+        size: Size,
+    }
+
+    #[derive(Reflect, Debug, PartialEq, Default)]
+    struct Size {
+        x: u32,
+        y: u32,
+    }
+
+    impl Default for WindowSettings {
+        fn default() -> Self {
+            WindowSettings {
+                title: "bevy".to_string(),
+                width: 1280.0,
+                vsync: true,
+                size: Size { x: 1, y: 2 },
+            }
+        }
+    }
+
+    fn app_with(settings: Settings) -> AppBuilder {
+        let mut app = AppBuilder::empty();
+        app.init_resource::<TypeRegistryArc>()
+            .register_type::<bool>()
+            .register_type::<u32>()
+            .register_type::<f32>()
+            .register_type::<String>()
+            .insert_resource(settings)
+            .init_settings::<WindowSettings>();
+        app
+    }
+
+    #[test]
+    fn layered_settings() {
+        let mut settings = Settings::default();
+        settings
+            .add_ron(
+                "settings.ron",
+                r#"{
+                    "WindowSettings": (title: "from file", width: 800.0, size: (x: 10)),
+                    "Unrelated": (value: 1),
+                }"#,
+            )
+            .unwrap()
+            .add_env_vars(
+                "TEST",
+                vec![
+                    (
+                        "TEST__WindowSettings__vsync".to_string(),
+                        "false".to_string(),
+                    ),
+                    (
+                        "TEST__WindowSettings__width".to_string(),
+                        "640.0".to_string(),
+                    ),
+                    (
+                        "OTHER__WindowSettings__width".to_string(),
+                        "1.0".to_string(),
+                    ),
+                ],
+            )
+            .unwrap()
+            .add_args(
+                vec![
+                    "--verbose",
+                    "--set",
+                    "WindowSettings.title=from args",
+                    "--set=WindowSettings.size.y=20",
+                ]
+                .into_iter()
+                .map(String::from),
+            )
+            .unwrap();
+
+        let mut app = app_with(settings);
+        assert_eq!(
+            app.world().get_resource::<WindowSettings>().unwrap(),
+            &WindowSettings {
+                title: "from args".to_string(),
+                width: 640.0,
+                vsync: false,
+                size: Size { x: 10, y: 20 },
+            }
+        );
+    }
+
+    #[test]
+    fn no_settings() {
+        let mut app = app_with(Settings::default());
+        assert_eq!(
+            app.world().get_resource::<WindowSettings>().unwrap(),
+            &WindowSettings::default()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not exist")]
+    fn unknown_field() {
+        let mut settings = Settings::default();
+        settings
+            .add_override("WindowSettings.height", "1.0")
+            .unwrap();
+        app_with(settings);
+    }
+
+    #[test]
+    fn invalid_syntax() {
+        let mut settings = Settings::default();
+        assert!(settings.add_ron("settings.ron", "{ \"a\": ( }").is_err());
+        assert!(settings
+            .add_args(vec!["--set".to_string(), "WindowSettings".to_string()])
+            .is_err());
+    }
+}
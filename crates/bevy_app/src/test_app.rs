@@ -0,0 +1,329 @@
+use crate::{app::App, app_builder::AppBuilder, event::Events};
+use bevy_ecs::{
+    component::Component,
+    query::{FilterFetch, WorldQuery},
+    world::World,
+};
+use bevy_utils::{Duration, Instant};
+use std::fmt::Debug;
+
+/// A clock that only moves forward when it is explicitly advanced.
+///
+/// When this resource is present, `bevy_core`'s `Time` is updated from it instead of the system
+/// clock, which makes time deterministic. [TestApp] inserts one automatically.
+#[derive(Debug, Clone, Copy)]
+pub struct ManualClock {
+    startup: Instant,
+    now: Instant,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        let now = Instant::now();
+        ManualClock { startup: now, now }
+    }
+}
+
+impl ManualClock {
+    /// The [Instant] the clock was created
+    #[inline]
+    pub fn startup(&self) -> Instant {
+        self.startup
+    }
+
+    /// The current [Instant] of the clock
+    #[inline]
+    pub fn now(&self) -> Instant {
+        self.now
+    }
+
+    /// The total [Duration] the clock has been advanced by
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.now - self.startup
+    }
+
+    /// Moves the clock forward by `duration`
+    pub fn advance(&mut self, duration: Duration) {
+        self.now += duration;
+    }
+}
+
+/// Drives an [App] frame by frame for integration tests.
+///
+/// A [TestApp] never calls the app's runner, so it works with any runner, including
+/// [ScheduleRunnerPlugin](crate::ScheduleRunnerPlugin), and does not need a window. Each frame
+/// advances the app's [ManualClock] by a fixed frame time before updating the app, so time
+/// dependent systems behave the same on every run.
+///
+/// ```
+/// # use bevy_app::{prelude::*, TestApp};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// struct Counter(u32);
+///
+/// fn count(mut counter: ResMut<Counter>) {
+///     counter.0 += 1;
+/// }
+///
+/// let mut app = TestApp::new(
+///     App::build()
+///         .insert_resource(Counter(0))
+///         .add_system(count.system()),
+/// );
+/// app.update_n(3);
+/// app.assert_resource_eq(&Counter(3));
+/// ```
+pub struct TestApp {
+    pub app: App,
+    frame_time: Duration,
+    frame_count: usize,
+}
+
+impl TestApp {
+    /// The frame time used by [TestApp::new]: 60 frames per second
+    pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+    /// Takes the [App] out of `app_builder`, inserting a [ManualClock] if the app does not have
+    /// one yet. Like [AppBuilder::run], this panics if a plugin's dependencies were not added.
+    pub fn new(app_builder: &mut AppBuilder) -> TestApp {
+        if let Err(err) = app_builder.check_plugin_dependencies() {
+            panic!("{}", err);
+        }
+        let mut app = std::mem::take(&mut app_builder.app);
+        app.world.get_resource_or_insert_with(ManualClock::default);
+        TestApp {
+            app,
+            frame_time: Self::DEFAULT_FRAME_TIME,
+            frame_count: 0,
+        }
+    }
+
+    /// Sets how far the [ManualClock] is advanced before each frame
+    pub fn with_frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    #[inline]
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    #[inline]
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    /// The number of frames that have been run
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    #[inline]
+    pub fn frame_time(&self) -> Duration {
+        self.frame_time
+    }
+
+    /// The app's [ManualClock]
+    pub fn clock(&self) -> ManualClock {
+        *self
+            .app
+            .world
+            .get_resource::<ManualClock>()
+            .expect("ManualClock resource was removed from the TestApp")
+    }
+
+    /// Advances the [ManualClock] without running a frame, e.g. to simulate a hitch
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.app
+            .world
+            .get_resource_or_insert_with(ManualClock::default)
+            .advance(duration);
+    }
+
+    /// Advances the [ManualClock] by the frame time, then runs the app's schedule once
+    pub fn update(&mut self) {
+        self.advance_time(self.frame_time);
+        self.app.update();
+        self.frame_count += 1;
+    }
+
+    /// Runs `frames` frames
+    pub fn update_n(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.update();
+        }
+    }
+
+    /// Runs frames until `predicate` returns true after a frame, and returns the number of frames
+    /// that were run. This will panic if `predicate` is still false after `max_frames` frames,
+    /// so a broken test fails instead of hanging.
+    pub fn run_until(
+        &mut self,
+        max_frames: usize,
+        mut predicate: impl FnMut(&mut World) -> bool,
+    ) -> usize {
+        for frame in 1..=max_frames {
+            self.update();
+            if predicate(&mut self.app.world) {
+                return frame;
+            }
+        }
+        panic!("condition was not met within {} frames", max_frames);
+    }
+
+    /// Sends `event`. It will be visible to systems during the next frame.
+    pub fn send_event<T: Component>(&mut self, event: T) {
+        self.app
+            .world
+            .get_resource_mut::<Events<T>>()
+            .unwrap_or_else(|| panic!("Events<{}> is not registered", std::any::type_name::<T>()))
+            .send(event);
+    }
+
+    /// The events of type `T` that were sent during the last frame
+    pub fn events<T: Component>(&self) -> Vec<&T> {
+        self.app
+            .world
+            .get_resource::<Events<T>>()
+            .unwrap_or_else(|| panic!("Events<{}> is not registered", std::any::type_name::<T>()))
+            .iter_current_update_events()
+            .collect()
+    }
+
+    pub fn assert_event_count<T: Component>(&self, expected: usize) {
+        let count = self.events::<T>().len();
+        assert_eq!(
+            count,
+            expected,
+            "expected {} {} event(s) during frame {}, found {}",
+            expected,
+            std::any::type_name::<T>(),
+            self.frame_count,
+            count
+        );
+    }
+
+    pub fn assert_event_sent<T: Component + PartialEq + Debug>(&self, expected: &T) {
+        let events = self.events::<T>();
+        assert!(
+            events.contains(&expected),
+            "expected {:?} to be sent during frame {}, found {:?}",
+            expected,
+            self.frame_count,
+            events
+        );
+    }
+
+    pub fn assert_resource_eq<T: Component + PartialEq + Debug>(&self, expected: &T) {
+        let resource =
+            self.app.world.get_resource::<T>().unwrap_or_else(|| {
+                panic!("resource {} does not exist", std::any::type_name::<T>())
+            });
+        assert_eq!(resource, expected);
+    }
+
+    /// The number of entities matching the query `Q` with the filter `F`
+    pub fn query_count<Q: WorldQuery, F: WorldQuery>(&mut self) -> usize
+    where
+        F::Fetch: FilterFetch,
+    {
+        let world = &mut self.app.world;
+        let mut query = world.query_filtered::<Q, F>();
+        query.iter_mut(world).count()
+    }
+
+    pub fn assert_query_count<Q: WorldQuery, F: WorldQuery>(&mut self, expected: usize)
+    where
+        F::Fetch: FilterFetch,
+    {
+        let count = self.query_count::<Q, F>();
+        assert_eq!(
+            count,
+            expected,
+            "expected {} entities matching {}, found {}",
+            expected,
+            std::any::type_name::<(Q, F)>(),
+            count
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ManualClock, TestApp};
+    use crate::{prelude::*, Events};
+    use bevy_ecs::prelude::*;
+    use bevy_utils::Duration;
+
+    #[derive(Debug, PartialEq)]
+    struct Ping(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Pong(u32);
+
+    struct Marker;
+
+    fn pong_system(mut pings: EventReader<Ping>, mut pongs: EventWriter<Pong>) {
+        for ping in pings.iter() {
+            pongs.send(Pong(ping.0));
+        }
+    }
+
+    fn spawn_system(mut commands: Commands) {
+        commands.spawn().insert(Marker);
+    }
+
+    fn test_app() -> TestApp {
+        TestApp::new(
+            App::build()
+                .add_event::<Ping>()
+                .add_event::<Pong>()
+                .add_system(pong_system.system())
+                .add_system(spawn_system.system()),
+        )
+        .with_frame_time(Duration::from_millis(10))
+    }
+
+    #[test]
+    fn manual_clock() {
+        let mut app = test_app();
+        app.update_n(3);
+        assert_eq!(app.frame_count(), 3);
+        assert_eq!(app.clock().elapsed(), Duration::from_millis(30));
+        app.advance_time(Duration::from_millis(5));
+        assert_eq!(app.clock().elapsed(), Duration::from_millis(35));
+        assert!(app.world().get_resource::<ManualClock>().is_some());
+    }
+
+    #[test]
+    fn events() {
+        let mut app = test_app();
+        app.send_event(Ping(1));
+        app.update();
+        app.assert_event_count::<Pong>(1);
+        app.assert_event_sent(&Pong(1));
+        app.update();
+        app.assert_event_count::<Pong>(0);
+        assert!(app.world().get_resource::<Events<Pong>>().is_some());
+    }
+
+    #[test]
+    fn run_until() {
+        let mut app = test_app();
+        let frames = app.run_until(10, |world| {
+            world.query::<&Marker>().iter(world).count() == 4
+        });
+        assert_eq!(frames, 4);
+        app.assert_query_count::<&Marker, ()>(4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn run_until_max_frames() {
+        let mut app = test_app();
+        app.run_until(3, |_| false);
+    }
+}
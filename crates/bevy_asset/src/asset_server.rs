@@ -1,19 +1,33 @@
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetProcessor, AssetSaver, AssetSaverDynamic, Assets, Handle, HandleId,
+    HandleUntyped, LabelId, LoadContext, LoadSettings, LoadSettingsDeserializer, LoadState,
+    ProcessedAssetIo, RefChange, RefChangeChannel, SaveContext, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::Res;
 use bevy_log::warn;
-use bevy_tasks::TaskPool;
+use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_tasks::{Task, TaskPool};
 use bevy_utils::{HashMap, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::RwLock;
-use std::{collections::hash_map::Entry, path::Path, sync::Arc};
+use serde::de::DeserializeSeed;
+use std::{
+    collections::hash_map::Entry,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use thiserror::Error;
 
+/// Meta files are not read by default on wasm, where looking for the meta file of each asset is
+/// an extra HTTP request, most of them failing
+pub(crate) const META_FILES_DEFAULT: bool = cfg!(not(target_arch = "wasm32"));
+
 /// Errors that occur while loading assets with an AssetServer
 #[derive(Error, Debug)]
 pub enum AssetServerError {
@@ -21,12 +35,33 @@ pub enum AssetServerError {
     AssetFolderNotADirectory(String),
     #[error("no `AssetLoader` found{}", format_missing_asset_ext(.extensions))]
     MissingAssetLoader { extensions: Vec<String> },
+    #[error("no `AssetLoader` named {0}")]
+    MissingAssetLoaderName(String),
+    #[error("failed to parse the meta file {path}: {error}")]
+    InvalidMeta { path: PathBuf, error: ron::Error },
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
     #[error("encountered an error while loading an asset: {0}")]
     AssetLoaderError(anyhow::Error),
-    #[error("encountered an error while reading an asset: {0}")]
+    #[error("encountered an error while reading or writing an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+    #[error("asset processing is disabled, set `AssetServerSettings::processed_asset_folder` to enable it")]
+    AssetProcessingDisabled,
+    #[error("no `AssetSaver` of {type_name} assets found{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver {
+        type_name: &'static str,
+        extensions: Vec<String>,
+    },
+    #[error("the `AssetIo` of the asset server can't save assets")]
+    ReadOnlyAssetIo,
+    #[error("there is no asset for the handle {0:?}")]
+    MissingAsset(HandleId),
+    #[error("encountered an error while saving an asset: {0}")]
+    AssetSaverError(anyhow::Error),
+    #[error("the asset server was dropped before the asset was saved")]
+    SaveCancelled,
+    #[error("{0} assets can't be saved because they were not added to the app with `add_asset`")]
+    UnregisteredAssetType(&'static str),
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -53,11 +88,28 @@ pub struct AssetServerInternal {
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
+    loader_type_names: RwLock<Vec<&'static str>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    /// Settings passed to [AssetServer::load_with_settings], which replace the settings from
+    /// meta files
+    load_settings: RwLock<HashMap<SourcePathId, Box<dyn Reflect>>>,
+    type_registry: RwLock<TypeRegistryArc>,
+    /// Whether assets are loaded with the settings of their meta files
+    meta_files: AtomicBool,
+    savers: RwLock<HashMap<Uuid, Vec<Arc<dyn AssetSaverDynamic>>>>,
+    save_requests: RwLock<HashMap<Uuid, Vec<SaveRequest>>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
     task_pool: TaskPool,
 }
 
+/// An asset waiting to be serialized by the [AssetSaver] of its type
+struct SaveRequest {
+    handle: HandleUntyped,
+    path: PathBuf,
+    saver: Arc<dyn AssetSaverDynamic>,
+    sender: async_channel::Sender<Result<(), AssetServerError>>,
+}
+
 /// Loads assets from the filesystem on background threads
 pub struct AssetServer {
     pub(crate) server: Arc<AssetServerInternal>,
@@ -80,7 +132,13 @@ impl AssetServer {
         AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
+                loader_type_names: Default::default(),
                 extension_to_loader_index: Default::default(),
+                load_settings: Default::default(),
+                type_registry: Default::default(),
+                meta_files: AtomicBool::new(META_FILES_DEFAULT),
+                savers: Default::default(),
+                save_requests: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
@@ -112,6 +170,66 @@ impl AssetServer {
                 .insert(extension.to_string(), loader_index);
         }
         loaders.push(Arc::new(Box::new(loader)));
+        self.server
+            .loader_type_names
+            .write()
+            .push(std::any::type_name::<T>());
+    }
+
+    /// Sets the registry used to deserialize the settings in meta files. [AssetPlugin](crate::AssetPlugin)
+    /// sets it to the registry of the app.
+    pub fn set_type_registry(&self, type_registry: TypeRegistryArc) {
+        *self.server.type_registry.write() = type_registry;
+    }
+
+    /// Sets whether assets are loaded with the settings of their meta files. This is on by
+    /// default, except on wasm. [AssetPlugin](crate::AssetPlugin) sets it from
+    /// [AssetServerSettings::meta_files](crate::AssetServerSettings::meta_files).
+    pub fn set_meta_files(&self, enabled: bool) {
+        self.server.meta_files.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Registers an [AssetSaver]. It replaces savers registered before it for the same asset type
+    /// and extensions.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        self.server
+            .savers
+            .write()
+            .entry(T::Asset::TYPE_UUID)
+            .or_default()
+            .push(Arc::new(saver));
+    }
+
+    /// Registers an [AssetProcessor]. It is only used if
+    /// [AssetServerSettings::processed_asset_folder](crate::AssetServerSettings::processed_asset_folder)
+    /// is set.
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        match self.server.asset_io.downcast_ref::<ProcessedAssetIo>() {
+            Some(asset_io) => asset_io.add_processor(processor),
+            None => warn!(
+                "`{}` is not used: {}",
+                std::any::type_name::<T>(),
+                AssetServerError::AssetProcessingDisabled
+            ),
+        }
+    }
+
+    /// Processes all assets in a folder that have an [AssetProcessor], for example to prepare the
+    /// processed asset folder for shipping. Assets are otherwise processed when they are loaded.
+    pub async fn process_folder<P: AsRef<Path>>(&self, path: P) -> Result<(), AssetServerError> {
+        let asset_io = self
+            .server
+            .asset_io
+            .downcast_ref::<ProcessedAssetIo>()
+            .ok_or(AssetServerError::AssetProcessingDisabled)?;
+        asset_io.process_folder(path.as_ref()).await?;
+        Ok(())
     }
 
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
@@ -143,6 +261,24 @@ impl AssetServer {
             })
     }
 
+    /// Finds a loader by its type name, or by its short type name without the module path
+    fn get_asset_loader_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Arc<Box<dyn AssetLoader>>, AssetServerError> {
+        let loader_type_names = self.server.loader_type_names.read();
+        let index = loader_type_names
+            .iter()
+            .position(|type_name| *type_name == name)
+            .or_else(|| {
+                loader_type_names
+                    .iter()
+                    .position(|type_name| type_name.rsplit("::").next() == Some(name))
+            })
+            .ok_or_else(|| AssetServerError::MissingAssetLoaderName(name.to_string()))?;
+        Ok(self.server.loaders.read()[index].clone())
+    }
+
     fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
@@ -173,6 +309,37 @@ impl AssetServer {
         })
     }
 
+    fn get_path_asset_saver<T: Asset>(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn AssetSaverDynamic>, AssetServerError> {
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|file_name| file_name.to_lowercase())
+            .unwrap_or_default();
+        let savers = self.server.savers.read();
+        let savers = savers.get(&T::TYPE_UUID).map_or(&[][..], Vec::as_slice);
+
+        let mut exts = Vec::new();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            exts.push(ext);
+            if let Some(saver) = savers
+                .iter()
+                .rev()
+                .find(|saver| saver.extensions().contains(&ext))
+            {
+                return Ok(saver.clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver {
+            type_name: std::any::type_name::<T>(),
+            extensions: exts.into_iter().map(String::from).collect(),
+        })
+    }
+
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
             .handle_to_path
@@ -216,6 +383,72 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
+    /// Loads the asset at `path` with `settings` for its loader, in place of the settings in its
+    /// meta file. The settings are kept when the asset is reloaded. If the asset is already
+    /// loaded, it is loaded again with the new settings. See [LoadContext::settings].
+    pub fn load_with_settings<'a, T: Asset, P: Into<AssetPath<'a>>, S: Reflect>(
+        &self,
+        path: P,
+        settings: S,
+    ) -> Handle<T> {
+        let asset_path: AssetPath = path.into();
+        self.server
+            .load_settings
+            .write()
+            .insert(asset_path.get_id().source_path_id(), Box::new(settings));
+        let handle_id = self.load_untracked(asset_path, true);
+        self.get_handle(handle_id)
+    }
+
+    /// Reads the meta file of the asset at `path` if meta files are enabled, and replaces its
+    /// settings with the ones passed to [AssetServer::load_with_settings]
+    async fn get_load_settings(
+        &self,
+        path: &Path,
+        source_path_id: SourcePathId,
+    ) -> Result<LoadSettings, AssetServerError> {
+        let meta_path = LoadSettings::meta_path(path);
+        let meta_bytes = if self.server.meta_files.load(Ordering::Relaxed) {
+            self.server.asset_io.load_path(&meta_path).await
+        } else {
+            Err(AssetIoError::NotFound(meta_path.clone()))
+        };
+        let mut load_settings = match meta_bytes {
+            Ok(bytes) => {
+                let extension_loader = self.get_path_asset_loader(path).ok();
+                let default_settings = |loader: Option<&str>| {
+                    match loader {
+                        Some(name) => self.get_asset_loader_by_name(name).ok(),
+                        None => extension_loader.clone(),
+                    }
+                    .and_then(|loader| loader.default_settings())
+                };
+                let type_registry = self.server.type_registry.read().clone();
+                let type_registry = type_registry.read();
+                let parse = || -> Result<LoadSettings, ron::Error> {
+                    let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+                    let load_settings = LoadSettingsDeserializer {
+                        registry: &type_registry,
+                        default_settings: &default_settings,
+                    }
+                    .deserialize(&mut deserializer)?;
+                    deserializer.end()?;
+                    Ok(load_settings)
+                };
+                parse().map_err(|error| AssetServerError::InvalidMeta {
+                    path: meta_path,
+                    error,
+                })?
+            }
+            Err(AssetIoError::NotFound(_)) => LoadSettings::default(),
+            Err(err) => return Err(err.into()),
+        };
+        if let Some(settings) = self.server.load_settings.read().get(&source_path_id) {
+            load_settings.settings = Some(settings.clone_value());
+        }
+        Ok(load_settings)
+    }
+
     // TODO: properly set failed LoadState in all failure cases
     async fn load_async<'a, P: Into<AssetPath<'a>>>(
         &self,
@@ -223,7 +456,6 @@ impl AssetServer {
         force: bool,
     ) -> Result<AssetPathId, AssetServerError> {
         let asset_path: AssetPath = path.into();
-        let asset_loader = self.get_path_asset_loader(asset_path.path())?;
         let asset_path_id: AssetPathId = asset_path.get_id();
 
         // load metadata and update source info. this is done in a scope to ensure we release the
@@ -258,15 +490,38 @@ impl AssetServer {
             source_info.version
         };
 
+        let set_failed = || {
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .get_mut(&asset_path_id.source_path_id())
+                .expect("`AssetSource` should exist at this point.");
+            source_info.load_state = LoadState::Failed;
+        };
+
+        // pick the loader and its settings
+        let loader_and_settings = async {
+            let load_settings = self
+                .get_load_settings(asset_path.path(), asset_path_id.source_path_id())
+                .await?;
+            let asset_loader = match &load_settings.loader {
+                Some(name) => self.get_asset_loader_by_name(name)?,
+                None => self.get_path_asset_loader(asset_path.path())?,
+            };
+            Ok::<_, AssetServerError>((load_settings, asset_loader))
+        };
+        let (load_settings, asset_loader) = match loader_and_settings.await {
+            Ok(loader_and_settings) => loader_and_settings,
+            Err(err) => {
+                set_failed();
+                return Err(err);
+            }
+        };
+
         // load the asset bytes
         let bytes = match self.server.asset_io.load_path(asset_path.path()).await {
             Ok(bytes) => bytes,
             Err(err) => {
-                let mut asset_sources = self.server.asset_sources.write();
-                let source_info = asset_sources
-                    .get_mut(&asset_path_id.source_path_id())
-                    .expect("`AssetSource` should exist at this point.");
-                source_info.load_state = LoadState::Failed;
+                set_failed();
                 return Err(AssetServerError::AssetIoError(err));
             }
         };
@@ -277,6 +532,7 @@ impl AssetServer {
             &self.server.asset_ref_counter.channel,
             &*self.server.asset_io,
             version,
+            load_settings.settings,
         );
         asset_loader
             .load(&bytes, &mut load_context)
@@ -446,6 +702,94 @@ impl AssetServer {
         }
     }
 
+    /// Saves the asset of `handle` to `path`, relative to the asset folder, with the [AssetSaver]
+    /// registered for its type and the extension of `path`. The asset is serialized the next time
+    /// [AssetStage::AssetEvents](crate::AssetStage::AssetEvents) runs and then written in the
+    /// background. The returned task completes once the asset is written.
+    pub fn save<T: Asset, P: AsRef<Path>>(
+        &self,
+        handle: &Handle<T>,
+        path: P,
+    ) -> Task<Result<(), AssetServerError>> {
+        let path = path.as_ref();
+        let (sender, receiver) = async_channel::bounded(1);
+        // requests are only handled by the `save_assets_system` of registered asset types
+        let saver = if !self
+            .server
+            .asset_lifecycles
+            .read()
+            .contains_key(&T::TYPE_UUID)
+        {
+            Err(AssetServerError::UnregisteredAssetType(
+                std::any::type_name::<T>(),
+            ))
+        } else if self.server.asset_io.as_writable().is_none() {
+            Err(AssetServerError::ReadOnlyAssetIo)
+        } else {
+            self.get_path_asset_saver::<T>(path)
+        };
+        match saver {
+            Ok(saver) => self
+                .server
+                .save_requests
+                .write()
+                .entry(T::TYPE_UUID)
+                .or_default()
+                .push(SaveRequest {
+                    handle: handle.clone_untyped(),
+                    path: path.to_owned(),
+                    saver,
+                    sender,
+                }),
+            Err(err) => {
+                let _ = sender.try_send(Err(err));
+            }
+        }
+        self.server.task_pool.spawn(async move {
+            receiver
+                .recv()
+                .await
+                .unwrap_or(Err(AssetServerError::SaveCancelled))
+        })
+    }
+
+    pub(crate) fn save_assets<T: Asset>(&self, assets: &Assets<T>) {
+        // this runs every frame, so only take the write lock when there are requests
+        if !self.server.save_requests.read().contains_key(&T::TYPE_UUID) {
+            return;
+        }
+        let requests = match self.server.save_requests.write().remove(&T::TYPE_UUID) {
+            Some(requests) => requests,
+            None => return,
+        };
+        for request in requests {
+            let bytes = match assets.get(&request.handle) {
+                Some(asset) => request
+                    .saver
+                    .save_dynamic(asset, &SaveContext::new(&request.path))
+                    .map_err(AssetServerError::AssetSaverError),
+                None => Err(AssetServerError::MissingAsset(request.handle.id)),
+            };
+            let server = self.server.clone();
+            self.server
+                .task_pool
+                .spawn(async move {
+                    let result = match bytes {
+                        Ok(bytes) => server
+                            .asset_io
+                            .as_writable()
+                            .expect("`AssetIo` should be writable at this point.")
+                            .save_path(&request.path, &bytes)
+                            .await
+                            .map_err(AssetServerError::from),
+                        Err(err) => Err(err),
+                    };
+                    let _ = request.sender.send(result).await;
+                })
+                .detach();
+        }
+    }
+
     pub(crate) fn update_asset_storage<T: Asset>(&self, assets: &mut Assets<T>) {
         let asset_lifecycles = self.server.asset_lifecycles.read();
         let asset_lifecycle = asset_lifecycles.get(&T::TYPE_UUID).unwrap();
@@ -540,7 +884,13 @@ mod test {
         let asset_server = AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
+                loader_type_names: Default::default(),
                 extension_to_loader_index: Default::default(),
+                load_settings: Default::default(),
+                type_registry: Default::default(),
+                meta_files: AtomicBool::new(META_FILES_DEFAULT),
+                savers: Default::default(),
+                save_requests: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
@@ -623,4 +973,149 @@ mod test {
         let t = asset_server.get_path_asset_loader("test.test.png");
         assert_eq!(t.unwrap().extensions()[0], "test.png");
     }
+
+    #[derive(bevy_reflect::TypeUuid)]
+    #[uuid = "a4c3f0f6-6d1a-4b8e-9a35-2f7d0c1e8b52"]
+    struct Text(String);
+
+    #[derive(Reflect, Default)]
+    struct TextSettings {
+        uppercase: bool,
+    }
+
+    struct TextLoader;
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let mut text = String::from_utf8(bytes.to_vec())?;
+                if load_context.settings::<TextSettings>().uppercase {
+                    text = text.to_uppercase();
+                }
+                load_context.set_default_asset(crate::LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn default_settings(&self) -> Option<Box<dyn Reflect>> {
+            Some(Box::new(TextSettings::default()))
+        }
+    }
+
+    #[test]
+    fn load_settings() {
+        use crate::FileAssetIo;
+        use futures_lite::future;
+        use std::fs;
+
+        let root = std::env::temp_dir().join(format!("bevy_asset_meta_{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("plain.txt"), "hello").unwrap();
+        fs::write(root.join("meta.txt"), "hello").unwrap();
+        fs::write(root.join("meta.txt.meta"), "(settings: (uppercase: true))").unwrap();
+        fs::write(root.join("named.text"), "hello").unwrap();
+        fs::write(root.join("named.text.meta"), "(loader: \"TextLoader\")").unwrap();
+        fs::write(root.join("invalid.txt"), "hello").unwrap();
+        fs::write(
+            root.join("invalid.txt.meta"),
+            "(settings: (lowercase: true))",
+        )
+        .unwrap();
+
+        let asset_server = AssetServer::new(FileAssetIo::new(&root), Default::default());
+        asset_server.add_loader(TextLoader);
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<bool>();
+        type_registry.write().register::<TextSettings>();
+        asset_server.set_type_registry(type_registry);
+
+        let mut texts = asset_server.register_asset_type::<Text>();
+        let mut load = |path: &str| {
+            future::block_on(asset_server.load_async(path, true))?;
+            asset_server.update_asset_storage(&mut texts);
+            Ok::<_, AssetServerError>(texts.get(path).unwrap().0.clone())
+        };
+
+        assert_eq!(load("plain.txt").unwrap(), "hello");
+        assert_eq!(load("meta.txt").unwrap(), "HELLO");
+        assert_eq!(load("named.text").unwrap(), "hello");
+        assert!(matches!(
+            load("invalid.txt"),
+            Err(AssetServerError::InvalidMeta { .. })
+        ));
+
+        // explicit settings replace the ones from the meta file
+        let _handle: Handle<Text> =
+            asset_server.load_with_settings("meta.txt", TextSettings { uppercase: false });
+        assert_eq!(load("meta.txt").unwrap(), "hello");
+
+        // meta files are not read when they are disabled
+        asset_server.set_meta_files(false);
+        assert_eq!(load("invalid.txt").unwrap(), "hello");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    struct TextSaver;
+    impl AssetSaver for TextSaver {
+        type Asset = Text;
+
+        fn save(&self, asset: &Text, _: &SaveContext) -> Result<Vec<u8>, anyhow::Error> {
+            Ok(asset.0.as_bytes().to_vec())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn save() {
+        use crate::FileAssetIo;
+        use futures_lite::future;
+        use std::fs;
+
+        let root = std::env::temp_dir().join(format!("bevy_asset_save_{}", std::process::id()));
+        let asset_server = AssetServer::new(FileAssetIo::new(&root), Default::default());
+        asset_server.add_saver(TextSaver);
+        let mut texts = asset_server.register_asset_type::<Text>();
+        let handle = texts.add(Text("hello".to_string()));
+
+        let saved = asset_server.save(&handle, "text/hello.txt");
+        let missing_saver = asset_server.save(&handle, "text/hello.bin");
+        asset_server.save_assets(&texts);
+        future::block_on(saved).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("text/hello.txt")).unwrap(),
+            "hello"
+        );
+        assert!(matches!(
+            future::block_on(missing_saver),
+            Err(AssetServerError::MissingAssetSaver { .. })
+        ));
+
+        let missing_asset = asset_server.save(&Handle::<Text>::default(), "missing.txt");
+        asset_server.save_assets(&texts);
+        assert!(matches!(
+            future::block_on(missing_asset),
+            Err(AssetServerError::MissingAsset(_))
+        ));
+
+        // saving fails right away if the asset type was not registered
+        let unregistered = AssetServer::new(FileAssetIo::new(&root), Default::default());
+        unregistered.add_saver(TextSaver);
+        assert!(matches!(
+            future::block_on(unregistered.save(&handle, "text/other.txt")),
+            Err(AssetServerError::UnregisteredAssetType(_))
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
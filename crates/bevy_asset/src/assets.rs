@@ -1,6 +1,6 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange,
+    save_assets_system, update_asset_storage_system, Asset, AssetLoader, AssetProcessor,
+    AssetSaver, AssetServer, AssetStage, Handle, HandleId, RefChange,
 };
 use bevy_app::{AppBuilder, EventWriter, Events};
 use bevy_ecs::{
@@ -200,6 +200,18 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld;
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
 }
 
 impl AddAsset for AppBuilder {
@@ -217,6 +229,7 @@ impl AddAsset for AppBuilder {
                 AssetStage::AssetEvents,
                 Assets::<T>::asset_event_system.system(),
             )
+            .add_system_to_stage(AssetStage::AssetEvents, save_assets_system::<T>.system())
             .add_system_to_stage(
                 AssetStage::LoadAssets,
                 update_asset_storage_system::<T>.system(),
@@ -243,4 +256,42 @@ impl AddAsset for AppBuilder {
             .add_loader(loader);
         self
     }
+
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld,
+    {
+        let result = T::from_world(self.world_mut());
+        self.add_asset_processor(result)
+    }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world_mut()
+            .get_resource_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_processor(processor);
+        self
+    }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(self.world_mut());
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world_mut()
+            .get_resource_mut::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_saver(saver);
+        self
+    }
 }
@@ -1,7 +1,14 @@
 use crate::{path::AssetPath, LabelId};
+use bevy_reflect::{Reflect, TypeRegistry};
 use bevy_utils::{HashMap, HashSet, Uuid};
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, Visitor},
+    Deserialize, Serialize,
+};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SourceMeta {
@@ -46,3 +53,127 @@ pub enum LoadState {
     Loaded,
     Failed,
 }
+
+/// How a source asset is loaded, read from the `.meta` file next to it, e.g.
+/// `textures/tree.png.meta` for `textures/tree.png`:
+/// ```ron
+/// (
+///     loader: "TreeTextureLoader",
+///     settings: (
+///         nearest_sampling: true,
+///     ),
+/// )
+/// ```
+/// Both fields are optional. `loader` is the type name, or the short type name, of the
+/// [AssetLoader](crate::AssetLoader) used instead of the one registered for the extension of the
+/// asset. It must come before `settings`, which overrides fields of the
+/// [AssetLoader::default_settings](crate::AssetLoader::default_settings) of the loader.
+#[derive(Debug, Default)]
+pub struct LoadSettings {
+    pub loader: Option<String>,
+    pub settings: Option<Box<dyn Reflect>>,
+}
+
+impl LoadSettings {
+    /// The extension appended to the path of an asset for its meta file
+    pub const META_EXTENSION: &'static str = "meta";
+
+    /// The path of the meta file of the asset at `path`
+    pub fn meta_path(path: &Path) -> PathBuf {
+        let mut meta_path = path.to_owned().into_os_string();
+        meta_path.push(".");
+        meta_path.push(Self::META_EXTENSION);
+        PathBuf::from(meta_path)
+    }
+}
+
+const LOAD_SETTINGS_STRUCT: &str = "LoadSettings";
+const LOAD_SETTINGS_FIELD_LOADER: &str = "loader";
+const LOAD_SETTINGS_FIELD_SETTINGS: &str = "settings";
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum LoadSettingsField {
+    Loader,
+    Settings,
+}
+
+/// Deserializes [LoadSettings]. `default_settings` returns the default settings of the loader with
+/// the given name, or of the loader for the extension of the asset if there is no name.
+pub(crate) struct LoadSettingsDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+    pub default_settings: &'a dyn Fn(Option<&str>) -> Option<Box<dyn Reflect>>,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for LoadSettingsDeserializer<'a> {
+    type Value = LoadSettings;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            LOAD_SETTINGS_STRUCT,
+            &[LOAD_SETTINGS_FIELD_LOADER, LOAD_SETTINGS_FIELD_SETTINGS],
+            self,
+        )
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for LoadSettingsDeserializer<'a> {
+    type Value = LoadSettings;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("asset load settings")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut load_settings = LoadSettings::default();
+        while let Some(key) = map.next_key()? {
+            match key {
+                LoadSettingsField::Loader => {
+                    if load_settings.loader.is_some() || load_settings.settings.is_some() {
+                        return Err(de::Error::custom(format!(
+                            "`{}` must come once, before `{}`",
+                            LOAD_SETTINGS_FIELD_LOADER, LOAD_SETTINGS_FIELD_SETTINGS
+                        )));
+                    }
+                    load_settings.loader = Some(map.next_value()?);
+                }
+                LoadSettingsField::Settings => {
+                    if load_settings.settings.is_some() {
+                        return Err(de::Error::duplicate_field(LOAD_SETTINGS_FIELD_SETTINGS));
+                    }
+                    let mut settings = (self.default_settings)(load_settings.loader.as_deref())
+                        .ok_or_else(|| de::Error::custom("the asset loader has no settings"))?;
+                    map.next_value_seed(SettingsSeed {
+                        target: &mut *settings,
+                        registry: self.registry,
+                    })?;
+                    load_settings.settings = Some(settings);
+                }
+            }
+        }
+        Ok(load_settings)
+    }
+}
+
+/// Applies the `settings` of a meta file to the default settings of the loader
+struct SettingsSeed<'a> {
+    target: &'a mut dyn Reflect,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SettingsSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        bevy_app::apply_deserialized(self.target, self.registry, deserializer)
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+use super::write_file;
+use crate::{
+    filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer, LoadSettings,
+    ProcessedAssetIo, WritableAssetIo,
+};
 use anyhow::Result;
 use bevy_ecs::system::Res;
 use bevy_utils::{BoxedFuture, HashSet};
@@ -102,6 +106,27 @@ impl AssetIo for FileAssetIo {
     fn is_directory(&self, path: &Path) -> bool {
         self.root_path.join(path).is_dir()
     }
+
+    fn as_writable(&self) -> Option<&dyn WritableAssetIo> {
+        Some(self)
+    }
+}
+
+impl WritableAssetIo for FileAssetIo {
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_file(&full_path, bytes)?;
+            Ok(())
+        })
+    }
 }
 
 #[cfg(all(
@@ -110,12 +135,17 @@ impl AssetIo for FileAssetIo {
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
+    let asset_io = &*asset_server.server.asset_io;
+    // source assets are watched, and processed again when they are reloaded
+    let asset_io = match asset_io.downcast_ref::<ProcessedAssetIo>() {
+        Some(processed_asset_io) => processed_asset_io.source(),
+        None => asset_io,
+    };
+    let asset_io = if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        asset_io
+    } else {
+        return;
+    };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -133,7 +163,15 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 for path in paths.iter() {
                     if !changed.contains(path) {
                         let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path, true);
+                        // a changed meta file reloads its asset
+                        let relative_path = if relative_path.extension()
+                            == Some(LoadSettings::META_EXTENSION.as_ref())
+                        {
+                            relative_path.with_extension("")
+                        } else {
+                            relative_path.to_owned()
+                        };
+                        let _ = asset_server.load_untracked(relative_path.as_path(), true);
                     }
                 }
                 changed.extend(paths);
@@ -2,6 +2,7 @@
 mod android_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
+mod processed_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
@@ -9,6 +10,7 @@ mod wasm_asset_io;
 pub use android_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
+pub use processed_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
@@ -30,6 +32,8 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+    #[error("failed to process asset {0}: {1}")]
+    ProcessError(PathBuf, anyhow::Error),
 }
 
 /// Handles load requests from an AssetServer
@@ -42,6 +46,39 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn is_directory(&self, path: &Path) -> bool;
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+    /// Returns this [AssetIo] as a [WritableAssetIo] if it can save assets
+    fn as_writable(&self) -> Option<&dyn WritableAssetIo> {
+        None
+    }
 }
 
 impl_downcast!(AssetIo);
+
+/// An [AssetIo] that can also write assets, used by [AssetServer::save](crate::AssetServer::save)
+pub trait WritableAssetIo: AssetIo {
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>>;
+}
+
+/// Creates the platform default [AssetIo] for the folder at `path`
+pub(crate) fn create_platform_asset_io<P: AsRef<Path>>(path: P) -> Box<dyn AssetIo> {
+    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+    let source = FileAssetIo::new(path);
+    #[cfg(target_arch = "wasm32")]
+    let source = WasmAssetIo::new(path);
+    #[cfg(target_os = "android")]
+    let source = AndroidAssetIo::new(path);
+
+    Box::new(source)
+}
+
+/// Writes to a temporary file that is then renamed, so readers never see a partially written file
+pub(crate) fn write_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temp_path = path.to_owned().into_os_string();
+    temp_path.push(format!(".{:x}.tmp", rand::random::<u64>()));
+    std::fs::write(&temp_path, contents)?;
+    std::fs::rename(&temp_path, path)
+}
@@ -0,0 +1,341 @@
+use super::write_file;
+use crate::{AssetIo, AssetIoError, AssetProcessor, ProcessContext, WritableAssetIo};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The inputs a processed asset was built from. A processed asset is rebuilt when they change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessedAssetInfo {
+    /// The 64 bit FNV-1a hash of the bytes of the source asset, which is the same on every
+    /// platform and with every compiler version
+    pub source_hash: u64,
+    /// The type name of the [AssetProcessor]
+    pub processor: String,
+    /// The [AssetProcessor::version] of the processor
+    pub processor_version: u32,
+}
+
+#[derive(Clone)]
+struct RegisteredProcessor {
+    name: &'static str,
+    processor: Arc<dyn AssetProcessor>,
+}
+
+/// An [AssetIo] that reads processed assets in place of their sources. When an asset that has an
+/// [AssetProcessor] is loaded, it is processed into the processed asset folder, unless the
+/// processed asset in that folder is up to date.
+///
+/// Platforms that can't write files (wasm and android) don't process assets, and read the
+/// processed assets that were shipped with the app instead, falling back to the source assets.
+/// Other platforms also read a processed asset without processing it if its source asset is
+/// missing, so builds can ship only the processed asset folder.
+pub struct ProcessedAssetIo {
+    source: Box<dyn AssetIo>,
+    processed: Box<dyn AssetIo>,
+    /// The folder processed assets are written to, if this platform can process assets
+    processed_root: Option<PathBuf>,
+    processors: RwLock<Vec<RegisteredProcessor>>,
+    extension_to_processor_index: RwLock<HashMap<String, usize>>,
+}
+
+impl ProcessedAssetIo {
+    /// The extension that is appended to the path of a processed asset for the file that stores
+    /// its [ProcessedAssetInfo]
+    pub const INFO_EXTENSION: &'static str = "processed.ron";
+
+    /// Reads source assets from `source`, and processed assets from `processed_folder`, which is
+    /// relative to the same root as the platform default asset folder
+    pub fn new<P: AsRef<Path>>(source: Box<dyn AssetIo>, processed_folder: P) -> Self {
+        let processed_folder = processed_folder.as_ref();
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        let processed_root = Some(crate::FileAssetIo::get_root_path().join(processed_folder));
+        #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+        let processed_root = None;
+
+        ProcessedAssetIo {
+            source,
+            processed: super::create_platform_asset_io(processed_folder),
+            processed_root,
+            processors: Default::default(),
+            extension_to_processor_index: Default::default(),
+        }
+    }
+
+    /// The [AssetIo] source assets are read from
+    pub fn source(&self) -> &dyn AssetIo {
+        &*self.source
+    }
+
+    pub fn add_processor<T: AssetProcessor>(&self, processor: T) {
+        let mut processors = self.processors.write();
+        let processor_index = processors.len();
+        for extension in processor.extensions().iter() {
+            self.extension_to_processor_index
+                .write()
+                .insert(extension.to_string(), processor_index);
+        }
+        processors.push(RegisteredProcessor {
+            name: std::any::type_name::<T>(),
+            processor: Arc::new(processor),
+        });
+    }
+
+    fn get_path_processor(&self, path: &Path) -> Option<RegisteredProcessor> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let extension_to_processor_index = self.extension_to_processor_index.read();
+        let mut extension = file_name.as_str();
+        while let Some(index) = extension.find('.') {
+            extension = &extension[index + 1..];
+            if let Some(index) = extension_to_processor_index.get(extension) {
+                return Some(self.processors.read()[*index].clone());
+            }
+        }
+        None
+    }
+
+    /// Processes the asset at `path` if it has an [AssetProcessor] and its processed asset is
+    /// missing or out of date. Returns whether there is a processed asset for `path`.
+    pub async fn process(&self, path: &Path) -> Result<bool, AssetIoError> {
+        match (self.get_path_processor(path), &self.processed_root) {
+            (Some(processor), Some(processed_root)) => {
+                self.process_with(path, &processor, processed_root).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Processes all assets in the source folder `path` and its subfolders, see
+    /// [ProcessedAssetIo::process]
+    pub async fn process_folder(&self, path: &Path) -> Result<(), AssetIoError> {
+        let mut paths = Vec::new();
+        self.collect_files(path, &mut paths)?;
+        for path in paths {
+            self.process(&path).await?;
+        }
+        Ok(())
+    }
+
+    fn collect_files(&self, path: &Path, paths: &mut Vec<PathBuf>) -> Result<(), AssetIoError> {
+        for child_path in self.source.read_directory(path)? {
+            if self.source.is_directory(&child_path) {
+                self.collect_files(&child_path, paths)?;
+            } else {
+                paths.push(child_path);
+            }
+        }
+        Ok(())
+    }
+
+    async fn process_with(
+        &self,
+        path: &Path,
+        processor: &RegisteredProcessor,
+        processed_root: &Path,
+    ) -> Result<(), AssetIoError> {
+        let output_path = processed_root.join(path);
+        let mut info_path = output_path.clone().into_os_string();
+        info_path.push(".");
+        info_path.push(Self::INFO_EXTENSION);
+        let info_path = PathBuf::from(info_path);
+
+        let bytes = match self.source.load_path(path).await {
+            Ok(bytes) => bytes,
+            // the source assets were not shipped, so the processed asset is used as it is
+            Err(AssetIoError::NotFound(_)) if output_path.is_file() && info_path.is_file() => {
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+        let info = ProcessedAssetInfo {
+            source_hash: fnv1a_hash(&bytes),
+            processor: processor.name.to_string(),
+            processor_version: processor.processor.version(),
+        };
+
+        let current_info = fs::read_to_string(&info_path)
+            .ok()
+            .and_then(|info| ron::from_str::<ProcessedAssetInfo>(&info).ok());
+        if output_path.is_file() && current_info.as_ref() == Some(&info) {
+            return Ok(());
+        }
+
+        let mut process_context = ProcessContext::new(path, &*self.source);
+        let output = processor
+            .processor
+            .process(&bytes, &mut process_context)
+            .await
+            .map_err(|error| AssetIoError::ProcessError(path.to_owned(), error))?;
+
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // the info is written last, so an interrupted write is redone on the next load
+        write_file(&output_path, &output)?;
+        let info = ron::ser::to_string_pretty(&info, Default::default())
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        write_file(&info_path, info.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// The 64 bit FNV-1a hash of `bytes`. Unlike the hashers of the standard library, its result
+/// never changes, so it can be stored in files.
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+impl AssetIo for ProcessedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            if let Some(processor) = self.get_path_processor(path) {
+                match &self.processed_root {
+                    Some(processed_root) => {
+                        self.process_with(path, &processor, processed_root).await?;
+                        return self.processed.load_path(path).await;
+                    }
+                    None => match self.processed.load_path(path).await {
+                        Err(AssetIoError::NotFound(_)) => {}
+                        result => return result,
+                    },
+                }
+            }
+            self.source.load_path(path).await
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.source.read_directory(path)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.source.is_directory(path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.source.watch_path_for_changes(path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.source.watch_for_changes()
+    }
+
+    /// Assets are saved to the source folder, and are processed again when they are loaded
+    fn as_writable(&self) -> Option<&dyn WritableAssetIo> {
+        self.source.as_writable()
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), not(target_os = "android")))]
+mod tests {
+    use super::{fnv1a_hash, ProcessedAssetIo};
+    use crate::{AssetIo, AssetProcessor, FileAssetIo, ProcessContext};
+    use bevy_utils::BoxedFuture;
+    use futures_lite::future;
+    use std::{
+        fs,
+        path::Path,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    struct UppercaseProcessor {
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _process_context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok(bytes.to_ascii_uppercase()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn version(&self) -> u32 {
+            1
+        }
+    }
+
+    #[test]
+    fn process_on_load() {
+        let root =
+            std::env::temp_dir().join(format!("bevy_asset_processor_{}", std::process::id()));
+        let source_root = root.join("assets");
+        let processed_root = root.join("imported_assets");
+        fs::create_dir_all(source_root.join("text")).unwrap();
+        fs::write(source_root.join("text/hello.txt"), "hello").unwrap();
+        fs::write(source_root.join("data.bin"), "data").unwrap();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let asset_io =
+            ProcessedAssetIo::new(Box::new(FileAssetIo::new(&source_root)), &processed_root);
+        asset_io.add_processor(UppercaseProcessor { runs: runs.clone() });
+        let load = |path: &str| future::block_on(asset_io.load_path(Path::new(path))).unwrap();
+
+        assert_eq!(load("text/hello.txt"), b"HELLO");
+        assert_eq!(
+            fs::read(processed_root.join("text/hello.txt")).unwrap(),
+            b"HELLO"
+        );
+        assert!(processed_root
+            .join("text/hello.txt.processed.ron")
+            .is_file());
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // up to date assets are not processed again
+        assert_eq!(load("text/hello.txt"), b"HELLO");
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // changed sources are
+        fs::write(source_root.join("text/hello.txt"), "hello again").unwrap();
+        assert_eq!(load("text/hello.txt"), b"HELLO AGAIN");
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // assets without a processor are read from the source folder
+        assert_eq!(load("data.bin"), b"data");
+        assert!(!processed_root.join("data.bin").exists());
+
+        fs::remove_dir_all(&processed_root).unwrap();
+        future::block_on(asset_io.process_folder(Path::new(""))).unwrap();
+        assert!(processed_root.join("text/hello.txt").is_file());
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        // builds that only ship the processed assets load them without their sources
+        fs::remove_file(source_root.join("text/hello.txt")).unwrap();
+        assert_eq!(load("text/hello.txt"), b"HELLO AGAIN");
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert!(future::block_on(asset_io.load_path(Path::new("text/missing.txt"))).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(fnv1a_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -11,6 +11,8 @@ mod info;
 mod io;
 mod loader;
 mod path;
+mod processor;
+mod saver;
 
 pub mod prelude {
     pub use crate::{AddAsset, AssetEvent, AssetServer, Assets, Handle, HandleUntyped};
@@ -24,12 +26,16 @@ pub use info::*;
 pub use io::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, AppBuilder};
 use bevy_ecs::{
+    reflect::ReflectResource,
     schedule::{StageLabel, SystemStage},
     system::IntoSystem,
 };
+use bevy_reflect::Reflect;
 use bevy_tasks::IoTaskPool;
 
 /// The names of asset stages in an App Schedule
@@ -44,14 +50,24 @@ pub enum AssetStage {
 #[derive(Default)]
 pub struct AssetPlugin;
 
+#[derive(Reflect)]
+#[reflect(Resource)]
 pub struct AssetServerSettings {
     pub asset_folder: String,
+    /// If set, assets that have an [AssetProcessor] are processed into this folder, and loaded
+    /// from it. See [ProcessedAssetIo].
+    pub processed_asset_folder: Option<String>,
+    /// Whether assets are loaded with the settings of their `.meta` files. Off by default on
+    /// wasm, where looking for a meta file is an HTTP request for every asset.
+    pub meta_files: bool,
 }
 
 impl Default for AssetServerSettings {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            processed_asset_folder: None,
+            meta_files: asset_server::META_FILES_DEFAULT,
         }
     }
 }
@@ -61,18 +77,9 @@ impl Default for AssetServerSettings {
 /// This is useful when providing a custom `AssetIo` instance that needs to
 /// delegate to the default `AssetIo` for the platform.
 pub fn create_platform_default_asset_io(app: &mut AppBuilder) -> Box<dyn AssetIo> {
-    let settings = app
-        .world_mut()
-        .get_resource_or_insert_with(AssetServerSettings::default);
-
-    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-    let source = FileAssetIo::new(&settings.asset_folder);
-    #[cfg(target_arch = "wasm32")]
-    let source = WasmAssetIo::new(&settings.asset_folder);
-    #[cfg(target_os = "android")]
-    let source = AndroidAssetIo::new(&settings.asset_folder);
-
-    Box::new(source)
+    app.init_settings::<AssetServerSettings>();
+    let settings = app.world().get_resource::<AssetServerSettings>().unwrap();
+    io::create_platform_asset_io(&settings.asset_folder)
 }
 
 impl Plugin for AssetPlugin {
@@ -85,13 +92,28 @@ impl Plugin for AssetPlugin {
                 .0
                 .clone();
 
-            let source = create_platform_default_asset_io(app);
+            let mut source = create_platform_default_asset_io(app);
+            let settings = app.world().get_resource::<AssetServerSettings>().unwrap();
+            if let Some(processed_asset_folder) = &settings.processed_asset_folder {
+                source = Box::new(ProcessedAssetIo::new(source, processed_asset_folder));
+            }
 
             let asset_server = AssetServer::with_boxed_io(source, task_pool);
+            asset_server.set_meta_files(settings.meta_files);
 
             app.insert_resource(asset_server);
         }
 
+        let type_registry = app
+            .world()
+            .get_resource::<bevy_reflect::TypeRegistryArc>()
+            .unwrap()
+            .clone();
+        app.world()
+            .get_resource::<AssetServer>()
+            .unwrap()
+            .set_type_registry(type_registry);
+
         app.add_stage_before(
             bevy_app::CoreStage::PreUpdate,
             AssetStage::LoadAssets,
@@ -7,7 +7,8 @@ use bevy_ecs::{
     component::Component,
     system::{Res, ResMut},
 };
-use bevy_reflect::{TypeUuid, TypeUuidDynamic};
+use bevy_log::warn;
+use bevy_reflect::{Reflect, TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
@@ -21,6 +22,11 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
     fn extensions(&self) -> &[&str];
+    /// The settings this loader reads with [LoadContext::settings]. Their fields can be set per
+    /// asset in `.meta` files, see [LoadSettings](crate::LoadSettings).
+    fn default_settings(&self) -> Option<Box<dyn Reflect>> {
+        None
+    }
 }
 
 pub trait Asset: TypeUuid + AssetDynamic {}
@@ -78,6 +84,7 @@ pub struct LoadContext<'a> {
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) settings: Option<Box<dyn Reflect>>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -86,6 +93,7 @@ impl<'a> LoadContext<'a> {
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
         version: usize,
+        settings: Option<Box<dyn Reflect>>,
     ) -> Self {
         Self {
             ref_change_channel,
@@ -93,6 +101,7 @@ impl<'a> LoadContext<'a> {
             labeled_assets: Default::default(),
             version,
             path,
+            settings,
         }
     }
 
@@ -100,6 +109,23 @@ impl<'a> LoadContext<'a> {
         &self.path
     }
 
+    /// The settings of this asset, from its `.meta` file or from
+    /// [AssetServer::load_with_settings], applied to the default value of `T`
+    pub fn settings<T: Reflect + Default>(&self) -> T {
+        let mut settings = T::default();
+        match &self.settings {
+            Some(value) if value.type_name() == settings.type_name() => settings.apply(&**value),
+            Some(value) => warn!(
+                "Ignoring the settings of {:?}: expected {}, found {}.",
+                self.path,
+                settings.type_name(),
+                value.type_name()
+            ),
+            None => {}
+        }
+        settings
+    }
+
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
     }
@@ -199,3 +225,11 @@ pub fn update_asset_storage_system<T: Asset + AssetDynamic>(
 ) {
     asset_server.update_asset_storage(&mut assets);
 }
+
+/// Serializes the assets passed to [AssetServer::save].
+pub fn save_assets_system<T: Asset + AssetDynamic>(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<T>>,
+) {
+    asset_server.save_assets(&assets);
+}
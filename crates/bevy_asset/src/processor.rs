@@ -0,0 +1,46 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use std::path::Path;
+
+/// Turns the bytes of a source asset into the bytes that are loaded at runtime, for example by
+/// compressing a texture or optimizing a mesh. Processors are registered for file extensions with
+/// [AddAsset::add_asset_processor](crate::AddAsset::add_asset_processor), and only run if
+/// [AssetServerSettings::processed_asset_folder](crate::AssetServerSettings::processed_asset_folder)
+/// is set.
+///
+/// The output is stored under the same path as the source, and is loaded by the
+/// [AssetLoader](crate::AssetLoader) registered for the extension of the source.
+pub trait AssetProcessor: Send + Sync + 'static {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>>;
+    fn extensions(&self) -> &[&str];
+    /// Processed assets are rebuilt when this changes. Increment it when the output of the
+    /// processor changes.
+    fn version(&self) -> u32;
+}
+
+pub struct ProcessContext<'a> {
+    pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) path: &'a Path,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub(crate) fn new(path: &'a Path, asset_io: &'a dyn AssetIo) -> Self {
+        Self { asset_io, path }
+    }
+
+    /// The path of the source asset, relative to the asset folder
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Reads the bytes of another source asset. Changes to that asset don't cause the processed
+    /// asset to be rebuilt.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
+        self.asset_io.load_path(path.as_ref()).await
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{Asset, AssetDynamic};
+use anyhow::Result;
+use std::path::Path;
+
+/// Serializes assets of type [AssetSaver::Asset] to bytes, the inverse of an
+/// [AssetLoader](crate::AssetLoader). Savers are registered with
+/// [AddAsset::add_asset_saver](crate::AddAsset::add_asset_saver), and
+/// [AssetServer::save](crate::AssetServer::save) picks one by the type of the asset and the
+/// extension of the path it is saved to.
+///
+/// Assets are serialized while their [Assets](crate::Assets) storage is borrowed, and the bytes are
+/// then written in the background.
+pub trait AssetSaver: Send + Sync + 'static {
+    type Asset: Asset;
+    fn save(
+        &self,
+        asset: &Self::Asset,
+        save_context: &SaveContext,
+    ) -> Result<Vec<u8>, anyhow::Error>;
+    fn extensions(&self) -> &[&str];
+}
+
+pub struct SaveContext<'a> {
+    pub(crate) path: &'a Path,
+}
+
+impl<'a> SaveContext<'a> {
+    /// Creates the context of an asset saved to `path`, to call an [AssetSaver] directly
+    pub fn new(path: &'a Path) -> Self {
+        Self { path }
+    }
+
+    /// The path the asset is saved to, relative to the asset folder
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+}
+
+/// An [AssetSaver] for an asset type that is only known at runtime
+pub(crate) trait AssetSaverDynamic: Send + Sync + 'static {
+    fn save_dynamic(
+        &self,
+        asset: &dyn AssetDynamic,
+        save_context: &SaveContext,
+    ) -> Result<Vec<u8>, anyhow::Error>;
+    fn extensions(&self) -> &[&str];
+}
+
+impl<T: AssetSaver> AssetSaverDynamic for T {
+    fn save_dynamic(
+        &self,
+        asset: &dyn AssetDynamic,
+        save_context: &SaveContext,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let asset = asset
+            .downcast_ref::<T::Asset>()
+            .expect("`AssetSaver` should only receive assets of its type.");
+        self.save(asset, save_context)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        AssetSaver::extensions(self)
+    }
+}
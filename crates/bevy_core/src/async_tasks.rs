@@ -0,0 +1,463 @@
+use bevy_app::Events;
+use bevy_ecs::{
+    component::Component,
+    entity::{Entities, Entity},
+    system::{Command, Commands, EntityCommands, ResMut},
+    world::World,
+};
+use bevy_tasks::{AsyncComputeTaskPool, CancellationToken, IoTaskPool, TaskPool};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Identifies a task spawned with [AsyncTasks]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    /// Creates a new unique [TaskId]
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        TaskId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for TaskId {
+    fn default() -> Self {
+        TaskId::new()
+    }
+}
+
+/// An event sent when a task spawned with [TaskCommandsExt::spawn_task_event] completes. Add it
+/// to the App with `add_event::<TaskCompleted<T>>()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskCompleted<T> {
+    pub id: TaskId,
+    /// The entity the task is attached to, if any
+    pub entity: Option<Entity>,
+    pub output: T,
+}
+
+/// The task pool a task spawned from [Commands] runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskPoolKind {
+    /// [AsyncComputeTaskPool], for CPU-intensive work that may span multiple frames
+    AsyncCompute,
+    /// [IoTaskPool], for tasks that mostly wait, e.g. on files or the network
+    Io,
+}
+
+type Completion = Arc<Mutex<Option<Box<dyn Command>>>>;
+
+struct SpawnedTask {
+    id: TaskId,
+    entity: Option<Entity>,
+    token: CancellationToken,
+    completion: Completion,
+}
+
+impl Drop for SpawnedTask {
+    fn drop(&mut self) {
+        // stops the future at its next await point, if it is still running
+        self.token.cancel();
+    }
+}
+
+/// Owns tasks running on a [TaskPool], and queues the [Command] each task returns once it
+/// completes, so its result can be applied with access to the [World].
+///
+/// Tasks are usually spawned with the [TaskCommandsExt] methods on [Commands] and
+/// [EntityCommands]. Tasks attached to an entity are cancelled without queueing their command
+/// once the entity is despawned, as are tasks that are removed with [AsyncTasks::cancel].
+#[derive(Default)]
+pub struct AsyncTasks {
+    tasks: Vec<SpawnedTask>,
+}
+
+impl AsyncTasks {
+    /// Spawns `future` on `pool`, and returns the id of the task. The command it returns is
+    /// queued by [async_tasks_system] in the frame after it completed.
+    pub fn spawn<C: Command>(
+        &mut self,
+        pool: &TaskPool,
+        entity: Option<Entity>,
+        future: impl Future<Output = C> + Send + 'static,
+    ) -> TaskId {
+        let id = TaskId::new();
+        self.spawn_with_id(id, pool, entity, Box::pin(boxed_command(future)));
+        id
+    }
+
+    fn spawn_with_id(
+        &mut self,
+        id: TaskId,
+        pool: &TaskPool,
+        entity: Option<Entity>,
+        future: BoxedFuture,
+    ) {
+        let token = CancellationToken::new();
+        let completion = Completion::default();
+        let task_token = token.clone();
+        let task_completion = completion.clone();
+        pool.spawn(async move {
+            if let Some(command) = task_token.run_until_cancelled(future).await {
+                *task_completion.lock().unwrap() = Some(command);
+            }
+        })
+        .detach();
+        self.tasks.push(SpawnedTask {
+            id,
+            entity,
+            token,
+            completion,
+        });
+    }
+
+    /// Cancels the task with the given id. Returns `false` if it already completed or was
+    /// cancelled.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        let len = self.tasks.len();
+        self.tasks.retain(|task| task.id != id);
+        self.tasks.len() != len
+    }
+
+    /// Returns `true` if the task with the given id has not completed yet
+    pub fn contains(&self, id: TaskId) -> bool {
+        self.tasks.iter().any(|task| task.id == id)
+    }
+
+    /// The number of tasks that have not completed yet
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+type BoxedFuture = Pin<Box<dyn Future<Output = Box<dyn Command>> + Send>>;
+
+async fn boxed_command<C: Command>(future: impl Future<Output = C>) -> Box<dyn Command> {
+    Box::new(future.await)
+}
+
+/// Queues the commands of completed tasks in [AsyncTasks], and cancels the tasks whose entity
+/// was despawned
+pub fn async_tasks_system(
+    mut tasks: ResMut<AsyncTasks>,
+    entities: &Entities,
+    mut commands: Commands,
+) {
+    tasks.tasks.retain(|task| {
+        if let Some(entity) = task.entity {
+            if !entities.contains(entity) {
+                return false;
+            }
+        }
+        match task.completion.lock().unwrap().take() {
+            Some(command) => {
+                commands.add(BoxedCommand(command));
+                false
+            }
+            None => true,
+        }
+    });
+}
+
+struct BoxedCommand(Box<dyn Command>);
+
+impl Command for BoxedCommand {
+    fn write(self: Box<Self>, world: &mut World) {
+        self.0.write(world);
+    }
+}
+
+struct SendTaskCompleted<T>(TaskCompleted<T>);
+
+impl<T: Component> Command for SendTaskCompleted<T> {
+    fn write(self: Box<Self>, world: &mut World) {
+        world
+            .get_resource_mut::<Events<TaskCompleted<T>>>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Events<TaskCompleted<{}>> is not registered, add it with add_event",
+                    std::any::type_name::<T>()
+                )
+            })
+            .send(self.0);
+    }
+}
+
+struct SpawnTask {
+    id: TaskId,
+    pool: TaskPoolKind,
+    entity: Option<Entity>,
+    // commands have to be `Sync`
+    future: Mutex<BoxedFuture>,
+}
+
+impl Command for SpawnTask {
+    fn write(self: Box<Self>, world: &mut World) {
+        let SpawnTask {
+            id,
+            pool,
+            entity,
+            future,
+        } = *self;
+        let pool = match pool {
+            TaskPoolKind::AsyncCompute => world
+                .get_resource::<AsyncComputeTaskPool>()
+                .expect("AsyncComputeTaskPool resource not found")
+                .0
+                .clone(),
+            TaskPoolKind::Io => world
+                .get_resource::<IoTaskPool>()
+                .expect("IoTaskPool resource not found")
+                .0
+                .clone(),
+        };
+        world
+            .get_resource_or_insert_with(AsyncTasks::default)
+            .spawn_with_id(id, &pool, entity, future.into_inner().unwrap());
+    }
+}
+
+struct CancelTask(TaskId);
+
+impl Command for CancelTask {
+    fn write(self: Box<Self>, world: &mut World) {
+        if let Some(mut tasks) = world.get_resource_mut::<AsyncTasks>() {
+            tasks.cancel(self.0);
+        }
+    }
+}
+
+/// Spawns tasks owned by [AsyncTasks] from [Commands] and [EntityCommands]. Tasks spawned from
+/// [EntityCommands] are attached to the entity, and cancelled once it is despawned.
+///
+/// ```
+/// # use bevy_core::*;
+/// # use bevy_ecs::{prelude::*, system::Command};
+/// struct Mesh(Vec<f32>);
+///
+/// struct InsertMesh {
+///     chunk: Entity,
+///     mesh: Mesh,
+/// }
+///
+/// impl Command for InsertMesh {
+///     fn write(self: Box<Self>, world: &mut World) {
+///         world.entity_mut(self.chunk).insert(self.mesh);
+///     }
+/// }
+///
+/// fn generate_terrain(mut commands: Commands) {
+///     let chunk = commands.spawn().id();
+///     // generate the mesh in the background, and insert it once it is done
+///     commands
+///         .entity(chunk)
+///         .spawn_task(TaskPoolKind::AsyncCompute, async move {
+///             let mesh = Mesh(vec![0.0; 1024]);
+///             InsertMesh { chunk, mesh }
+///         });
+/// }
+/// # generate_terrain.system();
+/// ```
+pub trait TaskCommandsExt {
+    /// Spawns `future` on the given pool, and queues the command it returns once it completes
+    fn spawn_task<C: Command>(
+        &mut self,
+        pool: TaskPoolKind,
+        future: impl Future<Output = C> + Send + 'static,
+    ) -> TaskId;
+
+    /// Cancels a task, if it has not completed yet
+    fn cancel_task(&mut self, id: TaskId);
+
+    /// Spawns `future` on the given pool, and sends a [TaskCompleted] event with its output once
+    /// it completes
+    fn spawn_task_event<T: Component>(
+        &mut self,
+        pool: TaskPoolKind,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> TaskId;
+}
+
+/// Queues a [SpawnTask] command for the future created by `future`, which is passed the id of
+/// the task
+fn add_spawn_task(
+    commands: &mut Commands,
+    pool: TaskPoolKind,
+    entity: Option<Entity>,
+    future: impl FnOnce(TaskId) -> BoxedFuture,
+) -> TaskId {
+    let id = TaskId::new();
+    commands.add(SpawnTask {
+        id,
+        pool,
+        entity,
+        future: Mutex::new(future(id)),
+    });
+    id
+}
+
+fn task_event_future<T: Component>(
+    id: TaskId,
+    entity: Option<Entity>,
+    future: impl Future<Output = T> + Send + 'static,
+) -> BoxedFuture {
+    Box::pin(async move {
+        let output = future.await;
+        Box::new(SendTaskCompleted(TaskCompleted { id, entity, output })) as Box<dyn Command>
+    })
+}
+
+impl<'a> TaskCommandsExt for Commands<'a> {
+    fn spawn_task<C: Command>(
+        &mut self,
+        pool: TaskPoolKind,
+        future: impl Future<Output = C> + Send + 'static,
+    ) -> TaskId {
+        add_spawn_task(self, pool, None, |_| Box::pin(boxed_command(future)))
+    }
+
+    fn cancel_task(&mut self, id: TaskId) {
+        self.add(CancelTask(id));
+    }
+
+    fn spawn_task_event<T: Component>(
+        &mut self,
+        pool: TaskPoolKind,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> TaskId {
+        add_spawn_task(self, pool, None, |id| task_event_future(id, None, future))
+    }
+}
+
+impl<'a, 'b> TaskCommandsExt for EntityCommands<'a, 'b> {
+    fn spawn_task<C: Command>(
+        &mut self,
+        pool: TaskPoolKind,
+        future: impl Future<Output = C> + Send + 'static,
+    ) -> TaskId {
+        let entity = Some(self.id());
+        add_spawn_task(self.commands(), pool, entity, |_| {
+            Box::pin(boxed_command(future))
+        })
+    }
+
+    fn cancel_task(&mut self, id: TaskId) {
+        self.commands().cancel_task(id);
+    }
+
+    fn spawn_task_event<T: Component>(
+        &mut self,
+        pool: TaskPoolKind,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> TaskId {
+        let entity = Some(self.id());
+        add_spawn_task(self.commands(), pool, entity, |id| {
+            task_event_future(id, entity, future)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncTasks, TaskCommandsExt, TaskCompleted, TaskPoolKind};
+    use crate::CorePlugin;
+    use bevy_app::{prelude::*, Events, TestApp};
+    use bevy_ecs::{prelude::*, system::Command};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Loaded(String);
+
+    impl Command for Loaded {
+        fn write(self: Box<Self>, world: &mut World) {
+            world.insert_resource(*self);
+        }
+    }
+
+    fn test_app() -> TestApp {
+        TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .add_event::<TaskCompleted<u32>>(),
+        )
+    }
+
+    fn tasks_done(world: &mut World) -> bool {
+        world.get_resource::<AsyncTasks>().unwrap().is_empty()
+    }
+
+    #[test]
+    fn commands_and_events() {
+        let mut app = test_app();
+        let mut queue = Default::default();
+        let mut commands = Commands::new(&mut queue, app.world());
+        commands.spawn_task(TaskPoolKind::Io, async { Loaded("level 1".to_string()) });
+        let id = commands.spawn_task_event(TaskPoolKind::AsyncCompute, async { 6u32 * 7 });
+        queue.apply(app.world_mut());
+
+        let mut events = Vec::new();
+        app.run_until(1000, |world| {
+            let completed = world.get_resource::<Events<TaskCompleted<u32>>>().unwrap();
+            events.extend(completed.iter_current_update_events().cloned());
+            tasks_done(world)
+        });
+        app.assert_resource_eq(&Loaded("level 1".to_string()));
+        assert_eq!(
+            events,
+            vec![TaskCompleted {
+                id,
+                entity: None,
+                output: 42
+            }]
+        );
+    }
+
+    /// Sets its flag when the future that owns it is dropped
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn despawned_entity() {
+        let mut app = test_app();
+        let entity = app.world_mut().spawn().id();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(dropped.clone());
+        let mut queue = Default::default();
+        let mut commands = Commands::new(&mut queue, app.world());
+        commands
+            .entity(entity)
+            .spawn_task_event(TaskPoolKind::AsyncCompute, async move {
+                let _flag = flag;
+                std::future::pending::<u32>().await
+            });
+        queue.apply(app.world_mut());
+        app.update();
+        assert!(!tasks_done(app.world_mut()));
+
+        app.world_mut().despawn(entity);
+        app.update();
+        assert!(tasks_done(app.world_mut()));
+        // the task is cancelled by the pool's threads
+        app.run_until(1000, |_| dropped.load(Ordering::SeqCst));
+        app.assert_event_count::<TaskCompleted<u32>>(0);
+    }
+}
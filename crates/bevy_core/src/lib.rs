@@ -1,19 +1,29 @@
+mod async_tasks;
 mod bytes;
 mod float_ord;
 mod label;
 mod name;
+mod std_layout;
 mod task_pool_options;
 mod time;
 
+pub use async_tasks::*;
 pub use bytes::*;
 pub use float_ord::*;
 pub use label::*;
 pub use name::*;
-pub use task_pool_options::DefaultTaskPoolOptions;
+pub use std_layout::*;
+pub use task_pool_options::{
+    tick_task_pools_system, DefaultTaskPoolOptions, TaskPoolAffinity,
+    TaskPoolThreadAssignmentPolicy,
+};
 pub use time::*;
 
 pub mod prelude {
-    pub use crate::{DefaultTaskPoolOptions, EntityLabels, Labels, Name, Time, Timer};
+    pub use crate::{
+        AsyncTasks, Clock, DefaultTaskPoolOptions, EntityLabels, EntityNames, Labels, Name,
+        TaskCommandsExt, TaskPoolKind, Time, Timer, TimerCommandsExt,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -47,7 +57,11 @@ impl Plugin for CorePlugin {
 
         app.init_resource::<Time>()
             .init_resource::<EntityLabels>()
+            .init_resource::<EntityNames>()
             .init_resource::<FixedTimesteps>()
+            .init_resource::<TimerScheduler>()
+            .init_resource::<AsyncTasks>()
+            .add_event::<TimerFired>()
             .register_type::<HashSet<String>>()
             .register_type::<Option<String>>()
             .register_type::<Entity>()
@@ -55,14 +69,21 @@ impl Plugin for CorePlugin {
             .register_type::<Labels>()
             .register_type::<Range<f32>>()
             .register_type::<Timer>()
+            .register_type::<Clock>()
             // time system is added as an "exclusive system" to ensure it runs before other systems
             // in CoreStage::First
             .add_system_to_stage(
                 CoreStage::First,
                 time_system.exclusive_system().label(CoreSystem::Time),
             )
+            .add_system_to_stage(CoreStage::PreUpdate, timer_scheduler_system.system())
+            .add_system_to_stage(CoreStage::PreUpdate, async_tasks_system.system())
             .add_startup_system_to_stage(StartupStage::PostStartup, entity_labels_system.system())
-            .add_system_to_stage(CoreStage::PostUpdate, entity_labels_system.system());
+            .add_system_to_stage(CoreStage::PostUpdate, entity_labels_system.system())
+            .add_startup_system_to_stage(StartupStage::PostStartup, entity_names_system.system())
+            .add_system_to_stage(CoreStage::PostUpdate, entity_names_system.system())
+            // an exclusive system, so it runs on the main thread
+            .add_system_to_stage(CoreStage::Last, tick_task_pools_system.exclusive_system());
 
         register_rust_types(app);
         register_math_types(app);
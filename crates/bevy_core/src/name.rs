@@ -1,6 +1,11 @@
-use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::{
+    entity::Entity,
+    query::Changed,
+    reflect::ReflectComponent,
+    system::{Query, RemovedComponents, ResMut},
+};
 use bevy_reflect::Reflect;
-use bevy_utils::AHasher;
+use bevy_utils::{AHasher, HashMap};
 use std::{
     borrow::Cow,
     hash::{Hash, Hasher},
@@ -97,3 +102,138 @@ impl Deref for Name {
         &self.name
     }
 }
+
+/// Maintains a mapping from [Name]s to the [Entities](bevy_ecs::prelude::Entity) that have them.
+///
+/// The mapping is updated at the end of each frame, so names added or changed during a frame
+/// can only be looked up from the next frame on.
+#[derive(Debug, Default)]
+pub struct EntityNames {
+    name_entities: HashMap<Cow<'static, str>, Vec<Entity>>,
+    entity_names: HashMap<Entity, Cow<'static, str>>,
+}
+
+impl EntityNames {
+    /// The entities named `name`
+    pub fn get(&self, name: &str) -> &[Entity] {
+        self.name_entities
+            .get(name)
+            .map(|entities| entities.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The entity named `name`, if exactly one entity has that name
+    pub fn get_single(&self, name: &str) -> Option<Entity> {
+        match self.get(name) {
+            [entity] => Some(*entity),
+            _ => None,
+        }
+    }
+
+    /// The name of `entity`, if it has one
+    pub fn name(&self, entity: Entity) -> Option<&str> {
+        self.entity_names.get(&entity).map(|name| name.deref())
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(name) = self.entity_names.remove(&entity) {
+            if let Some(entities) = self.name_entities.get_mut(&name) {
+                entities.retain(|e| *e != entity);
+                if entities.is_empty() {
+                    self.name_entities.remove(&name);
+                }
+            }
+        }
+    }
+}
+
+/// Updates [EntityNames]. [CorePlugin](crate::CorePlugin) runs this at the end of each frame.
+pub fn entity_names_system(
+    mut entity_names: ResMut<EntityNames>,
+    removed_names: RemovedComponents<Name>,
+    query: Query<(Entity, &Name), Changed<Name>>,
+) {
+    for entity in removed_names.iter() {
+        entity_names.remove(entity);
+    }
+
+    for (entity, name) in query.iter() {
+        if entity_names.name(entity) == Some(name.as_str()) {
+            continue;
+        }
+        entity_names.remove(entity);
+        entity_names
+            .name_entities
+            .entry(name.name.clone())
+            .or_insert_with(Vec::new)
+            .push(entity);
+        entity_names.entity_names.insert(entity, name.name.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        schedule::{Schedule, Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.insert_resource(EntityNames::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("test", SystemStage::single_threaded());
+        schedule.add_system_to_stage("test", entity_names_system.system());
+        (world, schedule)
+    }
+
+    #[test]
+    fn adds_named_entities() {
+        let (mut world, mut schedule) = setup();
+        let e1 = world.spawn().insert(Name::new("player")).id();
+        let e2 = world.spawn().insert(Name::new("enemy")).id();
+        let e3 = world.spawn().insert(Name::new("enemy")).id();
+        schedule.run(&mut world);
+
+        let entity_names = world.get_resource::<EntityNames>().unwrap();
+        assert_eq!(entity_names.get("player"), &[e1]);
+        assert_eq!(entity_names.get_single("player"), Some(e1));
+        assert_eq!(entity_names.get("enemy"), &[e2, e3]);
+        assert_eq!(entity_names.get_single("enemy"), None);
+        assert_eq!(entity_names.name(e2), Some("enemy"));
+    }
+
+    #[test]
+    fn renamed_entity() {
+        let (mut world, mut schedule) = setup();
+        let e1 = world.spawn().insert(Name::new("player")).id();
+        schedule.run(&mut world);
+
+        world.get_mut::<Name>(e1).unwrap().set("hero");
+        schedule.run(&mut world);
+
+        let entity_names = world.get_resource::<EntityNames>().unwrap();
+        assert_eq!(entity_names.get("player"), &[]);
+        assert_eq!(entity_names.get("hero"), &[e1]);
+        assert_eq!(entity_names.name(e1), Some("hero"));
+    }
+
+    #[test]
+    fn removes_despawned_entity() {
+        let (mut world, mut schedule) = setup();
+        let e1 = world.spawn().insert(Name::new("player")).id();
+        let e2 = world.spawn().insert(Name::new("player")).id();
+        schedule.run(&mut world);
+
+        assert!(world.despawn(e1));
+        world.entity_mut(e2).remove::<Name>();
+        schedule.run(&mut world);
+
+        let entity_names = world.get_resource::<EntityNames>().unwrap();
+        assert_eq!(entity_names.get("player"), &[]);
+        assert_eq!(entity_names.name(e1), None);
+        assert_eq!(entity_names.name(e2), None);
+    }
+}
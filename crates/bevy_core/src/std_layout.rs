@@ -0,0 +1,269 @@
+use crate::Bytes;
+use bevy_math::{
+    IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4,
+};
+
+pub use bevy_derive::{Std140, Std430};
+
+/// Writes a value with the `std140` layout GLSL uses for uniform blocks.
+///
+/// Unlike [Bytes], which copies a value as it is laid out in memory, `std140` pads values to
+/// their GLSL alignment: e.g. a `vec3` is aligned to 16 bytes, and array elements and structs
+/// are aligned to 16 bytes. Structs can derive this trait if all of their fields implement it.
+/// `#[std140(size = N)]` fails to compile if the struct's size is not `N` bytes.
+///
+/// ```
+/// # use bevy_core::{Std140, Std430};
+/// # use bevy_math::{Vec2, Vec3};
+/// // layout(std140) uniform MyMaterial { vec3 color; float intensity; vec2 offsets[2]; };
+/// #[derive(Std140, Std430)]
+/// #[std140(size = 48)]
+/// #[std430(size = 32)]
+/// struct MyMaterial {
+///     color: Vec3,
+///     intensity: f32,
+///     offsets: [Vec2; 2],
+/// }
+///
+/// let material = MyMaterial {
+///     color: Vec3::new(1.0, 0.5, 0.0),
+///     intensity: 2.0,
+///     offsets: [Vec2::ZERO, Vec2::ONE],
+/// };
+/// let bytes = material.std140_bytes();
+/// // `intensity` is packed after the `vec3`, and each array element is padded to 16 bytes
+/// assert_eq!(&bytes[12..16], &2.0f32.to_ne_bytes());
+/// assert_eq!(&bytes[32..36], &1.0f32.to_ne_bytes());
+/// ```
+///
+/// Structs are padded to their alignment, so this fails to compile:
+///
+/// ```compile_fail
+/// # use bevy_core::Std140;
+/// # use bevy_math::Vec3;
+/// #[derive(Std140)]
+/// #[std140(size = 12)]
+/// struct Light {
+///     direction: Vec3,
+/// }
+/// ```
+pub trait Std140 {
+    /// The alignment of the type in a `std140` block
+    const STD140_ALIGN: usize;
+    /// The size of the type in a `std140` block, including trailing padding for arrays and
+    /// structs
+    const STD140_SIZE: usize;
+
+    /// Writes the value to the start of `buffer`, which must be at least
+    /// [Std140::STD140_SIZE] bytes long
+    fn write_std140(&self, buffer: &mut [u8]);
+
+    fn std140_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::STD140_SIZE];
+        self.write_std140(&mut bytes);
+        bytes
+    }
+}
+
+/// Writes a value with the `std430` layout GLSL uses for storage buffers.
+///
+/// This is the same as [Std140], except that arrays and structs are not aligned to 16 bytes.
+/// `#[std430(size = N)]` fails to compile if a derived struct's size is not `N` bytes.
+pub trait Std430 {
+    /// The alignment of the type in a `std430` block
+    const STD430_ALIGN: usize;
+    /// The size of the type in a `std430` block, including trailing padding for arrays and
+    /// structs
+    const STD430_SIZE: usize;
+
+    /// Writes the value to the start of `buffer`, which must be at least
+    /// [Std430::STD430_SIZE] bytes long
+    fn write_std430(&self, buffer: &mut [u8]);
+
+    fn std430_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::STD430_SIZE];
+        self.write_std430(&mut bytes);
+        bytes
+    }
+}
+
+/// Rounds `offset` up to a multiple of `align`
+#[inline]
+pub const fn align_to(offset: usize, align: usize) -> usize {
+    (offset + align - 1) / align * align
+}
+
+/// The larger of two alignments. Used by the [Std140] and [Std430] derives.
+#[inline]
+pub const fn max_align(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Implements [Std140] and [Std430] for types that have the same layout in both, by writing
+/// `components` with [Bytes]
+macro_rules! impl_std_layout {
+    ($ty:ty, align: $align:expr, size: $size:expr, |$value:ident| $components:expr) => {
+        impl Std140 for $ty {
+            const STD140_ALIGN: usize = $align;
+            const STD140_SIZE: usize = $size;
+
+            fn write_std140(&self, buffer: &mut [u8]) {
+                let $value = self;
+                $components.write_bytes(&mut buffer[..$size]);
+            }
+        }
+
+        impl Std430 for $ty {
+            const STD430_ALIGN: usize = $align;
+            const STD430_SIZE: usize = $size;
+
+            fn write_std430(&self, buffer: &mut [u8]) {
+                let $value = self;
+                $components.write_bytes(&mut buffer[..$size]);
+            }
+        }
+    };
+}
+
+impl_std_layout!(f32, align: 4, size: 4, |value| *value);
+impl_std_layout!(i32, align: 4, size: 4, |value| *value);
+impl_std_layout!(u32, align: 4, size: 4, |value| *value);
+impl_std_layout!(f64, align: 8, size: 8, |value| *value);
+impl_std_layout!(bool, align: 4, size: 4, |value| *value as u32);
+impl_std_layout!(Vec2, align: 8, size: 8, |value| <[f32; 2]>::from(*value));
+impl_std_layout!(Vec3, align: 16, size: 12, |value| <[f32; 3]>::from(*value));
+impl_std_layout!(Vec4, align: 16, size: 16, |value| <[f32; 4]>::from(*value));
+impl_std_layout!(IVec2, align: 8, size: 8, |value| <[i32; 2]>::from(*value));
+impl_std_layout!(IVec3, align: 16, size: 12, |value| <[i32; 3]>::from(*value));
+impl_std_layout!(IVec4, align: 16, size: 16, |value| <[i32; 4]>::from(*value));
+impl_std_layout!(UVec2, align: 8, size: 8, |value| <[u32; 2]>::from(*value));
+impl_std_layout!(UVec3, align: 16, size: 12, |value| <[u32; 3]>::from(*value));
+impl_std_layout!(UVec4, align: 16, size: 16, |value| <[u32; 4]>::from(*value));
+impl_std_layout!(Quat, align: 16, size: 16, |value| <[f32; 4]>::from(*value));
+impl_std_layout!(Mat4, align: 16, size: 64, |value| value.to_cols_array());
+
+// matrices are laid out as arrays of column vectors
+impl Std140 for Mat3 {
+    const STD140_ALIGN: usize = 16;
+    const STD140_SIZE: usize = 48;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        [self.x_axis, self.y_axis, self.z_axis].write_std140(buffer);
+    }
+}
+
+impl Std430 for Mat3 {
+    const STD430_ALIGN: usize = 16;
+    const STD430_SIZE: usize = 48;
+
+    fn write_std430(&self, buffer: &mut [u8]) {
+        [self.x_axis, self.y_axis, self.z_axis].write_std430(buffer);
+    }
+}
+
+impl Std140 for Mat2 {
+    const STD140_ALIGN: usize = 16;
+    const STD140_SIZE: usize = 32;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        [self.x_axis, self.y_axis].write_std140(buffer);
+    }
+}
+
+impl Std430 for Mat2 {
+    const STD430_ALIGN: usize = 8;
+    const STD430_SIZE: usize = 16;
+
+    fn write_std430(&self, buffer: &mut [u8]) {
+        [self.x_axis, self.y_axis].write_std430(buffer);
+    }
+}
+
+impl<T: Std140, const N: usize> Std140 for [T; N] {
+    const STD140_ALIGN: usize = align_to(T::STD140_ALIGN, 16);
+    const STD140_SIZE: usize = align_to(T::STD140_SIZE, Self::STD140_ALIGN) * N;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        let stride = align_to(T::STD140_SIZE, Self::STD140_ALIGN);
+        for (index, value) in self.iter().enumerate() {
+            let offset = index * stride;
+            value.write_std140(&mut buffer[offset..offset + T::STD140_SIZE]);
+            buffer[offset + T::STD140_SIZE..offset + stride].fill(0);
+        }
+    }
+}
+
+impl<T: Std430, const N: usize> Std430 for [T; N] {
+    const STD430_ALIGN: usize = T::STD430_ALIGN;
+    const STD430_SIZE: usize = align_to(T::STD430_SIZE, T::STD430_ALIGN) * N;
+
+    fn write_std430(&self, buffer: &mut [u8]) {
+        let stride = align_to(T::STD430_SIZE, T::STD430_ALIGN);
+        for (index, value) in self.iter().enumerate() {
+            let offset = index * stride;
+            value.write_std430(&mut buffer[offset..offset + T::STD430_SIZE]);
+            buffer[offset + T::STD430_SIZE..offset + stride].fill(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{align_to, Std140, Std430};
+    use bevy_math::{Mat2, Mat3, Vec2, Vec3};
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks(4)
+            .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn align() {
+        assert_eq!(align_to(0, 16), 0);
+        assert_eq!(align_to(12, 16), 16);
+        assert_eq!(align_to(16, 16), 16);
+        assert_eq!(align_to(17, 8), 24);
+    }
+
+    #[test]
+    fn arrays() {
+        let array = [1.0f32, 2.0];
+        assert_eq!(<[f32; 2]>::STD140_SIZE, 32);
+        assert_eq!(
+            floats(&array.std140_bytes()),
+            vec![1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0]
+        );
+        assert_eq!(<[f32; 2]>::STD430_SIZE, 8);
+        assert_eq!(floats(&array.std430_bytes()), vec![1.0, 2.0]);
+
+        let array = [Vec3::new(1.0, 2.0, 3.0), Vec3::new(4.0, 5.0, 6.0)];
+        assert_eq!(<[Vec3; 2]>::STD430_SIZE, 32);
+        assert_eq!(
+            floats(&array.std430_bytes()),
+            vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0]
+        );
+        assert_eq!(<[Vec2; 3]>::STD140_SIZE, 48);
+        assert_eq!(<[Vec2; 3]>::STD430_SIZE, 24);
+    }
+
+    #[test]
+    fn matrices() {
+        let mat3 = Mat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        let expected = vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0, 7.0, 8.0, 9.0, 0.0];
+        assert_eq!(floats(&mat3.std140_bytes()), expected);
+        assert_eq!(floats(&mat3.std430_bytes()), expected);
+
+        let mat2 = Mat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(
+            floats(&mat2.std140_bytes()),
+            vec![1.0, 2.0, 0.0, 0.0, 3.0, 4.0, 0.0, 0.0]
+        );
+        assert_eq!(floats(&mat2.std430_bytes()), vec![1.0, 2.0, 3.0, 4.0]);
+    }
+}
@@ -1,6 +1,25 @@
 use bevy_ecs::world::World;
 use bevy_tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPoolBuilder};
-use bevy_utils::tracing::trace;
+use bevy_utils::{tracing::trace, HashSet};
+
+/// Which cores the threads of a task pool may run on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaskPoolAffinity {
+    /// Let the OS schedule the threads on any core
+    Any,
+    /// Pin each thread of the pool to its own core. Threads of pools using
+    /// [TaskPoolAffinity::Any] may still run on these cores.
+    Pinned,
+    /// Pin each thread of the pool to its own core, and keep the threads of pools using
+    /// [TaskPoolAffinity::Any] off these cores, so the pool has them to itself
+    Dedicated,
+}
+
+impl Default for TaskPoolAffinity {
+    fn default() -> Self {
+        TaskPoolAffinity::Any
+    }
+}
 
 /// Defines a simple way to determine how many threads to use given the number of remaining cores
 /// and number of total cores
@@ -13,6 +32,9 @@ pub struct TaskPoolThreadAssignmentPolicy {
     /// Target using this percentage of total cores, clamped by min_threads and max_threads. It is
     /// permitted to use 1.0 to try to use all remaining threads
     pub percent: f32,
+    /// Which cores the threads of this pool may run on. Ignored on platforms that don't support
+    /// pinning threads.
+    pub affinity: TaskPoolAffinity,
 }
 
 impl TaskPoolThreadAssignmentPolicy {
@@ -49,6 +71,10 @@ pub struct DefaultTaskPoolOptions {
     pub async_compute: TaskPoolThreadAssignmentPolicy,
     /// Used to determine number of compute threads to allocate
     pub compute: TaskPoolThreadAssignmentPolicy,
+
+    /// Whether the pools collect [TaskPoolMetrics](bevy_tasks::TaskPoolMetrics), e.g. for
+    /// `TaskPoolDiagnosticsPlugin`
+    pub metrics: bool,
 }
 
 impl Default for DefaultTaskPoolOptions {
@@ -63,6 +89,7 @@ impl Default for DefaultTaskPoolOptions {
                 min_threads: 1,
                 max_threads: 4,
                 percent: 0.25,
+                affinity: TaskPoolAffinity::Any,
             },
 
             // Use 25% of cores for async compute, at least 1, no more than 4
@@ -70,6 +97,7 @@ impl Default for DefaultTaskPoolOptions {
                 min_threads: 1,
                 max_threads: 4,
                 percent: 0.25,
+                affinity: TaskPoolAffinity::Any,
             },
 
             // Use all remaining cores for compute (at least 1)
@@ -77,7 +105,10 @@ impl Default for DefaultTaskPoolOptions {
                 min_threads: 1,
                 max_threads: std::usize::MAX,
                 percent: 1.0, // This 1.0 here means "whatever is left over"
+                affinity: TaskPoolAffinity::Any,
             },
+
+            metrics: false,
         }
     }
 }
@@ -99,55 +130,157 @@ impl DefaultTaskPoolOptions {
         trace!("Assigning {} cores to default task pools", total_threads);
 
         let mut remaining_threads = total_threads;
+        let mut number_of_threads = |policy: &TaskPoolThreadAssignmentPolicy, exists: bool| {
+            if exists {
+                return 0;
+            }
+            let threads = policy.get_number_of_threads(remaining_threads, total_threads);
+            remaining_threads = remaining_threads.saturating_sub(threads);
+            threads
+        };
 
-        if !world.contains_resource::<IoTaskPool>() {
-            // Determine the number of IO threads we will use
-            let io_threads = self
-                .io
-                .get_number_of_threads(remaining_threads, total_threads);
+        // Determine the number of threads we will use for the pools that don't exist yet.
+        // Compute is intentionally last so that an end user can specify 1.0 as the percent
+        let io_threads = number_of_threads(&self.io, world.contains_resource::<IoTaskPool>());
+        let async_compute_threads = number_of_threads(
+            &self.async_compute,
+            world.contains_resource::<AsyncComputeTaskPool>(),
+        );
+        let compute_threads =
+            number_of_threads(&self.compute, world.contains_resource::<ComputeTaskPool>());
 
+        let mut core_ids = assign_core_ids(
+            &bevy_tasks::core_ids(),
+            &[
+                (io_threads, self.io.affinity),
+                (async_compute_threads, self.async_compute.affinity),
+                (compute_threads, self.compute.affinity),
+            ],
+        )
+        .into_iter();
+        let mut builder = |threads: usize, thread_name: &str| {
+            let builder = TaskPoolBuilder::default()
+                .num_threads(threads)
+                .thread_name(thread_name.to_string())
+                .metrics(self.metrics);
+            match core_ids.next().flatten() {
+                Some(core_ids) => {
+                    trace!("{} cores: {:?}", thread_name, core_ids);
+                    builder.core_ids(core_ids)
+                }
+                None => builder,
+            }
+        };
+
+        let io_builder = builder(io_threads, "IO Task Pool");
+        if !world.contains_resource::<IoTaskPool>() {
             trace!("IO Threads: {}", io_threads);
-            remaining_threads = remaining_threads.saturating_sub(io_threads);
-
-            world.insert_resource(IoTaskPool(
-                TaskPoolBuilder::default()
-                    .num_threads(io_threads)
-                    .thread_name("IO Task Pool".to_string())
-                    .build(),
-            ));
+            world.insert_resource(IoTaskPool(io_builder.build()));
         }
 
+        let async_compute_builder = builder(async_compute_threads, "Async Compute Task Pool");
         if !world.contains_resource::<AsyncComputeTaskPool>() {
-            // Determine the number of async compute threads we will use
-            let async_compute_threads = self
-                .async_compute
-                .get_number_of_threads(remaining_threads, total_threads);
-
             trace!("Async Compute Threads: {}", async_compute_threads);
-            remaining_threads = remaining_threads.saturating_sub(async_compute_threads);
-
-            world.insert_resource(AsyncComputeTaskPool(
-                TaskPoolBuilder::default()
-                    .num_threads(async_compute_threads)
-                    .thread_name("Async Compute Task Pool".to_string())
-                    .build(),
-            ));
+            world.insert_resource(AsyncComputeTaskPool(async_compute_builder.build()));
         }
 
+        let compute_builder = builder(compute_threads, "Compute Task Pool");
         if !world.contains_resource::<ComputeTaskPool>() {
-            // Determine the number of compute threads we will use
-            // This is intentionally last so that an end user can specify 1.0 as the percent
-            let compute_threads = self
-                .compute
-                .get_number_of_threads(remaining_threads, total_threads);
-
             trace!("Compute Threads: {}", compute_threads);
-            world.insert_resource(ComputeTaskPool(
-                TaskPoolBuilder::default()
-                    .num_threads(compute_threads)
-                    .thread_name("Compute Task Pool".to_string())
-                    .build(),
-            ));
+            world.insert_resource(ComputeTaskPool(compute_builder.build()));
+        }
+    }
+}
+
+/// Picks the cores the threads of each pool are pinned to, given the number of threads and the
+/// affinity of each pool. Pinned and dedicated pools get consecutive cores, wrapping around if
+/// there are not enough of them. If any pool is dedicated, the other pools are pinned to the
+/// cores that are not dedicated to a pool.
+fn assign_core_ids(
+    core_ids: &[usize],
+    pools: &[(usize, TaskPoolAffinity)],
+) -> Vec<Option<Vec<usize>>> {
+    if core_ids.is_empty() {
+        return vec![None; pools.len()];
+    }
+
+    let mut next_core = 0;
+    let mut assigned = pools
+        .iter()
+        .map(|&(threads, affinity)| match affinity {
+            TaskPoolAffinity::Any => None,
+            TaskPoolAffinity::Pinned | TaskPoolAffinity::Dedicated => {
+                let pool_core_ids = (next_core..next_core + threads)
+                    .map(|i| core_ids[i % core_ids.len()])
+                    .collect::<Vec<_>>();
+                next_core += threads;
+                Some(pool_core_ids)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let dedicated = pools
+        .iter()
+        .zip(&assigned)
+        .filter(|((_, affinity), _)| *affinity == TaskPoolAffinity::Dedicated)
+        .flat_map(|(_, pool_core_ids)| pool_core_ids.iter().flatten().copied())
+        .collect::<HashSet<_>>();
+    if !dedicated.is_empty() {
+        let shared = core_ids
+            .iter()
+            .copied()
+            .filter(|id| !dedicated.contains(id))
+            .collect::<Vec<_>>();
+        // if every core is dedicated, the other pools can't avoid them
+        if !shared.is_empty() {
+            for ((_, affinity), pool_core_ids) in pools.iter().zip(&mut assigned) {
+                if *affinity == TaskPoolAffinity::Any {
+                    *pool_core_ids = Some(shared.clone());
+                }
+            }
         }
     }
+    assigned
+}
+
+/// Runs the tasks that make progress on the main thread: tasks spawned with `spawn_local`, and
+/// on wasm all tasks of the task pools. See [TaskPool::tick](bevy_tasks::TaskPool::tick).
+///
+/// The task pools share the executors of the main thread, so only one of them is ticked.
+pub fn tick_task_pools_system(world: &mut World) {
+    if let Some(pool) = world.get_resource::<ComputeTaskPool>() {
+        pool.tick();
+    } else if let Some(pool) = world.get_resource::<AsyncComputeTaskPool>() {
+        pool.tick();
+    } else if let Some(pool) = world.get_resource::<IoTaskPool>() {
+        pool.tick();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assign_core_ids, TaskPoolAffinity};
+
+    #[test]
+    fn core_assignment() {
+        use TaskPoolAffinity::*;
+
+        assert_eq!(
+            assign_core_ids(&[], &[(1, Dedicated), (2, Any)]),
+            vec![None, None]
+        );
+        assert_eq!(
+            assign_core_ids(&[0, 1, 2, 3], &[(1, Any), (2, Pinned), (1, Any)]),
+            vec![None, Some(vec![0, 1]), None]
+        );
+        assert_eq!(
+            assign_core_ids(&[0, 1, 2, 3], &[(1, Pinned), (1, Dedicated), (2, Any)]),
+            vec![Some(vec![0]), Some(vec![1]), Some(vec![0, 2, 3])]
+        );
+        // more threads than cores
+        assert_eq!(
+            assign_core_ids(&[0, 1], &[(1, Dedicated), (3, Dedicated), (1, Any)]),
+            vec![Some(vec![0]), Some(vec![1, 0, 1]), None]
+        );
+    }
 }
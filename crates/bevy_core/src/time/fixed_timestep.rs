@@ -1,13 +1,13 @@
-use crate::Time;
+use crate::{Clock, Time};
 use bevy_ecs::{
     archetype::{Archetype, ArchetypeComponentId},
     component::ComponentId,
     query::Access,
-    schedule::ShouldRun,
-    system::{IntoSystem, Local, Res, ResMut, System, SystemId},
+    schedule::{ShouldRun, Stage, SystemStage},
+    system::{IntoSystem, Local, ResMut, System, SystemId},
     world::World,
 };
-use bevy_utils::HashMap;
+use bevy_utils::{Duration, HashMap};
 use std::borrow::Cow;
 
 pub struct FixedTimestepState {
@@ -32,6 +32,9 @@ impl FixedTimestepState {
     }
 
     /// The percentage of "step" stored inside the accumulator. Calculated as accumulator / step
+    ///
+    /// This is how far the current frame is between the last step and the next one, so it can be
+    /// used to interpolate between the states of the last two steps when rendering.
     pub fn overstep_percentage(&self) -> f64 {
         self.accumulator / self.step
     }
@@ -48,6 +51,12 @@ impl FixedTimesteps {
     }
 }
 
+/// A run criteria that runs systems zero or more times per frame, once for each fixed `step` of
+/// time that has elapsed.
+///
+/// While the systems run, the [Time] resource is replaced by a fixed view, as with
+/// [FixedTimestepStage]. The view belongs to the whole stage, so use a single [FixedTimestep] per
+/// stage, for example with `SystemStage::with_run_criteria`, when systems read [Time].
 pub struct FixedTimestep {
     state: State,
     internal_system: Box<dyn System<In = (), Out = ShouldRun>>,
@@ -88,12 +97,27 @@ impl FixedTimestep {
         self
     }
 
+    /// Limits the number of steps that run in a single frame. When a frame takes too long,
+    /// running every step it missed can make the next frame take even longer. Once the limit is
+    /// reached, the remaining whole steps are dropped for that frame.
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.state.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Sets the [Clock] that drives the timestep. Defaults to [Clock::Real]. With
+    /// [Clock::Virtual], no steps run while [Time] is paused.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.state.clock = clock;
+        self
+    }
+
     fn prepare_system(
         mut state: Local<State>,
-        time: Res<Time>,
+        mut time: ResMut<Time>,
         mut fixed_timesteps: ResMut<FixedTimesteps>,
     ) -> ShouldRun {
-        let should_run = state.update(&time);
+        let should_run = state.update(&mut time);
         if let Some(ref label) = state.label {
             let res_state = fixed_timesteps.fixed_timesteps.get_mut(label).unwrap();
             res_state.step = state.step;
@@ -109,7 +133,11 @@ pub struct State {
     label: Option<String>, // TODO: consider making this a TypedLabel
     step: f64,
     accumulator: f64,
+    elapsed: Duration,
     looping: bool,
+    clock: Clock,
+    max_steps: Option<u32>,
+    steps: u32,
 }
 
 impl Default for State {
@@ -117,23 +145,45 @@ impl Default for State {
         Self {
             step: 1.0 / 60.0,
             accumulator: 0.0,
+            elapsed: Duration::from_secs(0),
             label: None,
             looping: false,
+            clock: Clock::Real,
+            max_steps: None,
+            steps: 0,
         }
     }
 }
 
 impl State {
-    fn update(&mut self, time: &Time) -> ShouldRun {
+    fn update(&mut self, time: &mut Time) -> ShouldRun {
         if !self.looping {
-            self.accumulator += time.delta_seconds_f64();
+            self.accumulator += time.frame_time().delta_for(self.clock).as_secs_f64();
+            self.steps = 0;
+        }
+
+        if self
+            .max_steps
+            .map_or(false, |max_steps| self.steps >= max_steps)
+        {
+            self.accumulator %= self.step;
         }
 
         if self.accumulator >= self.step {
             self.accumulator -= self.step;
             self.looping = true;
+            self.steps += 1;
+
+            // systems that run in the step see the fixed view of the frame's time
+            let step = Duration::from_secs_f64(self.step);
+            self.elapsed += step;
+            time.restore_frame();
+            *time = time.fixed_view(step, self.elapsed);
             ShouldRun::YesAndCheckAgain
         } else {
+            if self.looping {
+                time.restore_frame();
+            }
             self.looping = false;
             ShouldRun::No
         }
@@ -201,3 +251,312 @@ impl System for FixedTimestep {
         self.internal_system.check_change_tick(change_tick);
     }
 }
+
+/// A [Stage] that runs an inner stage zero or more times per frame, once for each fixed `step`
+/// of time that has elapsed.
+///
+/// The [Time] resource is replaced by a fixed view while the inner stage runs, so systems in it
+/// see [Time::delta] equal to `step`, and [Time::seconds_since_startup] advancing by `step` each
+/// time they run. The regular [Time] is restored afterwards. Pausing [Time] or changing its relative speed from inside the inner stage
+/// is applied to the regular [Time].
+///
+/// The stage's [FixedTimestepState] is available in [FixedTimesteps] under its label, which
+/// defaults to [FixedTimestepStage::DEFAULT_LABEL]. Its
+/// [overstep_percentage](FixedTimestepState::overstep_percentage) can be used to interpolate
+/// between steps when rendering.
+///
+/// ```
+/// # use bevy_core::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_utils::Duration;
+/// fn physics(time: Res<Time>) {
+///     assert_eq!(time.delta(), Duration::from_millis(20));
+/// }
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_stage(
+///     "fixed_update",
+///     FixedTimestepStage::new(Duration::from_millis(20))
+///         .with_stage(SystemStage::parallel().with_system(physics.system())),
+/// );
+/// ```
+pub struct FixedTimestepStage {
+    step: Duration,
+    accumulator: Duration,
+    elapsed: Duration,
+    max_steps: Option<u32>,
+    clock: Clock,
+    label: String,
+    stage: Box<dyn Stage>,
+}
+
+impl FixedTimestepStage {
+    /// The label used for the stage's [FixedTimestepState] unless one is set with
+    /// [FixedTimestepStage::with_label]
+    pub const DEFAULT_LABEL: &'static str = "fixed_timestep_stage";
+
+    /// Creates a stage that runs an empty parallel [SystemStage] every `step`
+    pub fn new(step: Duration) -> Self {
+        assert!(step > Duration::from_secs(0), "step must be greater than 0");
+        Self {
+            step,
+            accumulator: Duration::from_secs(0),
+            elapsed: Duration::from_secs(0),
+            max_steps: None,
+            clock: Clock::Real,
+            label: Self::DEFAULT_LABEL.to_string(),
+            stage: Box::new(SystemStage::parallel()),
+        }
+    }
+
+    pub fn steps_per_second(rate: f64) -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / rate))
+    }
+
+    /// Sets the stage that runs every step
+    pub fn with_stage<S: Stage>(mut self, stage: S) -> Self {
+        self.stage = Box::new(stage);
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    /// Sets the [Clock] that drives the steps. Defaults to [Clock::Real].
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Limits the number of steps that run in a single frame. See
+    /// [FixedTimestep::with_max_steps].
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// The stage that runs every step, if it is a `T`
+    pub fn stage_mut<T: Stage>(&mut self) -> Option<&mut T> {
+        self.stage.downcast_mut::<T>()
+    }
+
+    #[inline]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// The total time the inner stage has stepped through
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    fn write_state(&self, world: &mut World) {
+        let mut fixed_timesteps = world.get_resource_or_insert_with(FixedTimesteps::default);
+        fixed_timesteps.fixed_timesteps.insert(
+            self.label.clone(),
+            FixedTimestepState {
+                step: self.step.as_secs_f64(),
+                accumulator: self.accumulator.as_secs_f64(),
+            },
+        );
+    }
+}
+
+impl Stage for FixedTimestepStage {
+    fn run(&mut self, world: &mut World) {
+        self.accumulator += world
+            .get_resource::<Time>()
+            .expect("FixedTimestepStage requires the Time resource")
+            .delta_for(self.clock);
+
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            if self.max_steps.map_or(false, |max_steps| steps >= max_steps) {
+                self.accumulator = Duration::from_nanos(
+                    (self.accumulator.as_nanos() % self.step.as_nanos()) as u64,
+                );
+                break;
+            }
+            self.accumulator -= self.step;
+            self.elapsed += self.step;
+            steps += 1;
+
+            // systems in the stage read the state of the step they run in
+            self.write_state(world);
+            let mut time = world
+                .get_resource_mut::<Time>()
+                .expect("the Time resource was removed during a fixed step");
+            time.restore_frame();
+            *time = time.fixed_view(self.step, self.elapsed);
+            self.stage.run(world);
+        }
+
+        if steps > 0 {
+            world
+                .get_resource_mut::<Time>()
+                .expect("the Time resource was removed during a fixed step")
+                .restore_frame();
+        }
+
+        self.write_state(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixedTimestep, FixedTimestepStage, FixedTimesteps};
+    use crate::{CorePlugin, Time};
+    use bevy_app::{prelude::*, TestApp};
+    use bevy_ecs::prelude::*;
+    use bevy_utils::Duration;
+
+    #[derive(Default)]
+    struct Steps(Vec<(Duration, f64)>);
+
+    fn record_step(time: Res<Time>, mut steps: ResMut<Steps>) {
+        steps.0.push((time.delta(), time.seconds_since_startup()));
+    }
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+    struct FixedUpdateStage;
+
+    #[test]
+    fn fixed_time_view() {
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .init_resource::<Steps>()
+                .add_stage_after(
+                    CoreStage::Update,
+                    FixedUpdateStage,
+                    FixedTimestepStage::new(Duration::from_millis(40)).with_stage(
+                        SystemStage::parallel()
+                            .with_system(record_step.system())
+                            .with_system((|mut time: ResMut<Time>| time.pause()).system()),
+                    ),
+                ),
+        )
+        .with_frame_time(Duration::from_millis(100));
+
+        app.update_n(2);
+        let steps = &app.world().get_resource::<Steps>().unwrap().0;
+        assert_eq!(steps.len(), 2);
+        for (i, (delta, seconds)) in steps.iter().enumerate() {
+            assert_eq!(*delta, Duration::from_millis(40));
+            assert!((seconds - 0.04 * (i + 1) as f64).abs() < 1e-9);
+        }
+
+        // the regular time was restored, with the changes made during the fixed steps
+        let time = app.world().get_resource::<Time>().unwrap();
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        assert!(time.is_paused());
+
+        let fixed_timesteps = app.world().get_resource::<FixedTimesteps>().unwrap();
+        let state = fixed_timesteps
+            .get(FixedTimestepStage::DEFAULT_LABEL)
+            .unwrap();
+        assert!((state.overstep_percentage() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fixed_time_view_run_criteria() {
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .init_resource::<Steps>()
+                .add_stage_after(
+                    CoreStage::Update,
+                    FixedUpdateStage,
+                    SystemStage::parallel()
+                        .with_run_criteria(FixedTimestep::step(0.04))
+                        .with_system(record_step.system())
+                        .with_system((|mut time: ResMut<Time>| time.pause()).system()),
+                ),
+        )
+        .with_frame_time(Duration::from_millis(100));
+
+        app.update_n(2);
+        let steps = &app.world().get_resource::<Steps>().unwrap().0;
+        assert_eq!(steps.len(), 2);
+        for (i, (delta, seconds)) in steps.iter().enumerate() {
+            assert_eq!(*delta, Duration::from_millis(40));
+            assert!((seconds - 0.04 * (i + 1) as f64).abs() < 1e-9);
+        }
+
+        let time = app.world().get_resource::<Time>().unwrap();
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        assert!(time.is_paused());
+    }
+
+    #[derive(Default)]
+    struct Oversteps(Vec<f64>);
+
+    fn record_overstep(fixed_timesteps: Res<FixedTimesteps>, mut oversteps: ResMut<Oversteps>) {
+        let state = fixed_timesteps
+            .get(FixedTimestepStage::DEFAULT_LABEL)
+            .unwrap();
+        oversteps.0.push(state.overstep_percentage());
+    }
+
+    #[test]
+    fn state_during_step() {
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .init_resource::<Oversteps>()
+                .add_stage_after(
+                    CoreStage::Update,
+                    FixedUpdateStage,
+                    FixedTimestepStage::new(Duration::from_millis(40))
+                        .with_stage(SystemStage::parallel().with_system(record_overstep.system())),
+                ),
+        )
+        .with_frame_time(Duration::from_millis(100));
+
+        // the first frame has no delta. the second steps through 100ms and the third through
+        // the 20ms left over plus 100ms
+        app.update_n(3);
+        let oversteps = &app.world().get_resource::<Oversteps>().unwrap().0;
+        let expected = [1.5, 0.5, 2.0, 1.0, 0.0];
+        assert_eq!(oversteps.len(), expected.len(), "{:?}", oversteps);
+        for (overstep, expected) in oversteps.iter().zip(expected.iter()) {
+            assert!((overstep - expected).abs() < 1e-6, "{:?}", oversteps);
+        }
+    }
+
+    #[test]
+    fn max_steps() {
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .init_resource::<Steps>()
+                .add_stage_after(
+                    CoreStage::Update,
+                    FixedUpdateStage,
+                    FixedTimestepStage::new(Duration::from_millis(10))
+                        .with_max_steps(3)
+                        .with_stage(SystemStage::parallel().with_system(record_step.system())),
+                )
+                .add_system(
+                    record_step
+                        .system()
+                        .with_run_criteria(FixedTimestep::step(0.01).with_max_steps(2)),
+                ),
+        )
+        .with_frame_time(Duration::from_millis(105));
+
+        app.update_n(2);
+        // the fixed stage ran 3 steps, and the run criteria ran 2, with 10 steps due
+        assert_eq!(app.world().get_resource::<Steps>().unwrap().0.len(), 5);
+        let state = app
+            .world()
+            .get_resource::<FixedTimesteps>()
+            .unwrap()
+            .get(FixedTimestepStage::DEFAULT_LABEL)
+            .unwrap();
+        assert!((state.overstep_percentage() - 0.5).abs() < 1e-6);
+    }
+}
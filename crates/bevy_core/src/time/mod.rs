@@ -3,8 +3,10 @@ mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
+mod timer_scheduler;
 
 pub use fixed_timestep::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
+pub use timer_scheduler::*;
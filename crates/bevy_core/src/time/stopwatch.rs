@@ -1,3 +1,4 @@
+use crate::{Clock, Time};
 use bevy_ecs::reflect::ReflectComponent;
 use bevy_reflect::Reflect;
 use bevy_utils::Duration;
@@ -25,6 +26,7 @@ use bevy_utils::Duration;
 pub struct Stopwatch {
     elapsed: Duration,
     paused: bool,
+    clock: Clock,
 }
 
 impl Stopwatch {
@@ -96,6 +98,46 @@ impl Stopwatch {
         self
     }
 
+    /// Advance the stopwatch by the delta of its [`Clock`] in `time`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy_core::*;
+    /// use std::time::{Duration, Instant};
+    /// let mut time = Time::default();
+    /// let start = Instant::now();
+    /// time.update_with_instant(start);
+    /// time.pause();
+    /// time.update_with_instant(start + Duration::from_secs(1));
+    /// let mut real = Stopwatch::new();
+    /// let mut virtual_ = Stopwatch::new().with_clock(Clock::Virtual);
+    /// real.tick_with(&time);
+    /// virtual_.tick_with(&time);
+    /// assert_eq!(real.elapsed_secs(), 1.0);
+    /// assert_eq!(virtual_.elapsed_secs(), 0.0);
+    /// ```
+    pub fn tick_with(&mut self, time: &Time) -> &Self {
+        self.tick(time.delta_for(self.clock))
+    }
+
+    /// The [`Clock`] used by [`tick_with`](Stopwatch::tick_with). Defaults to
+    /// [`Clock::Real`].
+    #[inline]
+    pub fn clock(&self) -> Clock {
+        self.clock
+    }
+
+    #[inline]
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// Returns the stopwatch with [`clock`](Stopwatch::clock) set to `clock`
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Pauses the stopwatch. Any call to [`tick`](Stopwatch::tick) while
     /// paused will not have any effect on the elapsed time.
     ///
@@ -1,8 +1,34 @@
-use bevy_ecs::system::ResMut;
+use bevy_app::ManualClock;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_reflect::Reflect;
 use bevy_utils::{Duration, Instant};
 
+/// Selects which of the clocks tracked by [Time] drives a [Timer](crate::Timer),
+/// [Stopwatch](crate::Stopwatch) or [FixedTimestep](crate::FixedTimestep)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect_value(PartialEq, Hash)]
+pub enum Clock {
+    /// Wall clock time, which is not affected by pausing or the relative speed of [Time]
+    Real,
+    /// Game time, which stops while [Time] is paused and runs at its relative speed
+    Virtual,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::Real
+    }
+}
+
 /// Tracks elapsed time since the last update and since the App has started
-#[derive(Debug)]
+///
+/// [Time] tracks two clocks. The real clock follows the system clock (or the
+/// [ManualClock], if present), and is what [Time::delta] and [Time::seconds_since_startup]
+/// report. The virtual clock follows the real clock, but stops while [Time] is paused, runs at
+/// [Time::relative_speed] and never advances by more than [Time::max_delta] in a single update,
+/// so a hitch does not make the game jump ahead. Use the virtual clock for gameplay that should
+/// pause or slow down, and the real clock for things like UI animations.
+#[derive(Debug, Clone)]
 pub struct Time {
     delta: Duration,
     last_update: Option<Instant>,
@@ -10,6 +36,15 @@ pub struct Time {
     delta_seconds: f32,
     seconds_since_startup: f64,
     startup: Instant,
+    paused: bool,
+    relative_speed: f64,
+    max_delta: Duration,
+    virtual_delta: Duration,
+    virtual_delta_seconds_f64: f64,
+    virtual_delta_seconds: f32,
+    virtual_time_since_startup: Duration,
+    /// The frame's [Time] while this is a fixed view of it
+    frame: Option<Box<Time>>,
 }
 
 impl Default for Time {
@@ -21,21 +56,43 @@ impl Default for Time {
             delta_seconds_f64: 0.0,
             seconds_since_startup: 0.0,
             delta_seconds: 0.0,
+            paused: false,
+            relative_speed: 1.0,
+            max_delta: Time::DEFAULT_MAX_DELTA,
+            virtual_delta: Duration::from_secs(0),
+            virtual_delta_seconds_f64: 0.0,
+            virtual_delta_seconds: 0.0,
+            virtual_time_since_startup: Duration::from_secs(0),
+            frame: None,
         }
     }
 }
 
 impl Time {
+    /// The default value of [Time::max_delta]
+    pub const DEFAULT_MAX_DELTA: Duration = Duration::from_millis(250);
+
     pub fn update(&mut self) {
         let now = Instant::now();
         self.update_with_instant(now);
     }
 
-    pub(crate) fn update_with_instant(&mut self, instant: Instant) {
+    /// Updates the time as if [`Time::update`] was called at `instant`. This is useful for
+    /// driving time from a source other than the system clock.
+    pub fn update_with_instant(&mut self, instant: Instant) {
         if let Some(last_update) = self.last_update {
             self.delta = instant - last_update;
             self.delta_seconds_f64 = self.delta.as_secs_f64();
             self.delta_seconds = self.delta.as_secs_f32();
+
+            self.virtual_delta = if self.paused {
+                Duration::from_secs(0)
+            } else {
+                self.delta.min(self.max_delta).mul_f64(self.relative_speed)
+            };
+            self.virtual_delta_seconds_f64 = self.virtual_delta.as_secs_f64();
+            self.virtual_delta_seconds = self.virtual_delta.as_secs_f32();
+            self.virtual_time_since_startup += self.virtual_delta;
         }
 
         let duration_since_startup = instant - self.startup;
@@ -82,16 +139,152 @@ impl Time {
     pub fn time_since_startup(&self) -> Duration {
         Instant::now() - self.startup
     }
+
+    /// The delta of the virtual clock between the current and last tick as a [`Duration`]
+    #[inline]
+    pub fn virtual_delta(&self) -> Duration {
+        self.virtual_delta
+    }
+
+    /// The delta of the virtual clock between the current and last tick as [`f32`] seconds
+    #[inline]
+    pub fn virtual_delta_seconds(&self) -> f32 {
+        self.virtual_delta_seconds
+    }
+
+    /// The delta of the virtual clock between the current and last tick as [`f64`] seconds
+    #[inline]
+    pub fn virtual_delta_seconds_f64(&self) -> f64 {
+        self.virtual_delta_seconds_f64
+    }
+
+    /// The total time the virtual clock has advanced since startup
+    #[inline]
+    pub fn virtual_time_since_startup(&self) -> Duration {
+        self.virtual_time_since_startup
+    }
+
+    /// The total time the virtual clock has advanced since startup in seconds
+    #[inline]
+    pub fn virtual_seconds_since_startup(&self) -> f64 {
+        self.virtual_time_since_startup.as_secs_f64()
+    }
+
+    /// The delta between the current and last tick of the given [Clock]
+    #[inline]
+    pub fn delta_for(&self, clock: Clock) -> Duration {
+        match clock {
+            Clock::Real => self.delta,
+            Clock::Virtual => self.virtual_delta,
+        }
+    }
+
+    /// Stops the virtual clock, starting with the next update
+    #[inline]
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes the virtual clock, starting with the next update
+    #[inline]
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns `true` if the virtual clock is paused
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// How fast the virtual clock runs compared to the real clock. Defaults to 1.0.
+    #[inline]
+    pub fn relative_speed(&self) -> f64 {
+        self.relative_speed
+    }
+
+    /// Sets how fast the virtual clock runs compared to the real clock, e.g. 0.5 for slow motion,
+    /// starting with the next update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `relative_speed` is negative or not finite.
+    pub fn set_relative_speed(&mut self, relative_speed: f64) {
+        assert!(
+            relative_speed.is_finite() && relative_speed >= 0.0,
+            "relative speed must be finite and non-negative, got {}",
+            relative_speed
+        );
+        self.relative_speed = relative_speed;
+    }
+
+    /// The largest real delta the virtual clock advances by in a single update, before the
+    /// relative speed is applied. Defaults to [Time::DEFAULT_MAX_DELTA].
+    #[inline]
+    pub fn max_delta(&self) -> Duration {
+        self.max_delta
+    }
+
+    /// Sets the largest real delta the virtual clock advances by in a single update
+    #[inline]
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.max_delta = max_delta;
+    }
+
+    /// A copy of this [Time] where both clocks advance by `step` each update and have advanced
+    /// by `elapsed` since startup. [Time::restore_frame] turns it back into this [Time].
+    pub(crate) fn fixed_view(&self, step: Duration, elapsed: Duration) -> Time {
+        Time {
+            frame: Some(Box::new(self.clone())),
+            delta: step,
+            delta_seconds_f64: step.as_secs_f64(),
+            delta_seconds: step.as_secs_f32(),
+            seconds_since_startup: elapsed.as_secs_f64(),
+            virtual_delta: step,
+            virtual_delta_seconds_f64: step.as_secs_f64(),
+            virtual_delta_seconds: step.as_secs_f32(),
+            virtual_time_since_startup: elapsed,
+            ..self.clone()
+        }
+    }
+
+    /// The [Time] of the current frame, which is this [Time] unless it is a fixed view
+    pub(crate) fn frame_time(&self) -> &Time {
+        self.frame.as_deref().unwrap_or(self)
+    }
+
+    /// Replaces a fixed view with the [Time] of the frame, keeping the settings of the virtual
+    /// clock that were changed while the view was in use
+    pub(crate) fn restore_frame(&mut self) {
+        if let Some(frame) = self.frame.take() {
+            let mut frame = *frame;
+            frame.paused = self.paused;
+            frame.relative_speed = self.relative_speed;
+            frame.max_delta = self.max_delta;
+            *self = frame;
+        }
+    }
 }
 
-pub(crate) fn time_system(mut time: ResMut<Time>) {
-    time.update();
+pub(crate) fn time_system(mut time: ResMut<Time>, manual_clock: Option<Res<ManualClock>>) {
+    if let Some(manual_clock) = manual_clock {
+        // adopt the clock's startup so time since startup only depends on how far it advanced
+        if time.last_update.is_none() {
+            time.startup = manual_clock.startup();
+        }
+        time.update_with_instant(manual_clock.now());
+    } else {
+        time.update();
+    }
 }
 
 #[cfg(test)]
 #[allow(clippy::float_cmp)]
 mod tests {
-    use super::Time;
+    use super::{Clock, Time};
+    use crate::{CorePlugin, FixedTimestep};
+    use bevy_app::{App, TestApp};
+    use bevy_ecs::prelude::*;
     use bevy_utils::{Duration, Instant};
 
     #[test]
@@ -144,4 +337,100 @@ mod tests {
         );
         assert_eq!(time.delta_seconds(), time.delta().as_secs_f32());
     }
+
+    #[test]
+    fn manual_clock_test() {
+        let mut app = TestApp::new(App::build().add_plugin(CorePlugin))
+            .with_frame_time(Duration::from_millis(100));
+
+        app.update();
+        let time = app.world().get_resource::<Time>().unwrap();
+        assert_eq!(time.delta(), Duration::from_secs(0));
+        assert_eq!(time.seconds_since_startup(), 0.1);
+
+        app.update_n(2);
+        let time = app.world().get_resource::<Time>().unwrap();
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        assert_eq!(time.seconds_since_startup(), 0.3);
+    }
+
+    #[test]
+    fn virtual_time_test() {
+        let start_instant = Instant::now();
+        let mut time = Time {
+            startup: start_instant,
+            ..Default::default()
+        };
+        time.update_with_instant(start_instant);
+
+        time.set_relative_speed(2.0);
+        time.update_with_instant(start_instant + Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        assert_eq!(time.virtual_delta(), Duration::from_millis(200));
+        assert_eq!(time.delta_for(Clock::Real), Duration::from_millis(100));
+        assert_eq!(time.delta_for(Clock::Virtual), Duration::from_millis(200));
+
+        // a hitch is clamped to the max delta before scaling
+        time.set_relative_speed(1.0);
+        time.set_max_delta(Duration::from_millis(50));
+        time.update_with_instant(start_instant + Duration::from_secs(1));
+        assert_eq!(time.delta(), Duration::from_millis(900));
+        assert_eq!(time.virtual_delta(), Duration::from_millis(50));
+
+        time.pause();
+        assert!(time.is_paused());
+        time.update_with_instant(start_instant + Duration::from_millis(1010));
+        assert_eq!(time.delta(), Duration::from_millis(10));
+        assert_eq!(time.virtual_delta(), Duration::from_secs(0));
+        assert_eq!(time.virtual_delta_seconds(), 0.0);
+
+        time.unpause();
+        time.update_with_instant(start_instant + Duration::from_millis(1020));
+        assert_eq!(time.virtual_delta(), Duration::from_millis(10));
+        assert_eq!(
+            time.virtual_time_since_startup(),
+            Duration::from_millis(260)
+        );
+        assert_eq!(time.seconds_since_startup(), 1.02);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_relative_speed() {
+        Time::default().set_relative_speed(-1.0);
+    }
+
+    #[derive(Default)]
+    struct Steps {
+        real: u32,
+        virtual_: u32,
+    }
+
+    #[test]
+    fn fixed_timestep_clock_test() {
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .init_resource::<Steps>()
+                .add_system(
+                    (|mut steps: ResMut<Steps>| steps.real += 1)
+                        .system()
+                        .with_run_criteria(FixedTimestep::step(0.1)),
+                )
+                .add_system(
+                    (|mut steps: ResMut<Steps>| steps.virtual_ += 1)
+                        .system()
+                        .with_run_criteria(FixedTimestep::step(0.1).with_clock(Clock::Virtual)),
+                ),
+        )
+        .with_frame_time(Duration::from_millis(100));
+
+        app.update_n(3);
+        app.world_mut().get_resource_mut::<Time>().unwrap().pause();
+        app.update_n(3);
+        let steps = app.world().get_resource::<Steps>().unwrap();
+        // the first frame has no delta
+        assert_eq!(steps.real, 5);
+        assert_eq!(steps.virtual_, 2);
+    }
 }
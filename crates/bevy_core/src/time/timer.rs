@@ -1,4 +1,4 @@
-use crate::Stopwatch;
+use crate::{Clock, Stopwatch, Time};
 use bevy_ecs::reflect::ReflectComponent;
 use bevy_reflect::Reflect;
 use bevy_utils::Duration;
@@ -231,6 +231,46 @@ impl Timer {
         self
     }
 
+    /// Advance the timer by the delta of its [`Clock`] in `time`.
+    ///
+    /// See also [`Stopwatch::tick_with`](Stopwatch::tick_with).
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy_core::*;
+    /// use std::time::{Duration, Instant};
+    /// let mut time = Time::default();
+    /// let start = Instant::now();
+    /// time.update_with_instant(start);
+    /// time.set_relative_speed(0.5);
+    /// time.update_with_instant(start + Duration::from_millis(200));
+    /// let mut timer = Timer::from_seconds(1.0, false).with_clock(Clock::Virtual);
+    /// timer.tick_with(&time);
+    /// assert_eq!(timer.elapsed(), Duration::from_millis(100));
+    /// ```
+    pub fn tick_with(&mut self, time: &Time) -> &Self {
+        self.tick(time.delta_for(self.clock()))
+    }
+
+    /// The [`Clock`] used by [`tick_with`](Timer::tick_with). Defaults to [`Clock::Real`].
+    ///
+    /// See also [`Stopwatch::clock`](Stopwatch::clock).
+    #[inline]
+    pub fn clock(&self) -> Clock {
+        self.stopwatch.clock()
+    }
+
+    #[inline]
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.stopwatch.set_clock(clock);
+    }
+
+    /// Returns the timer with [`clock`](Timer::clock) set to `clock`
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.set_clock(clock);
+        self
+    }
+
     /// Pauses the Timer. Disables the ticking of the timer.
     ///
     /// See also [`Stopwatch::pause`](Stopwatch::pause).
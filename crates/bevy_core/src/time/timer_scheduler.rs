@@ -0,0 +1,421 @@
+use crate::{Time, Timer};
+use bevy_app::EventWriter;
+use bevy_ecs::{
+    entity::{Entities, Entity},
+    system::{Command, Commands, EntityCommands, Res, ResMut},
+    world::World,
+};
+use bevy_utils::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Identifies a timer scheduled with [TimerScheduler]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimerId(u64);
+
+impl TimerId {
+    /// Creates a new unique [TimerId]
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        TimerId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for TimerId {
+    fn default() -> Self {
+        TimerId::new()
+    }
+}
+
+/// An event sent when a timer scheduled with [TimerAction::Event] fires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerFired {
+    pub id: TimerId,
+    /// The entity the timer is attached to, if any
+    pub entity: Option<Entity>,
+}
+
+/// What happens when a scheduled timer fires
+pub enum TimerAction {
+    /// Sends a [TimerFired] event
+    Event,
+    /// Queues a [Command] the first time the timer fires, after which the timer is removed
+    Once(Box<dyn Command>),
+    /// Queues a [Command] created by the function every time the timer fires
+    Repeat(Box<dyn Fn() -> Box<dyn Command> + Send + Sync>),
+}
+
+impl TimerAction {
+    /// Queues `command` the first time the timer fires
+    pub fn once<C: Command>(command: C) -> Self {
+        TimerAction::Once(Box::new(command))
+    }
+
+    /// Queues a clone of `command` every time the timer fires
+    pub fn repeat<C: Command + Clone>(command: C) -> Self {
+        TimerAction::Repeat(Box::new(move || Box::new(command.clone())))
+    }
+}
+
+struct ScheduledTimer {
+    id: TimerId,
+    timer: Timer,
+    entity: Option<Entity>,
+    action: TimerAction,
+}
+
+/// Ticks scheduled timers with [Time], and sends an event or queues a command when they fire.
+///
+/// Timers are usually scheduled with the [TimerCommandsExt] methods on [Commands] and
+/// [EntityCommands]. Timers attached to an entity are removed without firing once the entity is
+/// despawned. Each timer is ticked by the [Clock](crate::Clock) of its [Timer].
+#[derive(Default)]
+pub struct TimerScheduler {
+    timers: Vec<ScheduledTimer>,
+}
+
+impl TimerScheduler {
+    /// Schedules `timer`, and returns the id of the scheduled timer. Paused timers do not fire
+    /// until they are unpaused, see [TimerScheduler::get_mut].
+    pub fn schedule(
+        &mut self,
+        timer: Timer,
+        entity: Option<Entity>,
+        action: TimerAction,
+    ) -> TimerId {
+        let id = TimerId::new();
+        self.schedule_with_id(id, timer, entity, action);
+        id
+    }
+
+    fn schedule_with_id(
+        &mut self,
+        id: TimerId,
+        timer: Timer,
+        entity: Option<Entity>,
+        action: TimerAction,
+    ) {
+        self.timers.push(ScheduledTimer {
+            id,
+            timer,
+            entity,
+            action,
+        });
+    }
+
+    /// Removes the timer with the given id. Returns `false` if it already fired or was removed.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|scheduled| scheduled.id != id);
+        self.timers.len() != len
+    }
+
+    /// Returns `true` if the timer with the given id is still scheduled
+    pub fn contains(&self, id: TimerId) -> bool {
+        self.timers.iter().any(|scheduled| scheduled.id == id)
+    }
+
+    pub fn get(&self, id: TimerId) -> Option<&Timer> {
+        self.timers
+            .iter()
+            .find(|scheduled| scheduled.id == id)
+            .map(|scheduled| &scheduled.timer)
+    }
+
+    /// The timer with the given id, e.g. to pause it or change its duration
+    pub fn get_mut(&mut self, id: TimerId) -> Option<&mut Timer> {
+        self.timers
+            .iter_mut()
+            .find(|scheduled| scheduled.id == id)
+            .map(|scheduled| &mut scheduled.timer)
+    }
+
+    /// The number of scheduled timers
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
+
+/// Ticks the timers in [TimerScheduler], and sends events or queues commands for the timers
+/// that fired
+pub fn timer_scheduler_system(
+    mut scheduler: ResMut<TimerScheduler>,
+    time: Res<Time>,
+    entities: &Entities,
+    mut commands: Commands,
+    mut timer_fired_events: EventWriter<TimerFired>,
+) {
+    let timers = &mut scheduler.timers;
+    let mut index = 0;
+    while index < timers.len() {
+        let scheduled = &mut timers[index];
+        if let Some(entity) = scheduled.entity {
+            if !entities.contains(entity) {
+                timers.remove(index);
+                continue;
+            }
+        }
+
+        let times_finished = scheduled.timer.tick_with(&time).times_finished();
+        match &scheduled.action {
+            TimerAction::Event => {
+                for _ in 0..times_finished {
+                    timer_fired_events.send(TimerFired {
+                        id: scheduled.id,
+                        entity: scheduled.entity,
+                    });
+                }
+            }
+            TimerAction::Repeat(command) => {
+                for _ in 0..times_finished {
+                    commands.add(BoxedCommand(command()));
+                }
+            }
+            TimerAction::Once(_) => {}
+        }
+
+        let fired_once = times_finished > 0 && matches!(scheduled.action, TimerAction::Once(_));
+        if fired_once || (scheduled.timer.finished() && !scheduled.timer.repeating()) {
+            if let TimerAction::Once(command) = timers.remove(index).action {
+                if fired_once {
+                    commands.add(BoxedCommand(command));
+                }
+            }
+        } else {
+            index += 1;
+        }
+    }
+}
+
+struct BoxedCommand(Box<dyn Command>);
+
+impl Command for BoxedCommand {
+    fn write(self: Box<Self>, world: &mut World) {
+        self.0.write(world);
+    }
+}
+
+struct ScheduleTimer {
+    id: TimerId,
+    timer: Timer,
+    entity: Option<Entity>,
+    action: TimerAction,
+}
+
+impl Command for ScheduleTimer {
+    fn write(self: Box<Self>, world: &mut World) {
+        let ScheduleTimer {
+            id,
+            timer,
+            entity,
+            action,
+        } = *self;
+        world
+            .get_resource_or_insert_with(TimerScheduler::default)
+            .schedule_with_id(id, timer, entity, action);
+    }
+}
+
+struct CancelTimer(TimerId);
+
+impl Command for CancelTimer {
+    fn write(self: Box<Self>, world: &mut World) {
+        if let Some(mut scheduler) = world.get_resource_mut::<TimerScheduler>() {
+            scheduler.cancel(self.0);
+        }
+    }
+}
+
+/// Schedules timers in [TimerScheduler] from [Commands] and [EntityCommands]. Timers scheduled
+/// from [EntityCommands] are attached to the entity, and removed without firing once it is
+/// despawned.
+///
+/// ```
+/// # use bevy_core::*;
+/// # use bevy_ecs::{prelude::*, system::Despawn};
+/// # use bevy_utils::Duration;
+/// fn spawn_explosion(mut commands: Commands) {
+///     let explosion = commands.spawn().id();
+///     // despawn the explosion after 2 seconds
+///     commands
+///         .entity(explosion)
+///         .after(Duration::from_secs(2), Despawn { entity: explosion });
+///     // send a `TimerFired` event every second while the explosion exists
+///     commands
+///         .entity(explosion)
+///         .schedule_timer(Timer::from_seconds(1.0, true), TimerAction::Event);
+/// }
+/// # spawn_explosion.system();
+/// ```
+pub trait TimerCommandsExt {
+    /// Schedules `timer`, running `action` when it fires
+    fn schedule_timer(&mut self, timer: Timer, action: TimerAction) -> TimerId;
+
+    /// Removes a scheduled timer, if it has not fired yet
+    fn cancel_timer(&mut self, id: TimerId);
+
+    /// Queues `command` once `delay` has elapsed
+    fn after<C: Command>(&mut self, delay: Duration, command: C) -> TimerId {
+        self.schedule_timer(Timer::new(delay, false), TimerAction::once(command))
+    }
+
+    /// Queues a clone of `command` every time `interval` elapses
+    fn every<C: Command + Clone>(&mut self, interval: Duration, command: C) -> TimerId {
+        self.schedule_timer(Timer::new(interval, true), TimerAction::repeat(command))
+    }
+}
+
+impl<'a> TimerCommandsExt for Commands<'a> {
+    fn schedule_timer(&mut self, timer: Timer, action: TimerAction) -> TimerId {
+        let id = TimerId::new();
+        self.add(ScheduleTimer {
+            id,
+            timer,
+            entity: None,
+            action,
+        });
+        id
+    }
+
+    fn cancel_timer(&mut self, id: TimerId) {
+        self.add(CancelTimer(id));
+    }
+}
+
+impl<'a, 'b> TimerCommandsExt for EntityCommands<'a, 'b> {
+    fn schedule_timer(&mut self, timer: Timer, action: TimerAction) -> TimerId {
+        let id = TimerId::new();
+        let entity = Some(self.id());
+        self.commands().add(ScheduleTimer {
+            id,
+            timer,
+            entity,
+            action,
+        });
+        id
+    }
+
+    fn cancel_timer(&mut self, id: TimerId) {
+        self.commands().cancel_timer(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TimerAction, TimerCommandsExt, TimerFired, TimerScheduler};
+    use crate::{Clock, CorePlugin, Time, Timer};
+    use bevy_app::{prelude::*, TestApp};
+    use bevy_ecs::{prelude::*, system::Command};
+    use bevy_utils::Duration;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Fired(u32);
+
+    impl Command for Fired {
+        fn write(self: Box<Self>, world: &mut World) {
+            world.insert_resource(*self);
+        }
+    }
+
+    fn test_app() -> TestApp {
+        TestApp::new(App::build().add_plugin(CorePlugin))
+            .with_frame_time(Duration::from_millis(100))
+    }
+
+    #[test]
+    fn after() {
+        let mut app = test_app();
+        let mut queue = Default::default();
+        let mut commands = Commands::new(&mut queue, app.world());
+        commands.after(Duration::from_millis(250), Fired(1));
+        queue.apply(app.world_mut());
+
+        // the first frame has no delta
+        app.update_n(3);
+        assert!(app.world().get_resource::<Fired>().is_none());
+        app.update();
+        app.assert_resource_eq(&Fired(1));
+        assert!(app
+            .world()
+            .get_resource::<TimerScheduler>()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn repeating_events() {
+        let mut app = test_app();
+        let id = app
+            .world_mut()
+            .get_resource_mut::<TimerScheduler>()
+            .unwrap()
+            .schedule(
+                Timer::new(Duration::from_millis(100), true),
+                None,
+                TimerAction::Event,
+            );
+        app.update();
+        app.assert_event_count::<TimerFired>(0);
+        app.update();
+        app.assert_event_sent(&TimerFired { id, entity: None });
+        app.update();
+        app.assert_event_count::<TimerFired>(1);
+
+        let mut scheduler = app
+            .world_mut()
+            .get_resource_mut::<TimerScheduler>()
+            .unwrap();
+        assert!(scheduler.cancel(id));
+        app.update();
+        app.assert_event_count::<TimerFired>(0);
+    }
+
+    #[test]
+    fn despawned_entity() {
+        let mut app = test_app();
+        let entity = app.world_mut().spawn().id();
+        let mut queue = Default::default();
+        let mut commands = Commands::new(&mut queue, app.world());
+        commands.entity(entity).schedule_timer(
+            Timer::new(Duration::from_millis(100), false),
+            TimerAction::repeat(Fired(2)),
+        );
+        queue.apply(app.world_mut());
+        app.world_mut().despawn(entity);
+
+        app.update_n(3);
+        assert!(app.world().get_resource::<Fired>().is_none());
+        assert!(app
+            .world()
+            .get_resource::<TimerScheduler>()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn virtual_clock() {
+        let mut app = test_app();
+        app.world_mut().get_resource_mut::<Time>().unwrap().pause();
+        let id = app
+            .world_mut()
+            .get_resource_mut::<TimerScheduler>()
+            .unwrap()
+            .schedule(
+                Timer::new(Duration::from_millis(100), false).with_clock(Clock::Virtual),
+                None,
+                TimerAction::Event,
+            );
+        app.update_n(3);
+        app.assert_event_count::<TimerFired>(0);
+
+        app.world_mut()
+            .get_resource_mut::<Time>()
+            .unwrap()
+            .unpause();
+        app.update();
+        app.assert_event_sent(&TimerFired { id, entity: None });
+    }
+}
@@ -8,6 +8,7 @@ mod render_resource;
 mod render_resources;
 mod resource;
 mod shader_defs;
+mod std_layout;
 
 use proc_macro::TokenStream;
 
@@ -24,15 +25,33 @@ pub fn derive_bytes(input: TokenStream) -> TokenStream {
     bytes::derive_bytes(input)
 }
 
+/// Derives the Std140 trait, which writes the struct with GLSL's `std140` layout. Each field must
+/// also implement Std140 or this will fail. `#[std140(size = N)]` checks the size at compile time.
+#[proc_macro_derive(Std140, attributes(std140, as_crate))]
+pub fn derive_std140(input: TokenStream) -> TokenStream {
+    std_layout::derive_std_layout(input, std_layout::StdLayout::Std140)
+}
+
+/// Derives the Std430 trait, which writes the struct with GLSL's `std430` layout. Each field must
+/// also implement Std430 or this will fail. `#[std430(size = N)]` checks the size at compile time.
+#[proc_macro_derive(Std430, attributes(std430, as_crate))]
+pub fn derive_std430(input: TokenStream) -> TokenStream {
+    std_layout::derive_std_layout(input, std_layout::StdLayout::Std430)
+}
+
 /// Derives the RenderResources trait. Each field must implement RenderResource or this will fail.
-/// You can ignore fields using `#[render_resources(ignore)]`.
+/// You can ignore fields using `#[render_resources(ignore)]`. Use `#[render_resources(std140)]` or
+/// `#[render_resources(std430)]` to write a field that implements `Std140` or `Std430` with that
+/// layout instead.
 #[proc_macro_derive(RenderResources, attributes(render_resources, as_crate))]
 pub fn derive_render_resources(input: TokenStream) -> TokenStream {
     render_resources::derive_render_resources(input)
 }
 
 /// Derives the RenderResource trait. The type must also implement `Bytes` or this will fail.
-#[proc_macro_derive(RenderResource, attributes(as_crate))]
+/// Use `#[render_resource(std140)]` or `#[render_resource(std430)]` to write the buffer with the
+/// `Std140` or `Std430` trait instead, which GLSL uniform blocks and storage buffers expect.
+#[proc_macro_derive(RenderResource, attributes(render_resource, as_crate))]
 pub fn derive_render_resource(input: TokenStream) -> TokenStream {
     render_resource::derive_render_resource(input)
 }
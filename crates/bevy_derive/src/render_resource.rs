@@ -1,7 +1,15 @@
 use crate::modules::{get_modules, get_path};
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Path};
+use syn::{parse::ParseStream, parse_macro_input, DeriveInput, Path};
+
+static RENDER_RESOURCE_ATTRIBUTE_NAME: &str = "render_resource";
+
+enum BufferLayout {
+    Bytes,
+    Std140,
+    Std430,
+}
 
 pub fn derive_render_resource(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -12,18 +20,63 @@ pub fn derive_render_resource(input: TokenStream) -> TokenStream {
     let bevy_core_path: Path = get_path(&modules.bevy_core);
     let struct_name = &ast.ident;
 
+    let layout = ast
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == RENDER_RESOURCE_ATTRIBUTE_NAME)
+        .map_or(BufferLayout::Bytes, |a| {
+            syn::custom_keyword!(std140);
+            syn::custom_keyword!(std430);
+            a.parse_args_with(|input: ParseStream| {
+                if input.parse::<Option<std140>>()?.is_some() {
+                    Ok(BufferLayout::Std140)
+                } else {
+                    input.parse::<std430>()?;
+                    Ok(BufferLayout::Std430)
+                }
+            })
+            .expect("Invalid 'render_resource' attribute format.")
+        });
+
+    let (write_buffer_bytes, buffer_byte_len) = match layout {
+        BufferLayout::Bytes => (
+            quote! {
+                use #bevy_core_path::Bytes;
+                self.write_bytes(buffer);
+            },
+            quote! {
+                use #bevy_core_path::Bytes;
+                Some(self.byte_len())
+            },
+        ),
+        BufferLayout::Std140 => (
+            quote! {
+                #bevy_core_path::Std140::write_std140(self, buffer);
+            },
+            quote! {
+                Some(<Self as #bevy_core_path::Std140>::STD140_SIZE)
+            },
+        ),
+        BufferLayout::Std430 => (
+            quote! {
+                #bevy_core_path::Std430::write_std430(self, buffer);
+            },
+            quote! {
+                Some(<Self as #bevy_core_path::Std430>::STD430_SIZE)
+            },
+        ),
+    };
+
     TokenStream::from(quote! {
         impl #bevy_render_path::renderer::RenderResource for #struct_name {
             fn resource_type(&self) -> Option<#bevy_render_path::renderer::RenderResourceType> {
                 Some(#bevy_render_path::renderer::RenderResourceType::Buffer)
             }
             fn write_buffer_bytes(&self, buffer: &mut [u8]) {
-                use #bevy_core_path::Bytes;
-                self.write_bytes(buffer);
+                #write_buffer_bytes
             }
             fn buffer_byte_len(&self) -> Option<usize> {
-                use #bevy_core_path::Bytes;
-                Some(self.byte_len())
+                #buffer_byte_len
             }
             fn texture(&self) -> Option<&#bevy_asset_path::Handle<#bevy_render_path::texture::Texture>> {
                 None
@@ -3,13 +3,19 @@ use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
     parse::ParseStream, parse_macro_input, punctuated::Punctuated, Data, DataStruct, DeriveInput,
-    Field, Fields, Path,
+    Field, Fields, Path, Token,
 };
 
 #[derive(Default)]
 struct RenderResourceFieldAttributes {
     pub ignore: bool,
     pub buffer: bool,
+    pub layout: Option<BufferLayout>,
+}
+
+enum BufferLayout {
+    Std140,
+    Std430,
 }
 
 #[derive(Default)]
@@ -100,14 +106,26 @@ pub fn derive_render_resources(input: TokenStream) -> TokenStream {
                         .map_or_else(RenderResourceFieldAttributes::default, |a| {
                             syn::custom_keyword!(ignore);
                             syn::custom_keyword!(buffer);
+                            syn::custom_keyword!(std140);
+                            syn::custom_keyword!(std430);
                             let mut attributes = RenderResourceFieldAttributes::default();
-                            a.parse_args_with(|input: ParseStream| {
+                            a.parse_args_with(|input: ParseStream| loop {
                                 if input.parse::<Option<ignore>>()?.is_some() {
                                     attributes.ignore = true;
                                 } else if input.parse::<Option<buffer>>()?.is_some() {
                                     attributes.buffer = true;
+                                } else if input.parse::<Option<std140>>()?.is_some() {
+                                    attributes.layout = Some(BufferLayout::Std140);
+                                } else if input.parse::<Option<std430>>()?.is_some() {
+                                    attributes.layout = Some(BufferLayout::Std430);
+                                } else if !input.is_empty() {
+                                    return Err(input.error(
+                                        "expected `ignore`, `buffer`, `std140` or `std430`",
+                                    ));
+                                }
+                                if input.parse::<Option<Token![,]>>()?.is_none() {
+                                    return Ok(());
                                 }
-                                Ok(())
                             })
                             .expect("Invalid 'render_resources' attribute format.");
 
@@ -117,7 +135,7 @@ pub fn derive_render_resources(input: TokenStream) -> TokenStream {
             })
             .collect::<Vec<(&Field, RenderResourceFieldAttributes)>>();
         let mut render_resource_names = Vec::new();
-        let mut render_resource_fields = Vec::new();
+        let mut render_resource_getters = Vec::new();
         let mut render_resource_hints = Vec::new();
         for (field, attrs) in field_attributes.iter() {
             if attrs.ignore {
@@ -126,7 +144,15 @@ pub fn derive_render_resources(input: TokenStream) -> TokenStream {
 
             let field_ident = field.ident.as_ref().unwrap();
             let field_name = field_ident.to_string();
-            render_resource_fields.push(field_ident);
+            render_resource_getters.push(match attrs.layout {
+                None => quote! { &self.#field_ident },
+                Some(BufferLayout::Std140) => {
+                    quote! { #bevy_render_path::renderer::Std140Buffer::from_ref(&self.#field_ident) }
+                }
+                Some(BufferLayout::Std430) => {
+                    quote! { #bevy_render_path::renderer::Std430Buffer::from_ref(&self.#field_ident) }
+                }
+            });
             render_resource_names.push(format!("{}_{}", struct_name, field_name));
             if attrs.buffer {
                 render_resource_hints
@@ -161,7 +187,7 @@ pub fn derive_render_resources(input: TokenStream) -> TokenStream {
 
                 fn get_render_resource(&self, index: usize) -> Option<&dyn #bevy_render_path::renderer::RenderResource> {
                     match index {
-                        #(#render_resource_indices => Some(&self.#render_resource_fields),)*
+                        #(#render_resource_indices => Some(#render_resource_getters),)*
                         _ => None,
                     }
                 }
@@ -0,0 +1,125 @@
+use crate::modules::{get_modules, get_path};
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::ParseStream, parse_macro_input, Data, DataStruct, DeriveInput, Fields, LitInt, Token,
+};
+
+/// A GLSL memory layout
+#[derive(Clone, Copy)]
+pub enum StdLayout {
+    Std140,
+    Std430,
+}
+
+impl StdLayout {
+    fn name(self) -> &'static str {
+        match self {
+            StdLayout::Std140 => "std140",
+            StdLayout::Std430 => "std430",
+        }
+    }
+}
+
+pub fn derive_std_layout(input: TokenStream, layout: StdLayout) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+
+    let modules = get_modules(&ast.attrs);
+    let bevy_core_path = get_path(&modules.bevy_core);
+
+    let name = layout.name();
+    let upper_name = name.to_uppercase();
+    let trait_ident = format_ident!("Std{}", &name[3..]);
+    let align_ident = format_ident!("{}_ALIGN", upper_name);
+    let size_ident = format_ident!("{}_SIZE", upper_name);
+    let write_ident = format_ident!("write_{}", name);
+
+    let expected_size = ast
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == name)
+        .map(|a| {
+            syn::custom_keyword!(size);
+            a.parse_args_with(|input: ParseStream| {
+                input.parse::<size>()?;
+                input.parse::<Token![=]>()?;
+                input.parse::<LitInt>()?.base10_parse::<usize>()
+            })
+            .unwrap_or_else(|_| panic!("Invalid '{}' attribute format.", name))
+        });
+
+    let field_idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    // std140 rounds the alignment of structs up to the alignment of a vec4
+    let min_align = match layout {
+        StdLayout::Std140 => 16usize,
+        StdLayout::Std430 => 1usize,
+    };
+
+    let generics = ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &ast.ident;
+
+    let size_check = expected_size.map(|expected_size| {
+        if !generics.params.is_empty() {
+            panic!("'{}(size)' is not supported on generic structs.", name);
+        }
+        quote! {
+            const _: [(); #expected_size] =
+                [(); <#struct_name as #bevy_core_path::#trait_ident>::#size_ident];
+        }
+    });
+
+    TokenStream::from(quote! {
+        impl #impl_generics #bevy_core_path::#trait_ident for #struct_name #ty_generics #where_clause {
+            const #align_ident: usize = {
+                let align = #min_align;
+                #(let align = #bevy_core_path::max_align(
+                    align,
+                    <#field_types as #bevy_core_path::#trait_ident>::#align_ident,
+                );)*
+                align
+            };
+            const #size_ident: usize = {
+                let offset = 0;
+                #(let offset = #bevy_core_path::align_to(
+                    offset,
+                    <#field_types as #bevy_core_path::#trait_ident>::#align_ident,
+                ) + <#field_types as #bevy_core_path::#trait_ident>::#size_ident;)*
+                #bevy_core_path::align_to(offset, Self::#align_ident)
+            };
+
+            fn #write_ident(&self, buffer: &mut [u8]) {
+                let buffer = &mut buffer[..Self::#size_ident];
+                for byte in buffer.iter_mut() {
+                    *byte = 0;
+                }
+                let offset = 0;
+                #(let offset = #bevy_core_path::align_to(
+                    offset,
+                    <#field_types as #bevy_core_path::#trait_ident>::#align_ident,
+                );
+                let size = <#field_types as #bevy_core_path::#trait_ident>::#size_ident;
+                #bevy_core_path::#trait_ident::#write_ident(
+                    &self.#field_idents,
+                    &mut buffer[offset..offset + size],
+                );
+                let offset = offset + size;)*
+                let _ = offset;
+            }
+        }
+
+        #size_check
+    })
+}
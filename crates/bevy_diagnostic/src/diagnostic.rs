@@ -1,6 +1,6 @@
 use bevy_log::warn;
 use bevy_utils::{Duration, Instant, StableHashMap, Uuid};
-use std::{borrow::Cow, collections::VecDeque};
+use std::{borrow::Cow, collections::VecDeque, ops::Range};
 
 use crate::log_diagnostics_plugin::MAX_LOG_NAME_WIDTH;
 
@@ -37,11 +37,16 @@ pub struct Diagnostic {
     history: VecDeque<DiagnosticMeasurement>,
     sum: f64,
     max_history_length: usize,
+    measurement_count: u64,
 }
 
 impl Diagnostic {
     pub fn add_measurement(&mut self, value: f64) {
-        let time = Instant::now();
+        self.add_measurement_at(value, Instant::now());
+    }
+
+    /// Adds a measurement taken at `time`, which should not be older than the newest measurement
+    pub fn add_measurement_at(&mut self, value: f64, time: Instant) {
         if self.history.len() == self.max_history_length {
             if let Some(removed_diagnostic) = self.history.pop_back() {
                 self.sum -= removed_diagnostic.value;
@@ -49,6 +54,7 @@ impl Diagnostic {
         }
 
         self.sum += value;
+        self.measurement_count += 1;
         self.history
             .push_front(DiagnosticMeasurement { time, value });
     }
@@ -74,6 +80,7 @@ impl Diagnostic {
             history: VecDeque::with_capacity(max_history_length),
             max_history_length,
             sum: 0.0,
+            measurement_count: 0,
         }
     }
 
@@ -98,10 +105,99 @@ impl Diagnostic {
         }
     }
 
+    /// The measurements in the history, from oldest to newest
+    pub fn measurements(&self) -> impl DoubleEndedIterator<Item = &DiagnosticMeasurement> {
+        self.history.iter().rev()
+    }
+
+    /// The smallest measurement in the history
+    pub fn min(&self) -> Option<f64> {
+        self.measurements()
+            .map(|measurement| measurement.value)
+            .fold(None, |min, value| {
+                Some(min.map_or(value, |min: f64| min.min(value)))
+            })
+    }
+
+    /// The largest measurement in the history
+    pub fn max(&self) -> Option<f64> {
+        self.measurements()
+            .map(|measurement| measurement.value)
+            .fold(None, |max, value| {
+                Some(max.map_or(value, |max: f64| max.max(value)))
+            })
+    }
+
+    /// The smallest and largest measurements taken within `window` of the newest measurement
+    pub fn min_max_over(&self, window: Duration) -> Option<(f64, f64)> {
+        let newest = self.history.front()?.time;
+        self.history
+            .iter()
+            .take_while(|measurement| newest.duration_since(measurement.time) <= window)
+            .fold(None, |min_max, measurement| {
+                let value = measurement.value;
+                Some(min_max.map_or((value, value), |(min, max): (f64, f64)| {
+                    (min.min(value), max.max(value))
+                }))
+            })
+    }
+
+    /// The `percentile` (from 0 to 100) of the measurements in the history, using the nearest
+    /// rank. E.g. `percentile(99.0)` of frame times is the frame time that only 1% of frames
+    /// exceeded.
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        self.percentiles(&[percentile])
+            .map(|percentiles| percentiles[0])
+    }
+
+    /// Like [Diagnostic::percentile], but only sorts the history once for several percentiles
+    pub fn percentiles(&self, percentiles: &[f64]) -> Option<Vec<f64>> {
+        let mut values = self
+            .history
+            .iter()
+            .map(|measurement| measurement.value)
+            .filter(|value| !value.is_nan())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Some(
+            percentiles
+                .iter()
+                .map(|percentile| {
+                    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * values.len() as f64).ceil()
+                        as usize;
+                    values[rank.max(1) - 1]
+                })
+                .collect(),
+        )
+    }
+
+    /// Counts the measurements in the history in `bucket_count` buckets of equal width
+    /// covering `range`
+    pub fn histogram(&self, range: Range<f64>, bucket_count: usize) -> DiagnosticHistogram {
+        let mut histogram = DiagnosticHistogram {
+            range,
+            buckets: vec![0; bucket_count],
+            below: 0,
+            above: 0,
+        };
+        for measurement in self.history.iter() {
+            histogram.add(measurement.value);
+        }
+        histogram
+    }
+
     pub fn history_len(&self) -> usize {
         self.history.len()
     }
 
+    /// The number of measurements ever added, including those that no longer fit in the history
+    pub fn measurement_count(&self) -> u64 {
+        self.measurement_count
+    }
+
     pub fn duration(&self) -> Option<Duration> {
         if self.history.len() < 2 {
             return None;
@@ -121,6 +217,46 @@ impl Diagnostic {
     }
 }
 
+/// Counts of [Diagnostic] measurements in buckets of equal width, see [Diagnostic::histogram]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosticHistogram {
+    pub range: Range<f64>,
+    pub buckets: Vec<usize>,
+    /// The number of measurements below `range`
+    pub below: usize,
+    /// The number of measurements at or above the end of `range`
+    pub above: usize,
+}
+
+impl DiagnosticHistogram {
+    pub fn bucket_width(&self) -> f64 {
+        (self.range.end - self.range.start) / self.buckets.len() as f64
+    }
+
+    /// The range of values counted by the bucket at `index`
+    pub fn bucket_range(&self, index: usize) -> Range<f64> {
+        let start = self.range.start + self.bucket_width() * index as f64;
+        start..start + self.bucket_width()
+    }
+
+    /// The total number of measurements, including those outside `range`
+    pub fn total(&self) -> usize {
+        self.buckets.iter().sum::<usize>() + self.below + self.above
+    }
+
+    fn add(&mut self, value: f64) {
+        if value < self.range.start {
+            self.below += 1;
+        } else if value >= self.range.end || self.buckets.is_empty() {
+            self.above += 1;
+        } else if !value.is_nan() {
+            let index = ((value - self.range.start) / self.bucket_width()) as usize;
+            let last = self.buckets.len() - 1;
+            self.buckets[index.min(last)] += 1;
+        }
+    }
+}
+
 /// A collection of [Diagnostic]s
 #[derive(Debug, Default)]
 pub struct Diagnostics {
@@ -158,3 +294,66 @@ impl Diagnostics {
         self.diagnostics.values()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, DiagnosticId};
+    use bevy_utils::{Duration, Instant};
+
+    fn with_measurements(values: impl IntoIterator<Item = f64>) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(DiagnosticId::default(), "test", 100);
+        for value in values {
+            diagnostic.add_measurement(value);
+        }
+        diagnostic
+    }
+
+    #[test]
+    fn percentiles() {
+        let diagnostic = with_measurements((1..=100).rev().map(|value| value as f64));
+        assert_eq!(diagnostic.percentile(50.0), Some(50.0));
+        assert_eq!(diagnostic.percentile(95.0), Some(95.0));
+        assert_eq!(diagnostic.percentile(99.0), Some(99.0));
+        assert_eq!(diagnostic.percentile(100.0), Some(100.0));
+        assert_eq!(diagnostic.percentile(0.0), Some(1.0));
+        assert_eq!(
+            diagnostic.percentiles(&[50.0, 99.0]),
+            Some(vec![50.0, 99.0])
+        );
+        assert_eq!(diagnostic.min(), Some(1.0));
+        assert_eq!(diagnostic.max(), Some(100.0));
+
+        let empty = with_measurements(None);
+        assert_eq!(empty.percentile(50.0), None);
+        assert_eq!(empty.max(), None);
+    }
+
+    #[test]
+    fn histogram() {
+        let diagnostic = with_measurements(vec![-1.0, 0.0, 0.5, 1.0, 1.5, 2.0, 3.5, 4.0]);
+        let histogram = diagnostic.histogram(0.0..4.0, 4);
+        assert_eq!(histogram.buckets, vec![2, 2, 1, 1]);
+        assert_eq!(histogram.below, 1);
+        assert_eq!(histogram.above, 1);
+        assert_eq!(histogram.total(), 8);
+        assert_eq!(histogram.bucket_range(2), 2.0..3.0);
+    }
+
+    #[test]
+    fn min_max_over() {
+        let start = Instant::now();
+        let mut diagnostic = Diagnostic::new(DiagnosticId::default(), "test", 100);
+        diagnostic.add_measurement_at(10.0, start);
+        diagnostic.add_measurement_at(0.0, start + Duration::from_millis(5));
+        diagnostic.add_measurement_at(3.0, start + Duration::from_millis(20));
+        diagnostic.add_measurement_at(5.0, start + Duration::from_millis(25));
+        assert_eq!(
+            diagnostic.min_max_over(Duration::from_millis(10)),
+            Some((3.0, 5.0))
+        );
+        assert_eq!(
+            diagnostic.min_max_over(Duration::from_secs(10)),
+            Some((0.0, 10.0))
+        );
+    }
+}
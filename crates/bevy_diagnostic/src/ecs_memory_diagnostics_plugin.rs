@@ -0,0 +1,427 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+use bevy_app::{prelude::*, Events, ManualEventReader, PluginId};
+use bevy_core::{Time, Timer};
+use bevy_ecs::{
+    archetype::{Archetype, ArchetypeId},
+    component::{ComponentId, ComponentTicks, Components, StorageType},
+    entity::Entity,
+    storage::Column,
+    system::{IntoExclusiveSystem, IntoSystem, ResMut},
+    world::World,
+};
+use bevy_log::info;
+use bevy_utils::{Duration, HashMap};
+use std::mem::size_of;
+
+/// Adds ECS memory usage diagnostics to an App, and keeps the [EcsMemoryUsage] resource up to
+/// date.
+///
+/// Send a [DumpEcsMemoryUsage] event to log the largest component types and archetypes.
+pub struct EcsMemoryDiagnosticsPlugin {
+    /// How often memory usage is measured
+    pub wait_duration: Duration,
+}
+
+impl Default for EcsMemoryDiagnosticsPlugin {
+    fn default() -> Self {
+        EcsMemoryDiagnosticsPlugin {
+            wait_duration: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Logs the `count` component types, resources and archetypes with the largest allocations
+#[derive(Debug, Clone, Copy)]
+pub struct DumpEcsMemoryUsage {
+    pub count: usize,
+}
+
+impl Default for DumpEcsMemoryUsage {
+    fn default() -> Self {
+        DumpEcsMemoryUsage { count: 10 }
+    }
+}
+
+/// State used by the [EcsMemoryDiagnosticsPlugin]
+struct EcsMemoryDiagnosticsState {
+    timer: Timer,
+    dump_reader: ManualEventReader<DumpEcsMemoryUsage>,
+}
+
+impl Plugin for EcsMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(EcsMemoryDiagnosticsState {
+            timer: Timer::new(self.wait_duration, true),
+            dump_reader: Default::default(),
+        })
+        .init_resource::<EcsMemoryUsage>()
+        .add_event::<DumpEcsMemoryUsage>()
+        .add_startup_system(Self::setup_system.system())
+        .add_system_to_stage(CoreStage::Last, Self::diagnostic_system.exclusive_system());
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
+}
+
+impl EcsMemoryDiagnosticsPlugin {
+    /// Bytes used by components in tables and sparse sets
+    pub const COMPONENT_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(120617343718312580862463512618427355402);
+    /// Bytes allocated for components in tables and sparse sets
+    pub const COMPONENT_CAPACITY_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(255306311009066006612733713941349862717);
+    /// Bytes used by resources
+    pub const RESOURCE_BYTES: DiagnosticId =
+        DiagnosticId::from_u128(16826532283693640289155462738411766297);
+    pub const ARCHETYPES: DiagnosticId =
+        DiagnosticId::from_u128(215640106734389407186096893474339089419);
+    /// Archetypes without entities
+    pub const EMPTY_ARCHETYPES: DiagnosticId =
+        DiagnosticId::from_u128(322451934447089632264925467346549617925);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::COMPONENT_BYTES,
+            "ecs_component_bytes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::COMPONENT_CAPACITY_BYTES,
+            "ecs_component_capacity_bytes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::RESOURCE_BYTES,
+            "ecs_resource_bytes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(Self::ARCHETYPES, "ecs_archetypes", 1));
+        diagnostics.add(Diagnostic::new(
+            Self::EMPTY_ARCHETYPES,
+            "ecs_empty_archetypes",
+            1,
+        ));
+    }
+
+    pub fn diagnostic_system(world: &mut World) {
+        let mut state = world
+            .remove_resource::<EcsMemoryDiagnosticsState>()
+            .expect("EcsMemoryDiagnosticsPlugin was not added to the app");
+        let delta = world
+            .get_resource::<Time>()
+            .map_or(Duration::from_secs(0), |time| time.delta());
+        let dump_count = world
+            .get_resource::<Events<DumpEcsMemoryUsage>>()
+            .and_then(|events| state.dump_reader.iter(events).map(|dump| dump.count).max());
+
+        if state.timer.tick(delta).just_finished() || dump_count.is_some() {
+            let usage = EcsMemoryUsage::from_world(world);
+            if let Some(mut diagnostics) = world.get_resource_mut::<Diagnostics>() {
+                diagnostics.add_measurement(Self::COMPONENT_BYTES, usage.component_bytes() as f64);
+                diagnostics.add_measurement(
+                    Self::COMPONENT_CAPACITY_BYTES,
+                    usage.component_capacity_bytes() as f64,
+                );
+                diagnostics.add_measurement(Self::RESOURCE_BYTES, usage.resource_bytes() as f64);
+                diagnostics.add_measurement(Self::ARCHETYPES, usage.archetypes.len() as f64);
+                diagnostics.add_measurement(Self::EMPTY_ARCHETYPES, usage.empty_archetypes as f64);
+            }
+            if let Some(count) = dump_count {
+                usage.dump(count);
+            }
+            world.insert_resource(usage);
+        }
+
+        world.insert_resource(state);
+    }
+}
+
+/// The memory used by the components of one type, or by one resource
+#[derive(Debug, Clone)]
+pub struct ComponentMemoryUsage {
+    pub id: ComponentId,
+    pub name: String,
+    pub storage_type: StorageType,
+    /// The number of components of this type
+    pub len: usize,
+    /// The bytes used by the components and their change ticks
+    pub bytes: usize,
+    /// The bytes allocated for the components and their change ticks
+    pub capacity_bytes: usize,
+}
+
+/// The memory used by the entities of one archetype
+#[derive(Debug, Clone)]
+pub struct ArchetypeMemoryUsage {
+    pub id: ArchetypeId,
+    /// The names of the archetype's components
+    pub components: Vec<String>,
+    pub entities: usize,
+    /// The bytes used by the archetype's components and their change ticks
+    pub bytes: usize,
+    /// The bytes allocated by the archetype's table. A table is shared by all archetypes that
+    /// only differ in sparse set components.
+    pub table_capacity_bytes: usize,
+}
+
+/// A snapshot of the memory used by the ECS, updated by the [EcsMemoryDiagnosticsPlugin]
+#[derive(Debug, Clone, Default)]
+pub struct EcsMemoryUsage {
+    /// Component types, ordered by `capacity_bytes`, largest first
+    pub components: Vec<ComponentMemoryUsage>,
+    /// Resources, ordered by `bytes`, largest first
+    pub resources: Vec<ComponentMemoryUsage>,
+    /// Archetypes that can contain entities, ordered by `bytes`, largest first
+    pub archetypes: Vec<ArchetypeMemoryUsage>,
+    /// The number of archetypes without entities
+    pub empty_archetypes: usize,
+}
+
+impl EcsMemoryUsage {
+    /// Measures the memory used by the components, resources and archetypes of `world`
+    pub fn from_world(world: &World) -> Self {
+        let components = world.components();
+        let storages = world.storages();
+        let mut memory = EcsMemoryUsage::default();
+
+        let mut component_usages = HashMap::<ComponentId, ComponentMemoryUsage>::default();
+        for table in storages.tables.iter() {
+            for column in table.iter() {
+                let usage = component_usages
+                    .entry(column.component_id())
+                    .or_insert_with(|| component_usage(components, column.component_id()));
+                let element_size = element_size(components, column.component_id());
+                usage.len += column.len();
+                usage.bytes += column.len() * element_size;
+                usage.capacity_bytes += column.capacity() * element_size;
+            }
+        }
+        for (component_id, sparse_set) in storages.sparse_sets.iter() {
+            let usage = component_usages
+                .entry(component_id)
+                .or_insert_with(|| component_usage(components, component_id));
+            // sparse sets also store the entity of each component
+            let element_size = element_size(components, component_id) + size_of::<Entity>();
+            usage.len += sparse_set.len();
+            usage.bytes += sparse_set.len() * element_size;
+            usage.capacity_bytes += sparse_set.capacity() * element_size;
+        }
+        memory.components = component_usages
+            .into_iter()
+            .map(|(_, usage)| usage)
+            .collect();
+        memory
+            .components
+            .sort_by(|a, b| b.capacity_bytes.cmp(&a.capacity_bytes));
+
+        let resource_columns = world.archetypes().resource().unique_components();
+        for (component_id, column) in resource_columns.indices().zip(resource_columns.values()) {
+            let mut usage = component_usage(components, component_id);
+            let element_size = element_size(components, component_id);
+            usage.len = column.len();
+            usage.bytes = column.len() * element_size;
+            usage.capacity_bytes = column.capacity() * element_size;
+            memory.resources.push(usage);
+        }
+        memory.resources.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+
+        for archetype in world.archetypes().iter() {
+            if archetype.id() == ArchetypeId::resource() {
+                continue;
+            }
+            if archetype.is_empty() {
+                memory.empty_archetypes += 1;
+            }
+            memory.archetypes.push(archetype_usage(world, archetype));
+        }
+        memory.archetypes.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+
+        memory
+    }
+
+    /// The bytes used by all components
+    pub fn component_bytes(&self) -> usize {
+        self.components.iter().map(|usage| usage.bytes).sum()
+    }
+
+    /// The bytes allocated for all components
+    pub fn component_capacity_bytes(&self) -> usize {
+        self.components
+            .iter()
+            .map(|usage| usage.capacity_bytes)
+            .sum()
+    }
+
+    /// The bytes used by all resources
+    pub fn resource_bytes(&self) -> usize {
+        self.resources.iter().map(|usage| usage.bytes).sum()
+    }
+
+    /// Logs the `count` component types, resources and archetypes that use the most memory
+    pub fn dump(&self, count: usize) {
+        info!(
+            target: "bevy diagnostic",
+            "ECS memory usage: {} bytes used and {} bytes allocated by components, {} bytes used by resources, {} archetypes ({} empty)",
+            self.component_bytes(),
+            self.component_capacity_bytes(),
+            self.resource_bytes(),
+            self.archetypes.len(),
+            self.empty_archetypes,
+        );
+        for usage in self.components.iter().take(count) {
+            info!(
+                target: "bevy diagnostic",
+                "component {} ({:?}): {} components, {} bytes used, {} bytes allocated",
+                usage.name,
+                usage.storage_type,
+                usage.len,
+                usage.bytes,
+                usage.capacity_bytes,
+            );
+        }
+        for usage in self.resources.iter().take(count) {
+            info!(
+                target: "bevy diagnostic",
+                "resource {}: {} bytes",
+                usage.name,
+                usage.bytes,
+            );
+        }
+        for usage in self.archetypes.iter().take(count) {
+            info!(
+                target: "bevy diagnostic",
+                "archetype {} [{}]: {} entities, {} bytes used, {} bytes allocated by its table",
+                usage.id.index(),
+                usage.components.join(", "),
+                usage.entities,
+                usage.bytes,
+                usage.table_capacity_bytes,
+            );
+        }
+    }
+}
+
+/// The bytes used by a component and its change ticks
+fn element_size(components: &Components, component_id: ComponentId) -> usize {
+    components
+        .get_info(component_id)
+        .map_or(0, |info| info.layout().size())
+        + size_of::<ComponentTicks>()
+}
+
+fn component_usage(components: &Components, component_id: ComponentId) -> ComponentMemoryUsage {
+    let info = components.get_info(component_id);
+    ComponentMemoryUsage {
+        id: component_id,
+        name: info.map_or_else(
+            || format!("{:?}", component_id),
+            |info| info.name().to_string(),
+        ),
+        storage_type: info.map_or(StorageType::Table, |info| info.storage_type()),
+        len: 0,
+        bytes: 0,
+        capacity_bytes: 0,
+    }
+}
+
+fn archetype_usage(world: &World, archetype: &Archetype) -> ArchetypeMemoryUsage {
+    let components = world.components();
+    let row_size = archetype
+        .components()
+        .map(|component_id| element_size(components, component_id))
+        .sum::<usize>();
+    let table_capacity_bytes =
+        world
+            .storages()
+            .tables
+            .get(archetype.table_id())
+            .map_or(0, |table| {
+                table
+                    .iter()
+                    .map(|column: &Column| {
+                        column.capacity() * element_size(components, column.component_id())
+                    })
+                    .sum()
+            });
+    ArchetypeMemoryUsage {
+        id: archetype.id(),
+        components: archetype
+            .components()
+            .map(|component_id| component_usage(components, component_id).name)
+            .collect(),
+        entities: archetype.len(),
+        bytes: archetype.len() * row_size,
+        table_capacity_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EcsMemoryUsage;
+    use bevy_ecs::{
+        component::{ComponentDescriptor, StorageType},
+        world::World,
+    };
+    use std::mem::size_of;
+
+    struct Position([f32; 3]);
+    struct Velocity([f32; 3]);
+    struct Marker(u64);
+    struct Settings([u8; 100]);
+
+    #[test]
+    fn memory_usage() {
+        let mut world = World::new();
+        world
+            .register_component(ComponentDescriptor::new::<Marker>(StorageType::SparseSet))
+            .unwrap();
+        world.insert_resource(Settings([0; 100]));
+        for i in 0..10 {
+            world
+                .spawn()
+                .insert(Position([0.0; 3]))
+                .insert(Velocity([0.0; 3]));
+            if i % 2 == 0 {
+                world.spawn().insert(Position([0.0; 3])).insert(Marker(0));
+            }
+        }
+        // an archetype that no longer has entities
+        let entity = world.spawn().insert(Velocity([0.0; 3])).id();
+        world.despawn(entity);
+
+        let usage = EcsMemoryUsage::from_world(&world);
+        let ticks = size_of::<bevy_ecs::component::ComponentTicks>();
+        let position = usage
+            .components
+            .iter()
+            .find(|usage| usage.name.ends_with("Position"))
+            .unwrap();
+        assert_eq!(position.len, 15);
+        assert_eq!(position.bytes, 15 * (12 + ticks));
+        assert!(position.capacity_bytes >= position.bytes);
+
+        let marker = usage
+            .components
+            .iter()
+            .find(|usage| usage.name.ends_with("Marker"))
+            .unwrap();
+        assert_eq!(marker.storage_type, StorageType::SparseSet);
+        assert_eq!(marker.len, 5);
+
+        let settings = usage
+            .resources
+            .iter()
+            .find(|usage| usage.name.ends_with("Settings"))
+            .unwrap();
+        assert_eq!(settings.bytes, 100 + ticks);
+
+        let largest = &usage.archetypes[0];
+        assert_eq!(largest.entities, 10);
+        assert_eq!(largest.bytes, 10 * 2 * (12 + ticks));
+        assert_eq!(largest.components.len(), 2);
+        // the archetype without components, and the `Position` and `Velocity` archetypes
+        assert_eq!(usage.empty_archetypes, 3);
+    }
+}
@@ -1,10 +1,10 @@
-use bevy_app::{AppBuilder, Plugin};
+use bevy_app::{AppBuilder, Plugin, PluginId};
 use bevy_ecs::{
     system::{IntoExclusiveSystem, IntoSystem, ResMut},
     world::World,
 };
 
-use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use crate::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
 
 /// Adds "entity count" diagnostic to an App
 #[derive(Default)]
@@ -15,6 +15,10 @@ impl Plugin for EntityCountDiagnosticsPlugin {
         app.add_startup_system(Self::setup_system.system())
             .add_system(Self::diagnostic_system.exclusive_system());
     }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
 }
 
 impl EntityCountDiagnosticsPlugin {
@@ -0,0 +1,348 @@
+use super::{Diagnostic, DiagnosticId, DiagnosticMeasurement, Diagnostics, DiagnosticsPlugin};
+use bevy_app::{prelude::*, PluginId};
+use bevy_core::{Time, Timer};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use bevy_log::error;
+use bevy_utils::{Duration, HashMap, Instant};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// The file format written by the [ExportDiagnosticsPlugin]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticsFileFormat {
+    /// Comma separated values with a `time,id,name,value` header
+    Csv,
+    /// One JSON object per line, with `time`, `id`, `name` and `value` fields
+    JsonLines,
+}
+
+/// An App Plugin that writes every [DiagnosticMeasurement] in [Diagnostics] to a file, e.g. to
+/// chart frame times of a benchmark run.
+///
+/// Each measurement is written once, with its time in seconds since the app started. The file is
+/// flushed every `flush_interval` and when the app shuts down.
+///
+/// ```no_run
+/// # use bevy_app::prelude::*;
+/// # use bevy_diagnostic::{DiagnosticsPlugin, ExportDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+/// App::build()
+///     .add_plugin(DiagnosticsPlugin)
+///     .add_plugin(FrameTimeDiagnosticsPlugin)
+///     .add_plugin(
+///         ExportDiagnosticsPlugin::csv("target/benchmark/diagnostics.csv")
+///             .filtered(vec![FrameTimeDiagnosticsPlugin::FRAME_TIME]),
+///     );
+/// ```
+pub struct ExportDiagnosticsPlugin {
+    pub path: PathBuf,
+    pub format: DiagnosticsFileFormat,
+    pub filter: Option<Vec<DiagnosticId>>,
+    pub flush_interval: Duration,
+}
+
+/// State used by the [ExportDiagnosticsPlugin]
+struct ExportDiagnosticsState {
+    /// `None` after writing failed
+    writer: Option<BufWriter<File>>,
+    path: PathBuf,
+    format: DiagnosticsFileFormat,
+    filter: Option<Vec<DiagnosticId>>,
+    flush_timer: Timer,
+    /// The [Diagnostic::measurement_count] of each diagnostic when it was last exported
+    exported_counts: HashMap<DiagnosticId, u64>,
+}
+
+impl Plugin for ExportDiagnosticsPlugin {
+    fn build(&self, app: &mut bevy_app::AppBuilder) {
+        let writer = match Self::create_file(&self.path, self.format) {
+            Ok(writer) => Some(writer),
+            Err(err) => {
+                error!(
+                    "Failed to create diagnostics file {}: {}",
+                    self.path.display(),
+                    err
+                );
+                None
+            }
+        };
+
+        app.insert_resource(ExportDiagnosticsState {
+            writer,
+            path: self.path.clone(),
+            format: self.format,
+            filter: self.filter.clone(),
+            flush_timer: Timer::new(self.flush_interval, true),
+            exported_counts: Default::default(),
+        })
+        .add_system_to_stage(CoreStage::Last, Self::export_diagnostics_system.system())
+        .add_shutdown_system(Self::flush_diagnostics_system.system());
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
+}
+
+impl ExportDiagnosticsPlugin {
+    pub fn new(path: impl Into<PathBuf>, format: DiagnosticsFileFormat) -> Self {
+        ExportDiagnosticsPlugin {
+            path: path.into(),
+            format,
+            filter: None,
+            flush_interval: Duration::from_secs(1),
+        }
+    }
+
+    pub fn csv(path: impl Into<PathBuf>) -> Self {
+        Self::new(path, DiagnosticsFileFormat::Csv)
+    }
+
+    pub fn json_lines(path: impl Into<PathBuf>) -> Self {
+        Self::new(path, DiagnosticsFileFormat::JsonLines)
+    }
+
+    /// Only exports the diagnostics in `filter`
+    pub fn filtered(mut self, filter: Vec<DiagnosticId>) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    fn create_file(path: &Path, format: DiagnosticsFileFormat) -> io::Result<BufWriter<File>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        if format == DiagnosticsFileFormat::Csv {
+            writeln!(writer, "time,id,name,value")?;
+        }
+        Ok(writer)
+    }
+
+    fn write_measurement(
+        writer: &mut impl Write,
+        format: DiagnosticsFileFormat,
+        startup: Instant,
+        diagnostic: &Diagnostic,
+        measurement: &DiagnosticMeasurement,
+    ) -> io::Result<()> {
+        let time = measurement
+            .time
+            .saturating_duration_since(startup)
+            .as_secs_f64();
+        match format {
+            DiagnosticsFileFormat::Csv => writeln!(
+                writer,
+                "{:.6},{},{},{}",
+                time,
+                diagnostic.id.0,
+                csv_field(&diagnostic.name),
+                measurement.value
+            ),
+            DiagnosticsFileFormat::JsonLines => writeln!(
+                writer,
+                "{{\"time\":{:.6},\"id\":\"{}\",\"name\":\"{}\",\"value\":{}}}",
+                time,
+                diagnostic.id.0,
+                json_string(&diagnostic.name),
+                json_number(measurement.value)
+            ),
+        }
+    }
+
+    fn export_diagnostics(
+        state: &mut ExportDiagnosticsState,
+        startup: Instant,
+        diagnostics: &Diagnostics,
+    ) -> io::Result<()> {
+        let writer = match &mut state.writer {
+            Some(writer) => writer,
+            None => return Ok(()),
+        };
+        let filter = &state.filter;
+        for diagnostic in diagnostics.iter().filter(|diagnostic| {
+            filter
+                .as_ref()
+                .map_or(true, |filter| filter.contains(&diagnostic.id))
+        }) {
+            let exported_count = state
+                .exported_counts
+                .get(&diagnostic.id)
+                .copied()
+                .unwrap_or(0);
+            // measurements can share an `Instant`, so new ones are found by count instead of time
+            let new_count = (diagnostic.measurement_count() - exported_count)
+                .min(diagnostic.history_len() as u64) as usize;
+            for measurement in diagnostic
+                .measurements()
+                .skip(diagnostic.history_len() - new_count)
+            {
+                Self::write_measurement(writer, state.format, startup, diagnostic, measurement)?;
+            }
+            state
+                .exported_counts
+                .insert(diagnostic.id, diagnostic.measurement_count());
+        }
+        Ok(())
+    }
+
+    fn export_diagnostics_system(
+        mut state: ResMut<ExportDiagnosticsState>,
+        time: Res<Time>,
+        diagnostics: Res<Diagnostics>,
+    ) {
+        let state = &mut *state;
+        let mut result = Self::export_diagnostics(state, time.startup(), &diagnostics);
+        if state.flush_timer.tick(time.delta()).just_finished() {
+            result = result.and_then(|_| state.flush());
+        }
+        if let Err(err) = result {
+            state.fail(err);
+        }
+    }
+
+    fn flush_diagnostics_system(mut state: ResMut<ExportDiagnosticsState>) {
+        if let Err(err) = state.flush() {
+            state.fail(err);
+        }
+    }
+}
+
+impl ExportDiagnosticsState {
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn fail(&mut self, err: io::Error) {
+        error!(
+            "Failed to write diagnostics to {}, no more diagnostics will be exported: {}",
+            self.path.display(),
+            err
+        );
+        self.writer = None;
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// JSON has no representation for NaN or infinity
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, json_string, ExportDiagnosticsPlugin};
+    use crate::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+    use bevy_app::{prelude::*, TestApp};
+    use bevy_core::CorePlugin;
+    use bevy_utils::Instant;
+    use std::{fs, path::PathBuf};
+
+    const FOO: DiagnosticId = DiagnosticId::from_u128(1);
+    const BAR: DiagnosticId = DiagnosticId::from_u128(2);
+
+    fn export(plugin: impl FnOnce(PathBuf) -> ExportDiagnosticsPlugin, name: &str) -> String {
+        let path = std::env::temp_dir()
+            .join(format!("bevy_diagnostic_{}", std::process::id()))
+            .join(name);
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .add_plugin(DiagnosticsPlugin)
+                .add_plugin(plugin(path.clone())),
+        );
+        {
+            let mut diagnostics = app.world_mut().get_resource_mut::<Diagnostics>().unwrap();
+            diagnostics.add(Diagnostic::new(FOO, "foo, \"quoted\"", 10));
+            diagnostics.add(Diagnostic::new(BAR, "bar", 10));
+        }
+        for value in 0..3 {
+            let mut diagnostics = app.world_mut().get_resource_mut::<Diagnostics>().unwrap();
+            diagnostics.add_measurement(FOO, value as f64);
+            // both measurements of a frame are exported, even though they share their time
+            let bar = diagnostics.get_mut(BAR).unwrap();
+            let now = Instant::now();
+            bar.add_measurement_at(10.0, now);
+            bar.add_measurement_at(20.0, now);
+            app.update();
+        }
+        app.app.shutdown();
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        contents
+    }
+
+    #[test]
+    fn csv() {
+        let contents = export(
+            |path| ExportDiagnosticsPlugin::csv(path).filtered(vec![FOO]),
+            "diagnostics.csv",
+        );
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "time,id,name,value");
+        for (value, line) in lines[1..].iter().enumerate() {
+            let expected = format!(",{},\"foo, \"\"quoted\"\"\",{}", FOO.0, value);
+            assert!(line.ends_with(&expected), "{}", line);
+        }
+    }
+
+    #[test]
+    fn json_lines() {
+        let contents = export(ExportDiagnosticsPlugin::json_lines, "diagnostics.jsonl");
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(
+            lines.iter().filter(|line| line.contains("\"bar\"")).count(),
+            6
+        );
+        assert!(lines[0].starts_with("{\"time\":"));
+        assert!(lines
+            .iter()
+            .any(|line| line.ends_with("\"name\":\"foo, \\\"quoted\\\"\",\"value\":2}")));
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(csv_field("frame_time"), "frame_time");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(json_string("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+}
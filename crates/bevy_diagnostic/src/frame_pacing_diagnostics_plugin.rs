@@ -0,0 +1,61 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+use bevy_app::{prelude::*, FramePacingStats, PluginId};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+
+/// Adds "frame pacing" diagnostics to an App, specifically "late frames" and "dropped frames".
+///
+/// The app's runner must pace frames: `ScheduleRunnerPlugin` always does, and `WinitPlugin` does
+/// when a [FramePacingSettings](bevy_app::FramePacingSettings) resource exists.
+#[derive(Default)]
+pub struct FramePacingDiagnosticsPlugin;
+
+impl Plugin for FramePacingDiagnosticsPlugin {
+    fn build(&self, app: &mut bevy_app::AppBuilder) {
+        app.add_startup_system(Self::setup_system.system())
+            .add_system(Self::diagnostic_system.system());
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
+}
+
+impl FramePacingDiagnosticsPlugin {
+    /// 1.0 for each frame that took longer than the target frame time, and 0.0 otherwise. The
+    /// average is the ratio of late frames.
+    pub const LATE_FRAMES: DiagnosticId =
+        DiagnosticId::from_u128(211398470512389402836091532866914823716);
+    /// The number of target frame intervals skipped by each frame
+    pub const DROPPED_FRAMES: DiagnosticId =
+        DiagnosticId::from_u128(95226411379580432740227196427451790382);
+    /// The total number of frames that were late since the app started
+    pub const TOTAL_LATE_FRAMES: DiagnosticId =
+        DiagnosticId::from_u128(38620452183406452624950651097712633104);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::LATE_FRAMES, "late_frames", 120));
+        diagnostics.add(Diagnostic::new(Self::DROPPED_FRAMES, "dropped_frames", 120));
+        diagnostics.add(Diagnostic::new(
+            Self::TOTAL_LATE_FRAMES,
+            "total_late_frames",
+            1,
+        ));
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        stats: Option<Res<FramePacingStats>>,
+    ) {
+        // stats are only available after the first frame was paced
+        let stats = match stats {
+            Some(stats) => stats,
+            None => return,
+        };
+        diagnostics.add_measurement(
+            Self::LATE_FRAMES,
+            if stats.last_frame_late { 1.0 } else { 0.0 },
+        );
+        diagnostics.add_measurement(Self::DROPPED_FRAMES, stats.last_frame_dropped as f64);
+        diagnostics.add_measurement(Self::TOTAL_LATE_FRAMES, stats.late_frames as f64);
+    }
+}
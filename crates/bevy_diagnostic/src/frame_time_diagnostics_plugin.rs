@@ -1,22 +1,89 @@
-use crate::{Diagnostic, DiagnosticId, Diagnostics};
-use bevy_app::prelude::*;
+use crate::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+use bevy_app::{prelude::*, PluginId};
 use bevy_core::Time;
 use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use std::collections::VecDeque;
 
-/// Adds "frame time" diagnostic to an App, specifically "frame time", "fps" and "frame count"
+/// Adds "frame time" diagnostic to an App, specifically "frame time", "fps", "fps 1% low" and
+/// "frame count"
 #[derive(Default)]
 pub struct FrameTimeDiagnosticsPlugin;
 
 pub struct FrameTimeDiagnosticsState {
     frame_count: f64,
+    /// The frame times the 1% low is computed from, which needs a longer history than the
+    /// average frame time
+    frame_times: SortedWindow,
+}
+
+impl Default for FrameTimeDiagnosticsState {
+    fn default() -> Self {
+        FrameTimeDiagnosticsState {
+            frame_count: 0.0,
+            frame_times: SortedWindow::new(FrameTimeDiagnosticsPlugin::ONE_PERCENT_LOW_FRAMES),
+        }
+    }
+}
+
+/// The last `capacity` values, also kept in sorted order so percentiles can be read every frame
+/// without sorting the whole window
+struct SortedWindow {
+    values: VecDeque<f64>,
+    sorted: Vec<f64>,
+    capacity: usize,
+}
+
+impl SortedWindow {
+    fn new(capacity: usize) -> Self {
+        SortedWindow {
+            values: VecDeque::with_capacity(capacity),
+            sorted: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if value.is_nan() || self.capacity == 0 {
+            return;
+        }
+        if self.values.len() == self.capacity {
+            if let Some(oldest) = self.values.pop_front() {
+                let index = self.position(oldest);
+                self.sorted.remove(index);
+            }
+        }
+        self.values.push_back(value);
+        let index = self.position(value);
+        self.sorted.insert(index, value);
+    }
+
+    fn position(&self, value: f64) -> usize {
+        self.sorted
+            .binary_search_by(|probe| probe.partial_cmp(&value).unwrap())
+            .unwrap_or_else(|index| index)
+    }
+
+    /// The nearest rank `percentile`, like [Diagnostic::percentile]
+    fn percentile(&self, percentile: f64) -> Option<f64> {
+        if self.sorted.is_empty() {
+            return None;
+        }
+        let rank =
+            (percentile.clamp(0.0, 100.0) / 100.0 * self.sorted.len() as f64).ceil() as usize;
+        Some(self.sorted[rank.max(1) - 1])
+    }
 }
 
 impl Plugin for FrameTimeDiagnosticsPlugin {
     fn build(&self, app: &mut bevy_app::AppBuilder) {
         app.add_startup_system(Self::setup_system.system())
-            .insert_resource(FrameTimeDiagnosticsState { frame_count: 0.0 })
+            .init_resource::<FrameTimeDiagnosticsState>()
             .add_system(Self::diagnostic_system.system());
     }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
 }
 
 impl FrameTimeDiagnosticsPlugin {
@@ -25,10 +92,17 @@ impl FrameTimeDiagnosticsPlugin {
         DiagnosticId::from_u128(54021991829115352065418785002088010277);
     pub const FRAME_TIME: DiagnosticId =
         DiagnosticId::from_u128(73441630925388532774622109383099159699);
+    /// The frames per second of the slowest 1% of the last [Self::ONE_PERCENT_LOW_FRAMES] frames
+    pub const FPS_1_PERCENT_LOW: DiagnosticId =
+        DiagnosticId::from_u128(212330871962817337592340536180296713349);
+
+    /// The number of frames the 1% low is computed over
+    pub const ONE_PERCENT_LOW_FRAMES: usize = 1000;
 
     pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
         diagnostics.add(Diagnostic::new(Self::FRAME_TIME, "frame_time", 20).with_suffix("s"));
         diagnostics.add(Diagnostic::new(Self::FPS, "fps", 20));
+        diagnostics.add(Diagnostic::new(Self::FPS_1_PERCENT_LOW, "fps_1%_low", 20));
         diagnostics.add(Diagnostic::new(Self::FRAME_COUNT, "frame_count", 1));
     }
 
@@ -61,5 +135,37 @@ impl FrameTimeDiagnosticsPlugin {
         {
             diagnostics.add_measurement(Self::FPS, fps);
         }
+
+        state.frame_times.push(time.delta_seconds_f64());
+        if let Some(frame_time) = state.frame_times.percentile(99.0) {
+            if frame_time > 0.0 {
+                diagnostics.add_measurement(Self::FPS_1_PERCENT_LOW, 1.0 / frame_time);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SortedWindow;
+    use crate::{Diagnostic, DiagnosticId};
+
+    #[test]
+    fn sorted_window_percentile() {
+        let mut window = SortedWindow::new(100);
+        let mut diagnostic = Diagnostic::new(DiagnosticId::default(), "test", 100);
+        assert_eq!(window.percentile(99.0), None);
+        for i in 0..250 {
+            let value = ((i * 37) % 101) as f64;
+            window.push(value);
+            diagnostic.add_measurement(value);
+            for percentile in [0.0, 50.0, 99.0, 100.0].iter() {
+                assert_eq!(
+                    window.percentile(*percentile),
+                    diagnostic.percentile(*percentile)
+                );
+            }
+        }
+        assert_eq!(window.sorted.len(), 100);
     }
 }
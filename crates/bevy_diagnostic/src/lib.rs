@@ -1,11 +1,24 @@
 mod diagnostic;
+mod ecs_memory_diagnostics_plugin;
 mod entity_count_diagnostics_plugin;
+mod export_diagnostics_plugin;
+mod frame_pacing_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod task_pool_diagnostics_plugin;
 pub use diagnostic::*;
+pub use ecs_memory_diagnostics_plugin::{
+    ArchetypeMemoryUsage, ComponentMemoryUsage, DumpEcsMemoryUsage, EcsMemoryDiagnosticsPlugin,
+    EcsMemoryUsage,
+};
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use export_diagnostics_plugin::{DiagnosticsFileFormat, ExportDiagnosticsPlugin};
+pub use frame_pacing_diagnostics_plugin::FramePacingDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use task_pool_diagnostics_plugin::{
+    TaskPoolDiagnosticIds, TaskPoolDiagnosticsPlugin, TaskPoolDiagnosticsState,
+};
 
 use bevy_app::prelude::*;
 
@@ -1,5 +1,5 @@
-use super::{Diagnostic, DiagnosticId, Diagnostics};
-use bevy_app::prelude::*;
+use super::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+use bevy_app::{prelude::*, PluginId};
 use bevy_core::{Time, Timer};
 use bevy_ecs::system::{IntoSystem, Res, ResMut};
 use bevy_log::{debug, info};
@@ -48,6 +48,10 @@ impl Plugin for LogDiagnosticsPlugin {
             app.add_system_to_stage(CoreStage::PostUpdate, Self::log_diagnostics_system.system());
         }
     }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
 }
 
 impl LogDiagnosticsPlugin {
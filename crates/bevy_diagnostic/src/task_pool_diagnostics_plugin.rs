@@ -0,0 +1,210 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+use bevy_app::{prelude::*, PluginId};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use bevy_tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPool, TaskPoolStats};
+
+/// Adds diagnostics for each of the default task pools: "queued tasks", "active threads", "task
+/// duration" and "idle", named after the pool, e.g. `io_queued_tasks`
+///
+/// The pools only collect these if they were created with
+/// [DefaultTaskPoolOptions::metrics](bevy_core::DefaultTaskPoolOptions::metrics) set, so insert
+/// the options before adding `CorePlugin`. Pools without metrics have no measurements.
+#[derive(Default)]
+pub struct TaskPoolDiagnosticsPlugin;
+
+/// The stats of each pool when the diagnostics were last measured
+#[derive(Default)]
+pub struct TaskPoolDiagnosticsState {
+    compute: TaskPoolStats,
+    async_compute: TaskPoolStats,
+    io: TaskPoolStats,
+}
+
+impl Plugin for TaskPoolDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_startup_system(Self::setup_system.system())
+            .init_resource::<TaskPoolDiagnosticsState>()
+            .add_system(Self::diagnostic_system.system());
+    }
+
+    fn dependencies(&self) -> Vec<PluginId> {
+        vec![PluginId::of::<DiagnosticsPlugin>()]
+    }
+}
+
+/// The diagnostics of one task pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskPoolDiagnosticIds {
+    /// The number of spawned tasks that have not started yet
+    pub queued_tasks: DiagnosticId,
+    /// The number of threads of the pool that are polling a task. Threads that poll tasks while
+    /// they wait in [TaskPool::scope] are not counted.
+    pub active_threads: DiagnosticId,
+    /// The average duration in seconds of the tasks that completed since the last frame
+    pub task_duration: DiagnosticId,
+    /// The percentage of time the threads of the pool spent waiting for tasks since the last
+    /// frame
+    pub idle: DiagnosticId,
+}
+
+impl TaskPoolDiagnosticIds {
+    pub const COMPUTE: TaskPoolDiagnosticIds = TaskPoolDiagnosticIds {
+        queued_tasks: DiagnosticId::from_u128(66142928240181901210243946823879053431),
+        active_threads: DiagnosticId::from_u128(57128926345300061538310438591869210335),
+        task_duration: DiagnosticId::from_u128(72934549678878621201175495642308987602),
+        idle: DiagnosticId::from_u128(9222540442221954545678551305050007557),
+    };
+    pub const ASYNC_COMPUTE: TaskPoolDiagnosticIds = TaskPoolDiagnosticIds {
+        queued_tasks: DiagnosticId::from_u128(117704592531893705141662635082390250874),
+        active_threads: DiagnosticId::from_u128(99292924628153416332067892812750535390),
+        task_duration: DiagnosticId::from_u128(167108510309147216173991160361344668773),
+        idle: DiagnosticId::from_u128(3118215946693973630735691037453199906),
+    };
+    pub const IO: TaskPoolDiagnosticIds = TaskPoolDiagnosticIds {
+        queued_tasks: DiagnosticId::from_u128(145836696013430231025020008425210772489),
+        active_threads: DiagnosticId::from_u128(92204655989857773497748076508300212836),
+        task_duration: DiagnosticId::from_u128(158297416996996865605647008591451120181),
+        idle: DiagnosticId::from_u128(8153076318734208974762044160526292478),
+    };
+
+    fn add_diagnostics(&self, diagnostics: &mut Diagnostics, pool_name: &str) {
+        diagnostics.add(Diagnostic::new(
+            self.queued_tasks,
+            format!("{}_queued_tasks", pool_name),
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            self.active_threads,
+            format!("{}_active_threads", pool_name),
+            20,
+        ));
+        diagnostics.add(
+            Diagnostic::new(
+                self.task_duration,
+                format!("{}_task_duration", pool_name),
+                20,
+            )
+            .with_suffix("s"),
+        );
+        diagnostics
+            .add(Diagnostic::new(self.idle, format!("{}_idle", pool_name), 20).with_suffix("%"));
+    }
+
+    fn add_measurements(
+        &self,
+        diagnostics: &mut Diagnostics,
+        pool: &TaskPool,
+        previous: &mut TaskPoolStats,
+    ) {
+        let stats = match pool.metrics() {
+            Some(metrics) => metrics.stats(),
+            None => return,
+        };
+        diagnostics.add_measurement(self.queued_tasks, stats.queued as f64);
+        diagnostics.add_measurement(self.active_threads, stats.running as f64);
+
+        let finished = stats.finished - previous.finished;
+        if finished > 0 {
+            let task_time = stats.task_time - previous.task_time;
+            diagnostics.add_measurement(
+                self.task_duration,
+                task_time.as_secs_f64() / finished as f64,
+            );
+        }
+
+        let available_time =
+            (stats.elapsed - previous.elapsed).as_secs_f64() * stats.threads as f64;
+        if available_time > 0.0 {
+            let idle_time = stats.idle_time.saturating_sub(previous.idle_time);
+            diagnostics.add_measurement(
+                self.idle,
+                (idle_time.as_secs_f64() / available_time * 100.0).min(100.0),
+            );
+        }
+        *previous = stats;
+    }
+}
+
+impl TaskPoolDiagnosticsPlugin {
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        TaskPoolDiagnosticIds::COMPUTE.add_diagnostics(&mut diagnostics, "compute");
+        TaskPoolDiagnosticIds::ASYNC_COMPUTE.add_diagnostics(&mut diagnostics, "async_compute");
+        TaskPoolDiagnosticIds::IO.add_diagnostics(&mut diagnostics, "io");
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut state: ResMut<TaskPoolDiagnosticsState>,
+        compute: Option<Res<ComputeTaskPool>>,
+        async_compute: Option<Res<AsyncComputeTaskPool>>,
+        io: Option<Res<IoTaskPool>>,
+    ) {
+        let state = &mut *state;
+        if let Some(pool) = compute {
+            TaskPoolDiagnosticIds::COMPUTE.add_measurements(
+                &mut diagnostics,
+                &pool,
+                &mut state.compute,
+            );
+        }
+        if let Some(pool) = async_compute {
+            TaskPoolDiagnosticIds::ASYNC_COMPUTE.add_measurements(
+                &mut diagnostics,
+                &pool,
+                &mut state.async_compute,
+            );
+        }
+        if let Some(pool) = io {
+            TaskPoolDiagnosticIds::IO.add_measurements(&mut diagnostics, &pool, &mut state.io);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TaskPoolDiagnosticIds, TaskPoolDiagnosticsPlugin};
+    use crate::{log_diagnostics_plugin::MAX_LOG_NAME_WIDTH, Diagnostics, DiagnosticsPlugin};
+    use bevy_app::{prelude::*, TestApp};
+    use bevy_core::{CorePlugin, DefaultTaskPoolOptions};
+    use bevy_tasks::IoTaskPool;
+    use std::time::Duration;
+
+    #[test]
+    fn task_pool_diagnostics() {
+        let mut app = TestApp::new(
+            App::build()
+                .insert_resource(DefaultTaskPoolOptions {
+                    metrics: true,
+                    ..Default::default()
+                })
+                .add_plugin(CorePlugin)
+                .add_plugin(DiagnosticsPlugin)
+                .add_plugin(TaskPoolDiagnosticsPlugin),
+        );
+        app.update();
+        app.world_mut()
+            .get_resource::<IoTaskPool>()
+            .unwrap()
+            .scope(|scope| scope.spawn(async { std::thread::sleep(Duration::from_millis(5)) }));
+        app.update();
+
+        let ids = TaskPoolDiagnosticIds::IO;
+        let diagnostics = app.world_mut().get_resource::<Diagnostics>().unwrap();
+        assert_eq!(
+            diagnostics.get(ids.queued_tasks).unwrap().value(),
+            Some(0.0)
+        );
+        assert!(diagnostics.get(ids.task_duration).unwrap().value().unwrap() >= 0.005);
+        let idle = diagnostics.get(ids.idle).unwrap().value().unwrap();
+        assert!((0.0..=100.0).contains(&idle), "{}", idle);
+
+        // the names fit in the log, so they don't warn
+        for diagnostic in diagnostics.iter() {
+            assert!(
+                diagnostic.name.chars().count() <= MAX_LOG_NAME_WIDTH,
+                "{}",
+                diagnostic.name
+            );
+        }
+    }
+}
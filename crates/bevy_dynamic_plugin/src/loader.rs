@@ -19,7 +19,6 @@ pub trait DynamicPluginExt {
 impl DynamicPluginExt for AppBuilder {
     fn load_plugin(&mut self, path: &str) -> &mut Self {
         let (_lib, plugin) = dynamically_load_plugin(path);
-        plugin.build(self);
-        self
+        self.add_boxed_plugin(plugin)
     }
 }
@@ -36,6 +36,7 @@ impl ArchetypeId {
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum ComponentStatus {
     Added,
     Mutated,
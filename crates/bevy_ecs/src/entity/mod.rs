@@ -293,6 +293,15 @@ impl Entities {
         loc
     }
 
+    /// Check whether the given ID is in use by a live entity, regardless of its generation
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    pub fn contains_id(&self, id: u32) -> bool {
+        self.meta.get(id as usize).map_or(false, |meta| {
+            meta.location.index != EntityMeta::EMPTY.location.index
+        })
+    }
+
     /// Destroy an entity, allowing it to be reused
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -12,7 +12,7 @@ pub mod world;
 
 pub mod prelude {
     #[cfg(feature = "bevy_reflect")]
-    pub use crate::reflect::ReflectComponent;
+    pub use crate::reflect::{ReflectComponent, ReflectResource};
     pub use crate::{
         bundle::Bundle,
         entity::Entity,
@@ -517,6 +517,85 @@ mod tests {
         assert_eq!(values, expected);
     }
 
+    #[test]
+    fn insert_or_spawn_batch() {
+        let mut world = World::new();
+        world
+            .register_component(ComponentDescriptor::new::<B>(StorageType::SparseSet))
+            .unwrap();
+        let e0 = world.spawn().insert(A(0)).id();
+        let e1 = Entity::new(3);
+        let stale = world.spawn().id();
+        world.despawn(stale);
+        let e2 = world.spawn().id();
+        assert_eq!(e2.id(), stale.id());
+
+        let result =
+            world.insert_or_spawn_batch(vec![(e0, (B(0),)), (e1, (B(1),)), (stale, (B(2),))]);
+        assert_eq!(result, Err(vec![stale]));
+        assert_eq!(world.get::<A>(e0).unwrap().0, 0);
+        assert_eq!(world.get::<B>(e0).unwrap().0, 0);
+        assert_eq!(world.get::<B>(e1).unwrap().0, 1);
+        assert!(world.get::<B>(e2).is_none());
+        assert_eq!(world.entities.len(), 3);
+
+        // ids skipped over by spawning at e1 can still be allocated
+        let spawned = world
+            .spawn_batch(vec![(A(1),), (A(2),)])
+            .collect::<Vec<_>>();
+        assert!(!spawned.contains(&e1));
+        assert_eq!(world.entities.len(), 5);
+    }
+
+    #[test]
+    fn insert_batch() {
+        let mut world = World::new();
+        let e0 = world.spawn().insert(A(0)).id();
+        let e1 = world.spawn().insert_bundle((A(1), B(1))).id();
+        let e2 = world.spawn().id();
+        world.insert_batch(vec![
+            (e0, (B(10), "a")),
+            (e1, (B(11), "b")),
+            (e2, (B(12), "c")),
+        ]);
+        let mut values = world
+            .query::<(Entity, &B, &&str)>()
+            .iter(&world)
+            .map(|(e, b, s)| (e, b.0, *s))
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![(e0, 10, "a"), (e1, 11, "b"), (e2, 12, "c")]);
+        assert_eq!(world.get::<A>(e0).unwrap().0, 0);
+        assert_eq!(world.get::<A>(e1).unwrap().0, 1);
+    }
+
+    #[test]
+    fn insert_batch_grouped_by_archetype() {
+        let mut world = World::new();
+        let entities = world
+            .spawn_batch((0..10).map(|i| (A(i),)))
+            .collect::<Vec<_>>();
+        let other = world.spawn().insert_bundle((A(10), "other")).id();
+        // entities of the same archetype are interleaved with another archetype, and the last
+        // entity is inserted into twice
+        let mut batch = entities
+            .iter()
+            .map(|e| (*e, (B(world.get::<A>(*e).unwrap().0),)))
+            .collect::<Vec<_>>();
+        batch.insert(5, (other, (B(10),)));
+        batch.push((entities[9], (B(99),)));
+        world.insert_batch(batch);
+
+        for (i, entity) in entities.iter().enumerate() {
+            assert_eq!(world.get::<A>(*entity).unwrap().0, i);
+            let b = if i == 9 { 99 } else { i };
+            assert_eq!(world.get::<B>(*entity).unwrap().0, b);
+        }
+        assert_eq!(world.get::<B>(other).unwrap().0, 10);
+        assert_eq!(world.get::<&str>(other), Some(&"other"));
+        assert_eq!(world.query::<(&A, &B)>().iter(&world).count(), 11);
+    }
+
     #[test]
     fn query_get() {
         let mut world = World::new();
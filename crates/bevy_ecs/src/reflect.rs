@@ -113,6 +113,72 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
     }
 }
 
+#[derive(Clone)]
+pub struct ReflectResource {
+    init_resource: fn(&mut World),
+    insert_resource: fn(&mut World, &dyn Reflect),
+    apply_resource: fn(&mut World, &dyn Reflect),
+    reflect_resource: fn(&World) -> Option<&dyn Reflect>,
+    reflect_resource_mut: fn(&mut World) -> Option<&mut dyn Reflect>,
+}
+
+impl ReflectResource {
+    /// Inserts the resource using [FromWorld] if it does not exist
+    pub fn init_resource(&self, world: &mut World) {
+        (self.init_resource)(world);
+    }
+
+    /// Inserts the resource, initializing it with [FromWorld] before applying `resource`
+    pub fn insert_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.insert_resource)(world, resource);
+    }
+
+    /// Applies `resource` to the existing resource, inserting it first if it does not exist
+    pub fn apply_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.apply_resource)(world, resource);
+    }
+
+    pub fn reflect_resource<'a>(&self, world: &'a World) -> Option<&'a dyn Reflect> {
+        (self.reflect_resource)(world)
+    }
+
+    pub fn reflect_resource_mut<'a>(&self, world: &'a mut World) -> Option<&'a mut dyn Reflect> {
+        (self.reflect_resource_mut)(world)
+    }
+}
+
+impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectResource {
+    fn from_type() -> Self {
+        ReflectResource {
+            init_resource: |world| {
+                if !world.contains_resource::<C>() {
+                    let resource = C::from_world(world);
+                    world.insert_resource(resource);
+                }
+            },
+            insert_resource: |world, reflected_resource| {
+                let mut resource = C::from_world(world);
+                resource.apply(reflected_resource);
+                world.insert_resource(resource);
+            },
+            apply_resource: |world, reflected_resource| {
+                if !world.contains_resource::<C>() {
+                    let resource = C::from_world(world);
+                    world.insert_resource(resource);
+                }
+                let mut resource = world.get_resource_mut::<C>().unwrap();
+                resource.apply(reflected_resource);
+            },
+            reflect_resource: |world| world.get_resource::<C>().map(|r| r as &dyn Reflect),
+            reflect_resource_mut: |world| {
+                world
+                    .get_resource_mut::<C>()
+                    .map(|r| r.into_inner() as &mut dyn Reflect)
+            },
+        }
+    }
+}
+
 /// Unique borrow of a Reflected component
 pub struct ReflectMut<'a> {
     pub(crate) value: &'a mut dyn Reflect,
@@ -112,6 +112,12 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// The number of components the sparse set can hold without reallocating
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.dense.capacity()
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse set.
     /// The caller is responsible for ensuring the value is not dropped. This collection will drop
     /// the value when needed.
@@ -439,6 +445,10 @@ impl SparseSets {
         self.sets.get(component_id)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &ComponentSparseSet)> {
+        self.sets.indices().zip(self.sets.values())
+    }
+
     pub fn get_mut(&mut self, component_id: ComponentId) -> Option<&mut ComponentSparseSet> {
         self.sets.get_mut(component_id)
     }
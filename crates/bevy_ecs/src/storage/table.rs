@@ -57,11 +57,22 @@ impl Column {
         self.data.set_unchecked(row, data);
     }
 
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.data.len()
     }
 
+    /// The number of components the column can hold without reallocating
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.data.is_empty()
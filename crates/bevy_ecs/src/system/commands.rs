@@ -310,9 +310,9 @@ where
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct Despawn {
-    entity: Entity,
+#[derive(Debug, Clone, Copy)]
+pub struct Despawn {
+    pub entity: Entity,
 }
 
 impl Command for Despawn {
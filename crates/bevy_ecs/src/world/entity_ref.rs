@@ -2,10 +2,11 @@ use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes, ComponentStatus},
     bundle::{Bundle, BundleInfo},
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
-    entity::{Entity, EntityLocation},
+    entity::{Entities, Entity, EntityLocation},
     storage::{SparseSet, Storages},
     world::{Mut, World},
 };
+use bevy_utils::HashMap;
 use std::any::TypeId;
 
 pub struct EntityRef<'w> {
@@ -182,89 +183,24 @@ impl<'w> EntityMut<'w> {
             })
     }
 
-    // TODO: factor out non-generic part to cut down on monomorphization (just check perf)
     // TODO: move relevant methods to World (add/remove bundle)
     pub fn insert_bundle<T: Bundle>(&mut self, bundle: T) -> &mut Self {
-        let entity = self.entity;
         let change_tick = self.world.change_tick();
-        let entities = &mut self.world.entities;
-        let archetypes = &mut self.world.archetypes;
-        let components = &mut self.world.components;
-        let storages = &mut self.world.storages;
-
-        let bundle_info = self.world.bundles.init_info::<T>(components);
-        let current_location = self.location;
-
-        let (archetype, bundle_status, archetype_index) = unsafe {
-            // SAFE: component ids in `bundle_info` and self.location are valid
-            let new_archetype_id = add_bundle_to_archetype(
-                archetypes,
-                storages,
-                components,
-                self.location.archetype_id,
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components);
+        // SAFE: self.location is the current location of self.entity and bundle components were
+        // initialized above
+        self.location = unsafe {
+            insert_bundle_at_location(
+                &mut self.world.entities,
+                &mut self.world.archetypes,
+                &mut self.world.components,
+                &mut self.world.storages,
                 bundle_info,
-            );
-            if new_archetype_id == current_location.archetype_id {
-                let archetype = &archetypes[current_location.archetype_id];
-                let edge = archetype.edges().get_add_bundle(bundle_info.id).unwrap();
-                (archetype, &edge.bundle_status, current_location.index)
-            } else {
-                let (old_table_row, old_table_id) = {
-                    let old_archetype = &mut archetypes[current_location.archetype_id];
-                    let result = old_archetype.swap_remove(current_location.index);
-                    if let Some(swapped_entity) = result.swapped_entity {
-                        entities.meta[swapped_entity.id as usize].location = current_location;
-                    }
-                    (result.table_row, old_archetype.table_id())
-                };
-
-                let new_table_id = archetypes[new_archetype_id].table_id();
-
-                let new_location = if old_table_id == new_table_id {
-                    archetypes[new_archetype_id].allocate(entity, old_table_row)
-                } else {
-                    let (old_table, new_table) =
-                        storages.tables.get_2_mut(old_table_id, new_table_id);
-                    // PERF: store "non bundle" components in edge, then just move those to avoid
-                    // redundant copies
-                    let move_result =
-                        old_table.move_to_superset_unchecked(old_table_row, new_table);
-
-                    let new_location =
-                        archetypes[new_archetype_id].allocate(entity, move_result.new_row);
-                    // if an entity was moved into this entity's table spot, update its table row
-                    if let Some(swapped_entity) = move_result.swapped_entity {
-                        let swapped_location = entities.get(swapped_entity).unwrap();
-                        archetypes[swapped_location.archetype_id]
-                            .set_entity_table_row(swapped_location.index, old_table_row);
-                    }
-                    new_location
-                };
-
-                self.location = new_location;
-                entities.meta[self.entity.id as usize].location = new_location;
-                let (old_archetype, new_archetype) =
-                    archetypes.get_2_mut(current_location.archetype_id, new_archetype_id);
-                let edge = old_archetype
-                    .edges()
-                    .get_add_bundle(bundle_info.id)
-                    .unwrap();
-                (&*new_archetype, &edge.bundle_status, new_location.index)
-
-                // Sparse set components are intentionally ignored here. They don't need to move
-            }
-        };
-
-        let table = &storages.tables[archetype.table_id()];
-        let table_row = archetype.entity_table_row(archetype_index);
-        // SAFE: table row is valid
-        unsafe {
-            bundle_info.write_components(
-                &mut storages.sparse_sets,
-                entity,
-                table,
-                table_row,
-                bundle_status,
+                self.entity,
+                self.location,
                 bundle,
                 change_tick,
             )
@@ -642,6 +578,198 @@ fn contains_component_with_id(
     world.archetypes[location.archetype_id].contains(component_id)
 }
 
+/// Moves `entity` from `current_location` into the archetype that results from adding the given
+/// bundle, then writes the bundle's components. Returns the new location of `entity`.
+///
+/// # Safety
+/// `current_location` must be the current location of `entity` and components in `bundle_info`
+/// must exist
+#[allow(clippy::too_many_arguments)]
+pub(crate) unsafe fn insert_bundle_at_location<T: Bundle>(
+    entities: &mut Entities,
+    archetypes: &mut Archetypes,
+    components: &mut Components,
+    storages: &mut Storages,
+    bundle_info: &BundleInfo,
+    entity: Entity,
+    current_location: EntityLocation,
+    bundle: T,
+    change_tick: u32,
+) -> EntityLocation {
+    let new_archetype_id = add_bundle_to_archetype(
+        archetypes,
+        storages,
+        components,
+        current_location.archetype_id,
+        bundle_info,
+    );
+    let new_location = if new_archetype_id == current_location.archetype_id {
+        current_location
+    } else {
+        move_entity_to_archetype(
+            entities,
+            archetypes,
+            storages,
+            entity,
+            current_location,
+            new_archetype_id,
+        )
+    };
+    let edge = archetypes[current_location.archetype_id]
+        .edges()
+        .get_add_bundle(bundle_info.id)
+        .unwrap();
+    let archetype = &archetypes[new_archetype_id];
+    let table = &storages.tables[archetype.table_id()];
+    let table_row = archetype.entity_table_row(new_location.index);
+    // SAFE: table row is valid
+    bundle_info.write_components(
+        &mut storages.sparse_sets,
+        entity,
+        table,
+        table_row,
+        &edge.bundle_status,
+        bundle,
+        change_tick,
+    );
+    new_location
+}
+
+/// Inserts each bundle into its entity. Entities are grouped by their current archetype, so the
+/// target archetype is looked up and its storage reserved once per group rather than once per
+/// entity.
+///
+/// # Safety
+/// every entity in `batch` must exist and components in `bundle_info` must exist
+pub(crate) unsafe fn insert_bundle_batch<T: Bundle>(
+    entities: &mut Entities,
+    archetypes: &mut Archetypes,
+    components: &mut Components,
+    storages: &mut Storages,
+    bundle_info: &BundleInfo,
+    batch: Vec<(Entity, T)>,
+    change_tick: u32,
+) {
+    let mut group_indices = HashMap::<ArchetypeId, usize>::default();
+    let mut groups: Vec<(ArchetypeId, Vec<(Entity, T)>)> = Vec::new();
+    for (entity, bundle) in batch {
+        let archetype_id = entities.get(entity).unwrap().archetype_id;
+        let index = *group_indices.entry(archetype_id).or_insert_with(|| {
+            groups.push((archetype_id, Vec::new()));
+            groups.len() - 1
+        });
+        groups[index].1.push((entity, bundle));
+    }
+
+    for (archetype_id, group) in groups {
+        let new_archetype_id =
+            add_bundle_to_archetype(archetypes, storages, components, archetype_id, bundle_info);
+        let bundle_status = archetypes[archetype_id]
+            .edges()
+            .get_add_bundle(bundle_info.id)
+            .unwrap()
+            .bundle_status
+            .clone();
+        if new_archetype_id != archetype_id {
+            let new_archetype = &mut archetypes[new_archetype_id];
+            new_archetype.reserve(group.len());
+            storages.tables[new_archetype.table_id()].reserve(group.len());
+        }
+
+        for (entity, bundle) in group {
+            let location = entities.get(entity).unwrap();
+            if location.archetype_id != archetype_id {
+                // the entity appears more than once in the batch and was already moved
+                insert_bundle_at_location(
+                    entities,
+                    archetypes,
+                    components,
+                    storages,
+                    bundle_info,
+                    entity,
+                    location,
+                    bundle,
+                    change_tick,
+                );
+                continue;
+            }
+            let new_location = if new_archetype_id == archetype_id {
+                location
+            } else {
+                move_entity_to_archetype(
+                    entities,
+                    archetypes,
+                    storages,
+                    entity,
+                    location,
+                    new_archetype_id,
+                )
+            };
+            let archetype = &archetypes[new_archetype_id];
+            let table = &storages.tables[archetype.table_id()];
+            let table_row = archetype.entity_table_row(new_location.index);
+            // SAFE: table row is valid
+            bundle_info.write_components(
+                &mut storages.sparse_sets,
+                entity,
+                table,
+                table_row,
+                &bundle_status,
+                bundle,
+                change_tick,
+            );
+        }
+    }
+}
+
+/// Moves `entity` from `current_location` into `new_archetype_id`, moving its table components if
+/// the table changes. Returns the new location of `entity`.
+///
+/// # Safety
+/// `current_location` must be the current location of `entity`, and `new_archetype_id` must contain
+/// every component of the current archetype
+unsafe fn move_entity_to_archetype(
+    entities: &mut Entities,
+    archetypes: &mut Archetypes,
+    storages: &mut Storages,
+    entity: Entity,
+    current_location: EntityLocation,
+    new_archetype_id: ArchetypeId,
+) -> EntityLocation {
+    let (old_table_row, old_table_id) = {
+        let old_archetype = &mut archetypes[current_location.archetype_id];
+        let result = old_archetype.swap_remove(current_location.index);
+        if let Some(swapped_entity) = result.swapped_entity {
+            entities.meta[swapped_entity.id as usize].location = current_location;
+        }
+        (result.table_row, old_archetype.table_id())
+    };
+
+    let new_table_id = archetypes[new_archetype_id].table_id();
+
+    let new_location = if old_table_id == new_table_id {
+        archetypes[new_archetype_id].allocate(entity, old_table_row)
+    } else {
+        let (old_table, new_table) = storages.tables.get_2_mut(old_table_id, new_table_id);
+        // PERF: store "non bundle" components in edge, then just move those to avoid
+        // redundant copies
+        let move_result = old_table.move_to_superset_unchecked(old_table_row, new_table);
+
+        let new_location = archetypes[new_archetype_id].allocate(entity, move_result.new_row);
+        // if an entity was moved into this entity's table spot, update its table row
+        if let Some(swapped_entity) = move_result.swapped_entity {
+            let swapped_location = entities.get(swapped_entity).unwrap();
+            archetypes[swapped_location.archetype_id]
+                .set_entity_table_row(swapped_location.index, old_table_row);
+        }
+        new_location
+    };
+
+    // Sparse set components are intentionally ignored here. They don't need to move
+    entities.meta[entity.id as usize].location = new_location;
+    new_location
+}
+
 /// Adds a bundle to the given archetype and returns the resulting archetype. This could be the same
 /// [ArchetypeId], in the event that adding the given bundle does not result in an Archetype change.
 /// Results are cached in the Archetype Graph to avoid redundant work.
@@ -333,6 +333,162 @@ impl World {
         SpawnBatchIter::new(self, iter.into_iter())
     }
 
+    /// For each `(entity, bundle)` pair, inserts the [Bundle] into `entity` if it exists, and
+    /// otherwise spawns `entity` at its given id with the [Bundle]. This is faster than doing the
+    /// equivalent operations one-by-one, as spawned entities are allocated directly in the
+    /// [Bundle]'s archetype, and existing entities that share an archetype are moved into the
+    /// target archetype together.
+    ///
+    /// Returns `Err` with the entities whose id is already in use by a live entity with a
+    /// different generation. These entities are skipped. This is useful when mirroring the
+    /// entities of another [World], such as when applying a network snapshot.
+    ///
+    /// ```
+    /// use bevy_ecs::{entity::Entity, world::World};
+    ///
+    /// let mut world = World::new();
+    /// let e0 = world.spawn().id();
+    /// let e1 = Entity::new(10);
+    /// world.insert_or_spawn_batch(vec![(e0, ("a", 0.0)), (e1, ("b", 1.0))]).unwrap();
+    ///
+    /// assert_eq!(world.get::<&str>(e0), Some(&"a"));
+    /// assert_eq!(world.get::<f64>(e1), Some(&1.0));
+    /// ```
+    pub fn insert_or_spawn_batch<I, B>(&mut self, iter: I) -> Result<(), Vec<Entity>>
+    where
+        I: IntoIterator<Item = (Entity, B)>,
+        B: Bundle,
+    {
+        self.flush();
+
+        let iter = iter.into_iter();
+        let change_tick = *self.change_tick.get_mut();
+        let bundle_info = self.bundles.init_info::<B>(&mut self.components);
+        // SAFE: empty archetype exists and bundle components were initialized above
+        let spawn_archetype_id = unsafe {
+            add_bundle_to_archetype(
+                &mut self.archetypes,
+                &mut self.storages,
+                &mut self.components,
+                ArchetypeId::empty(),
+                bundle_info,
+            )
+        };
+        let spawn_bundle_status = self
+            .archetypes
+            .empty()
+            .edges()
+            .get_add_bundle(bundle_info.id())
+            .unwrap()
+            .bundle_status
+            .clone();
+        let (lower, _) = iter.size_hint();
+        let spawn_archetype = &mut self.archetypes[spawn_archetype_id];
+        spawn_archetype.reserve(lower);
+        self.storages.tables[spawn_archetype.table_id()].reserve(lower);
+
+        let mut invalid_entities = Vec::new();
+        let mut existing = Vec::new();
+        for (entity, bundle) in iter {
+            if self.entities.get(entity).is_some() {
+                existing.push((entity, bundle));
+            } else if self.entities.contains_id(entity.id()) {
+                invalid_entities.push(entity);
+            } else {
+                // the id is free, so this will not replace an existing entity
+                self.entities.alloc_at(entity);
+                let archetype = &mut self.archetypes[spawn_archetype_id];
+                let table = &mut self.storages.tables[archetype.table_id()];
+                // SAFE: component values are immediately written to relevant storages (which have
+                // been allocated)
+                unsafe {
+                    let table_row = table.allocate(entity);
+                    let location = archetype.allocate(entity, table_row);
+                    bundle_info.write_components(
+                        &mut self.storages.sparse_sets,
+                        entity,
+                        table,
+                        table_row,
+                        &spawn_bundle_status,
+                        bundle,
+                        change_tick,
+                    );
+                    self.entities.meta[entity.id as usize].location = location;
+                }
+            }
+        }
+
+        // SAFE: the entities of `existing` were checked above and bundle components were
+        // initialized above
+        unsafe {
+            insert_bundle_batch(
+                &mut self.entities,
+                &mut self.archetypes,
+                &mut self.components,
+                &mut self.storages,
+                bundle_info,
+                existing,
+                change_tick,
+            );
+        }
+
+        if invalid_entities.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_entities)
+        }
+    }
+
+    /// For each `(entity, bundle)` pair, inserts the [Bundle] into `entity`. This is faster than
+    /// calling [EntityMut::insert_bundle] on each entity, as the [Bundle] is only initialized
+    /// once for the whole batch, and entities that share an archetype are moved into the target
+    /// archetype together.
+    ///
+    /// This will panic if any of the given entities do not exist, before any [Bundle] is inserted.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// let mut world = World::new();
+    /// let e0 = world.spawn().insert(1u32).id();
+    /// let e1 = world.spawn().id();
+    /// world.insert_batch(vec![(e0, ("a",)), (e1, ("b",))]);
+    ///
+    /// assert_eq!(world.get::<&str>(e0), Some(&"a"));
+    /// assert_eq!(world.get::<u32>(e0), Some(&1));
+    /// assert_eq!(world.get::<&str>(e1), Some(&"b"));
+    /// ```
+    pub fn insert_batch<I, B>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Entity, B)>,
+        B: Bundle,
+    {
+        self.flush();
+
+        let batch = iter.into_iter().collect::<Vec<_>>();
+        for (entity, _) in batch.iter() {
+            assert!(
+                self.entities.get(*entity).is_some(),
+                "Entity does not exist"
+            );
+        }
+        let change_tick = *self.change_tick.get_mut();
+        let bundle_info = self.bundles.init_info::<B>(&mut self.components);
+        // SAFE: the entities of `batch` were checked above and bundle components were initialized
+        // above
+        unsafe {
+            insert_bundle_batch(
+                &mut self.entities,
+                &mut self.archetypes,
+                &mut self.components,
+                &mut self.storages,
+                bundle_info,
+                batch,
+                change_tick,
+            );
+        }
+    }
+
     /// Retrieves a reference to the given `entity`'s [Component] of the given type.
     /// Returns [None] if the `entity` does not have a [Component] of the given type.
     /// ```
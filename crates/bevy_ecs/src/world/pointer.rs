@@ -46,4 +46,10 @@ impl<'w, T> Mut<'w, T> {
         self.component_ticks
             .is_changed(self.last_change_tick, self.change_tick)
     }
+
+    /// Marks the value as changed and returns a mutable reference to it, consuming self
+    pub fn into_inner(self) -> &'w mut T {
+        self.component_ticks.set_changed(self.change_tick);
+        self.value
+    }
 }
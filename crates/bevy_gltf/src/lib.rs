@@ -9,6 +9,7 @@ use bevy_pbr::prelude::StandardMaterial;
 use bevy_reflect::TypeUuid;
 use bevy_render::mesh::Mesh;
 use bevy_scene::Scene;
+use bevy_transform::hierarchy::EntityPath;
 
 /// Adds support for GLTF file loading to Apps
 #[derive(Default)]
@@ -17,6 +18,7 @@ pub struct GltfPlugin;
 impl Plugin for GltfPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_asset_loader::<GltfLoader>()
+            .register_type::<GltfSettings>()
             .add_asset::<Gltf>()
             .add_asset::<GltfNode>()
             .add_asset::<GltfPrimitive>()
@@ -35,6 +37,10 @@ pub struct Gltf {
     pub named_materials: HashMap<String, Handle<StandardMaterial>>,
     pub nodes: Vec<Handle<GltfNode>>,
     pub named_nodes: HashMap<String, Handle<GltfNode>>,
+    /// The nodes whose ancestors are all named, by their [EntityPath] in a spawned scene. The path
+    /// is relative to the unnamed entity a scene's nodes are spawned under, so use
+    /// [EntityPath::resolve_from] on that entity to find a node's entity.
+    pub node_paths: HashMap<EntityPath, Handle<GltfNode>>,
     pub default_scene: Option<Handle<Scene>>,
 }
 
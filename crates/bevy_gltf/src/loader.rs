@@ -6,6 +6,7 @@ use bevy_core::Name;
 use bevy_ecs::world::World;
 use bevy_math::Mat4;
 use bevy_pbr::prelude::{PbrBundle, StandardMaterial};
+use bevy_reflect::Reflect;
 use bevy_render::{
     camera::{
         Camera, CameraProjection, OrthographicProjection, PerspectiveProjection, VisibleEntities,
@@ -18,7 +19,7 @@ use bevy_render::{
 };
 use bevy_scene::Scene;
 use bevy_transform::{
-    hierarchy::{BuildWorldChildren, WorldChildBuilder},
+    hierarchy::{BuildWorldChildren, EntityPath, WorldChildBuilder},
     prelude::{GlobalTransform, Transform},
 };
 use gltf::{
@@ -59,6 +60,26 @@ pub enum GltfError {
 #[derive(Default)]
 pub struct GltfLoader;
 
+/// The settings of [GltfLoader], e.g. `(settings: (cameras: false))` in the `.meta` file of a
+/// GLTF file to import its scenes without cameras
+#[derive(Debug, Clone, Reflect)]
+pub struct GltfSettings {
+    /// Whether nodes with a camera get a [Camera] in the scenes
+    pub cameras: bool,
+    /// Whether nodes with a mesh get their primitives spawned in the scenes. The meshes are
+    /// loaded either way.
+    pub meshes: bool,
+}
+
+impl Default for GltfSettings {
+    fn default() -> Self {
+        GltfSettings {
+            cameras: true,
+            meshes: true,
+        }
+    }
+}
+
 impl AssetLoader for GltfLoader {
     fn load<'a>(
         &'a self,
@@ -71,6 +92,10 @@ impl AssetLoader for GltfLoader {
     fn extensions(&self) -> &[&str] {
         &["gltf", "glb"]
     }
+
+    fn default_settings(&self) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(GltfSettings::default()))
+    }
 }
 
 async fn load_gltf<'a, 'b>(
@@ -78,6 +103,7 @@ async fn load_gltf<'a, 'b>(
     load_context: &'a mut LoadContext<'b>,
 ) -> Result<(), GltfError> {
     let gltf = gltf::Gltf::from_slice(bytes)?;
+    let settings = load_context.settings::<GltfSettings>();
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
     let mut materials = vec![];
@@ -212,6 +238,10 @@ async fn load_gltf<'a, 'b>(
                 .map(|handle| (name.to_string(), handle.clone()))
         })
         .collect();
+    let node_paths = node_paths(&gltf)
+        .into_iter()
+        .filter_map(|(path, index)| nodes.get(index).map(|handle| (path, handle.clone())))
+        .collect();
 
     for gltf_texture in gltf.textures() {
         let mut texture = match gltf_texture.source().source() {
@@ -253,7 +283,7 @@ async fn load_gltf<'a, 'b>(
             .insert_bundle((Transform::identity(), GlobalTransform::identity()))
             .with_children(|parent| {
                 for node in scene.nodes() {
-                    let result = load_node(&node, parent, load_context, &buffer_data);
+                    let result = load_node(&node, parent, load_context, &buffer_data, &settings);
                     if result.is_err() {
                         err = Some(result);
                         return;
@@ -285,6 +315,7 @@ async fn load_gltf<'a, 'b>(
         named_materials,
         nodes,
         named_nodes,
+        node_paths,
     }));
 
     Ok(())
@@ -369,6 +400,7 @@ fn load_node(
     world_builder: &mut WorldChildBuilder,
     load_context: &mut LoadContext,
     buffer_data: &[Vec<u8>],
+    settings: &GltfSettings,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
@@ -382,7 +414,7 @@ fn load_node(
     }
 
     // create camera node
-    if let Some(camera) = gltf_node.camera() {
+    if let Some(camera) = gltf_node.camera().filter(|_| settings.cameras) {
         node.insert(VisibleEntities {
             ..Default::default()
         });
@@ -431,7 +463,7 @@ fn load_node(
     }
 
     node.with_children(|parent| {
-        if let Some(mesh) = gltf_node.mesh() {
+        if let Some(mesh) = gltf_node.mesh().filter(|_| settings.meshes) {
             // append primitives
             for primitive in mesh.primitives() {
                 let material = primitive.material();
@@ -460,7 +492,7 @@ fn load_node(
 
         // append other nodes
         for child in gltf_node.children() {
-            if let Err(err) = load_node(&child, parent, load_context, buffer_data) {
+            if let Err(err) = load_node(&child, parent, load_context, buffer_data, settings) {
                 gltf_error = Some(err);
                 return;
             }
@@ -600,6 +632,33 @@ async fn load_buffers(
     Ok(buffer_data)
 }
 
+/// The [EntityPath] of each node whose ancestors are all named, as spawned in the scene
+fn node_paths(gltf: &gltf::Gltf) -> Vec<(EntityPath, usize)> {
+    let mut parents = HashMap::new();
+    for node in gltf.nodes() {
+        for child in node.children() {
+            parents.insert(child.index(), node.index());
+        }
+    }
+
+    let names = gltf.nodes().map(|node| node.name()).collect::<Vec<_>>();
+    (0..names.len())
+        .filter_map(|index| {
+            let mut parts = Vec::new();
+            let mut current = Some(index);
+            while let Some(node) = current {
+                parts.push(names[node]?);
+                current = parents.get(&node).copied();
+            }
+            let mut path = EntityPath::default();
+            for part in parts.into_iter().rev() {
+                path.push(part);
+            }
+            Some((path, index))
+        })
+        .collect()
+}
+
 fn resolve_node_hierarchy(
     nodes_intermediate: Vec<(String, GltfNode, Vec<usize>)>,
 ) -> Vec<(String, GltfNode)> {
@@ -648,8 +707,8 @@ fn resolve_node_hierarchy(
 
 #[cfg(test)]
 mod test {
-    use super::resolve_node_hierarchy;
-    use crate::GltfNode;
+    use super::{resolve_node_hierarchy, Handle};
+    use crate::{GltfNode, GltfPlugin};
 
     impl GltfNode {
         fn empty() -> Self {
@@ -747,4 +806,72 @@ mod test {
         assert_eq!(result[0].0, "l2");
         assert_eq!(result[0].1.children.len(), 0);
     }
+
+    #[test]
+    fn settings() {
+        use bevy_app::App;
+        use bevy_asset::{
+            AddAsset, AssetPlugin, AssetServer, AssetServerSettings, Assets, LoadState,
+        };
+        use bevy_core::CorePlugin;
+        use bevy_render::camera::Camera;
+        use bevy_scene::Scene;
+        use std::{
+            fs,
+            time::{Duration, Instant},
+        };
+
+        let root = std::env::temp_dir().join(format!("bevy_gltf_settings_{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let gltf = r#"{
+            "asset": {"version": "2.0"},
+            "scenes": [{"nodes": [0]}],
+            "nodes": [{"name": "camera", "camera": 0}],
+            "cameras": [{"type": "perspective", "perspective": {"yfov": 1.0, "znear": 0.1}}]
+        }"#;
+        fs::write(root.join("camera.gltf"), gltf).unwrap();
+        fs::write(root.join("no_camera.gltf"), gltf).unwrap();
+        fs::write(
+            root.join("no_camera.gltf.meta"),
+            "(settings: (cameras: false))",
+        )
+        .unwrap();
+
+        let mut builder = App::build();
+        builder
+            .insert_resource(AssetServerSettings {
+                asset_folder: root.to_str().unwrap().to_string(),
+                ..Default::default()
+            })
+            .add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Scene>()
+            .add_plugin(GltfPlugin);
+        let mut app = builder.app;
+        let asset_server = app.world.get_resource::<AssetServer>().unwrap().clone();
+        let with_camera: Handle<Scene> = asset_server.load("camera.gltf#Scene0");
+        let without_camera: Handle<Scene> = asset_server.load("no_camera.gltf#Scene0");
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match asset_server.get_group_load_state(vec![with_camera.id, without_camera.id]) {
+                LoadState::Loaded => break,
+                LoadState::Failed => panic!("failed to load the scenes"),
+                _ => {}
+            }
+            assert!(Instant::now() < deadline, "timed out loading the scenes");
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut scenes = app.world.get_resource_mut::<Assets<Scene>>().unwrap();
+        let mut camera_count = |handle: &Handle<Scene>| {
+            let world = &mut scenes.get_mut(handle).unwrap().world;
+            world.query::<&Camera>().iter(world).count()
+        };
+        assert_eq!(camera_count(&with_camera), 1);
+        assert_eq!(camera_count(&without_camera), 0);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
@@ -99,6 +99,12 @@ pub mod pbr {
     pub use bevy_pbr::*;
 }
 
+#[cfg(feature = "bevy_remote")]
+pub mod remote {
+    //! Inspect and modify a running App from external tools.
+    pub use bevy_remote::*;
+}
+
 #[cfg(feature = "bevy_render")]
 pub mod render {
     //! Cameras, meshes, textures, shaders, and pipelines.
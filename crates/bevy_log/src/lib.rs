@@ -1,5 +1,10 @@
 #[cfg(target_os = "android")]
 mod android_tracing;
+mod log_capture;
+mod log_file;
+
+pub use log_capture::*;
+pub use log_file::*;
 
 pub mod prelude {
     pub use bevy_utils::tracing::{
@@ -11,7 +16,13 @@ pub use bevy_utils::tracing::{
     Level,
 };
 
-use bevy_app::{AppBuilder, Plugin};
+use bevy_app::{AppBuilder, CoreStage, Plugin};
+use bevy_ecs::{reflect::ReflectResource, system::IntoSystem};
+use bevy_reflect::{
+    erased_serde, GetTypeRegistration, Reflect, ReflectDeserialize, TypeRegistration,
+    TypeRegistryArc,
+};
+use serde::Deserialize;
 #[cfg(feature = "tracing-chrome")]
 use tracing_subscriber::fmt::{format::DefaultFields, FormattedFields};
 use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
@@ -21,6 +32,8 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 pub struct LogPlugin;
 
 /// LogPlugin settings
+#[derive(Reflect)]
+#[reflect(Resource)]
 pub struct LogSettings {
     /// Filters logs using the [EnvFilter] format
     pub filter: String,
@@ -28,6 +41,19 @@ pub struct LogSettings {
     /// Filters out logs that are "less than" the given level.
     /// This can be further filtered using the `filter` setting.
     pub level: Level,
+
+    /// How many recent lines are kept in the [LogBuffer]. 0 disables capturing logs.
+    pub capture_capacity: usize,
+
+    /// Also writes logs to files in this directory, see [LogFileWriter]. Not supported on wasm.
+    pub file_directory: Option<String>,
+
+    /// When a new log file is started
+    pub file_rotation: LogRotation,
+
+    /// How many log files are kept in `file_directory`, including the current one. 0 keeps all
+    /// files.
+    pub max_log_files: usize,
 }
 
 impl Default for LogSettings {
@@ -35,23 +61,100 @@ impl Default for LogSettings {
         Self {
             filter: "wgpu=error".to_string(),
             level: Level::INFO,
+            capture_capacity: 1000,
+            file_directory: None,
+            file_rotation: LogRotation::Session,
+            max_log_files: 10,
         }
     }
 }
 
+/// Deserializes a [Level] from its name, e.g. `"debug"`, as [Level] does not implement
+/// [Deserialize]
+fn deserialize_level(
+    deserializer: &mut dyn erased_serde::Deserializer,
+) -> Result<Box<dyn Reflect>, erased_serde::Error> {
+    let level = String::deserialize(deserializer)?;
+    let level = level
+        .parse::<Level>()
+        .map_err(<erased_serde::Error as serde::de::Error>::custom)?;
+    Ok(Box::new(level))
+}
+
+/// The registration of [Level], with a [ReflectDeserialize] that reads the level's name. This can't
+/// come from `impl_reflect_value!`, which needs [Level] to implement [Deserialize].
+fn level_registration() -> TypeRegistration {
+    let mut registration = Level::get_type_registration();
+    registration.insert(ReflectDeserialize {
+        func: deserialize_level,
+    });
+    registration
+}
+
 impl Plugin for LogPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        let default_filter = {
-            let settings = app
-                .world_mut()
-                .get_resource_or_insert_with(LogSettings::default);
-            format!("{},{}", settings.level, settings.filter)
+        if app.world().contains_resource::<TypeRegistryArc>() {
+            app.register_type::<LogRotation>()
+                .register_type::<Option<String>>();
+            app.world_mut()
+                .get_resource_mut::<TypeRegistryArc>()
+                .unwrap()
+                .write()
+                .add_registration(level_registration());
+            app.init_settings::<LogSettings>();
+        } else {
+            // settings are loaded through the type registry, which apps built with
+            // `AppBuilder::empty` don't have
+            app.init_resource::<LogSettings>();
+        }
+
+        let (default_filter, capture_capacity, file_settings) = {
+            let settings = app.world().get_resource::<LogSettings>().unwrap();
+            (
+                format!("{},{}", settings.level, settings.filter),
+                settings.capture_capacity,
+                settings
+                    .file_directory
+                    .clone()
+                    .map(|directory| (directory, settings.file_rotation, settings.max_log_files)),
+            )
         };
 
+        let capture_layer = LogCaptureLayer::new(capture_capacity);
+        app.insert_resource(capture_layer.capture())
+            .insert_resource(LogBuffer::with_capacity(capture_capacity))
+            .add_event::<LogEntry>()
+            .add_system_to_stage(CoreStage::First, log_capture_system.system());
+
         let filter_layer = EnvFilter::try_from_default_env()
             .or_else(|_| EnvFilter::try_new(&default_filter))
             .unwrap();
-        let subscriber = Registry::default().with(filter_layer);
+        let subscriber = Registry::default().with(filter_layer).with(capture_layer);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let subscriber = {
+            let file_layer = file_settings.and_then(|(directory, rotation, max_files)| {
+                match LogFileWriter::new(&directory, rotation, max_files) {
+                    Ok(writer) => {
+                        app.insert_resource(writer.clone())
+                            .add_shutdown_system(flush_log_file_system.system());
+                        Some(
+                            tracing_subscriber::fmt::Layer::default()
+                                .with_ansi(false)
+                                .with_writer(move || writer.clone()),
+                        )
+                    }
+                    Err(err) => {
+                        // the subscriber that would log this error does not exist yet
+                        eprintln!("Failed to create log file in {}: {}", directory, err);
+                        None
+                    }
+                }
+            });
+            subscriber.with(file_layer)
+        };
+        #[cfg(target_arch = "wasm32")]
+        let _ = file_settings;
 
         #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
         {
@@ -103,4 +206,9 @@ impl Plugin for LogPlugin {
                 .expect("Could not set global default tracing subscriber. If you've already set up a tracing subscriber, please disable LogPlugin from Bevy's DefaultPlugins");
         }
     }
+
+    /// The global tracing subscriber can only be set once
+    fn is_unique(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,326 @@
+use bevy_app::EventWriter;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_utils::tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// A log line captured by the [LogCaptureLayer]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub level: Level,
+    pub target: String,
+    /// The log message, followed by the other fields of the event as `name=value`
+    pub message: String,
+    /// The number of frames that ran before the line was logged
+    pub frame: u64,
+}
+
+/// Selects [LogEntry]s, e.g. to only show warnings of a module in a console
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Only matches entries that are at least as severe as this level
+    pub level: Option<Level>,
+    /// Only matches entries whose target starts with this prefix
+    pub target: Option<String>,
+    /// Only matches entries whose message contains this text
+    pub contains: Option<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.level.map_or(true, |level| entry.level <= level)
+            && self
+                .target
+                .as_ref()
+                .map_or(true, |target| entry.target.starts_with(target.as_str()))
+            && self
+                .contains
+                .as_ref()
+                .map_or(true, |contains| entry.message.contains(contains.as_str()))
+    }
+}
+
+/// The most recent log lines, oldest first.
+///
+/// [LogPlugin](crate::LogPlugin) keeps the last [LogSettings::capture_capacity](crate::LogSettings::capture_capacity)
+/// lines, and sends a [LogEntry] event for each new line, so in-game consoles and overlays can
+/// show logs.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        LogBuffer::with_capacity(1000)
+    }
+}
+
+impl LogBuffer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        LogBuffer {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Adds an entry, removing the oldest entry if the buffer is full
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn iter_filtered<'a>(
+        &'a self,
+        filter: &'a LogFilter,
+    ) -> impl DoubleEndedIterator<Item = &'a LogEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| filter.matches(entry))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// The state shared by a [LogCaptureLayer] and the App it captures logs for
+#[derive(Debug, Clone)]
+pub struct LogCapture {
+    pending: Arc<Mutex<VecDeque<LogEntry>>>,
+    frame: Arc<AtomicU64>,
+    capacity: usize,
+}
+
+impl LogCapture {
+    /// Removes the entries captured since the last call
+    pub fn take(&self) -> VecDeque<LogEntry> {
+        mem::take(&mut *self.pending.lock().unwrap())
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame.load(Ordering::Relaxed)
+    }
+}
+
+/// A tracing [Layer] that captures log events as [LogEntry]s.
+///
+/// [LogPlugin](crate::LogPlugin) installs this layer. If you set up your own subscriber instead,
+/// add the layer to it, insert its [LogCapture] as a resource, and add [log_capture_system] and
+/// the [LogBuffer] and [LogEntry] event resources to the App.
+#[derive(Debug, Clone)]
+pub struct LogCaptureLayer {
+    capture: LogCapture,
+}
+
+impl LogCaptureLayer {
+    /// Creates a layer that keeps at most `capacity` lines until they are moved into the
+    /// [LogBuffer]. A capacity of 0 disables capturing.
+    pub fn new(capacity: usize) -> Self {
+        LogCaptureLayer {
+            capture: LogCapture {
+                pending: Default::default(),
+                frame: Default::default(),
+                capacity,
+            },
+        }
+    }
+
+    /// The state to insert as a resource into the App
+    pub fn capture(&self) -> LogCapture {
+        self.capture.clone()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogCaptureLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if self.capture.capacity == 0 {
+            return;
+        }
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        visitor.message.push_str(&visitor.fields);
+        let entry = LogEntry {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            frame: self.capture.frame(),
+        };
+        let mut pending = self.capture.pending.lock().unwrap();
+        // the App may not update, e.g. while it is starting up
+        if pending.len() == self.capture.capacity {
+            pending.pop_front();
+        }
+        pending.push_back(entry);
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Moves the lines captured by the [LogCaptureLayer] into the [LogBuffer], sends a [LogEntry]
+/// event for each of them, and advances the frame number of new lines
+pub fn log_capture_system(
+    capture: Res<LogCapture>,
+    mut buffer: ResMut<LogBuffer>,
+    mut events: EventWriter<LogEntry>,
+) {
+    for entry in capture.take() {
+        buffer.push(entry.clone());
+        events.send(entry);
+    }
+    capture.frame.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{log_capture_system, LogBuffer, LogCaptureLayer, LogEntry, LogFilter};
+    use bevy_app::{EventReader, Events};
+    use bevy_ecs::{
+        schedule::{ParallelSystemDescriptorCoercion, Schedule, Stage, SystemStage},
+        system::{IntoSystem, ResMut},
+        world::World,
+    };
+    use bevy_utils::tracing::{self, info, warn, Level};
+    use tracing_subscriber::{prelude::*, registry::Registry};
+
+    #[derive(Default)]
+    struct Received(Vec<String>);
+
+    fn receive(mut events: EventReader<LogEntry>, mut received: ResMut<Received>) {
+        received
+            .0
+            .extend(events.iter().map(|entry| entry.message.clone()));
+    }
+
+    fn entry(level: Level, target: &str, message: &str) -> LogEntry {
+        LogEntry {
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+            frame: 0,
+        }
+    }
+
+    #[test]
+    fn capture() {
+        let layer = LogCaptureLayer::new(3);
+        let mut world = World::new();
+        world.insert_resource(layer.capture());
+        world.insert_resource(LogBuffer::with_capacity(2));
+        world.insert_resource(Events::<LogEntry>::default());
+        world.insert_resource(Received::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage(
+            "update",
+            SystemStage::single_threaded()
+                .with_system(log_capture_system.system().label("capture"))
+                .with_system(receive.system().after("capture")),
+        );
+
+        let subscriber = Registry::default().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            info!(target: "game", "first");
+            schedule.run(&mut world);
+            info!(target: "game", "second");
+            warn!(target: "game::physics", speed = 3, "third");
+            info!(target: "game", "fourth");
+            info!(target: "game", "fifth");
+        });
+        schedule.run(&mut world);
+
+        let buffer = world.get_resource::<LogBuffer>().unwrap();
+        let entries = buffer.iter().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "fourth");
+        assert_eq!(entries[0].frame, 1);
+        assert_eq!(entries[1].message, "fifth");
+        // the layer only kept the last 3 lines logged between the two frames
+        let received = &world.get_resource::<Received>().unwrap().0;
+        assert_eq!(received, &["first", "third speed=3", "fourth", "fifth"]);
+    }
+
+    #[test]
+    fn filter() {
+        let mut buffer = LogBuffer::with_capacity(10);
+        buffer.push(entry(Level::ERROR, "game::physics", "exploded"));
+        buffer.push(entry(Level::INFO, "game::physics", "stepped"));
+        buffer.push(entry(Level::WARN, "wgpu", "slow"));
+
+        let warnings = LogFilter {
+            level: Some(Level::WARN),
+            ..Default::default()
+        };
+        let messages = |filter: &LogFilter| {
+            buffer
+                .iter_filtered(filter)
+                .map(|entry| entry.message.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(messages(&warnings), vec!["exploded", "slow"]);
+        let physics = LogFilter {
+            target: Some("game::physics".to_string()),
+            contains: Some("step".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(messages(&physics), vec!["stepped"]);
+    }
+}
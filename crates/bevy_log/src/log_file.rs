@@ -0,0 +1,336 @@
+use bevy_ecs::system::Res;
+use bevy_reflect::{Reflect, ReflectDeserialize};
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// When [LogSettings::file_directory](crate::LogSettings::file_directory) starts a new log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum LogRotation {
+    /// Every time the App starts
+    Session,
+    /// Every time the App starts, and when the current file would grow beyond this many bytes
+    Size(u64),
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        LogRotation::Session
+    }
+}
+
+enum Message {
+    Line(Vec<u8>),
+    Flush(Sender<()>),
+}
+
+/// Writes log lines to rotating files in a directory, without blocking the threads that log.
+///
+/// Lines are sent to a background thread that does the disk I/O. If that thread falls more than
+/// [LogFileWriter::QUEUE_CAPACITY] lines behind, new lines are dropped, and a line saying how many
+/// were dropped is written once the thread catches up. Files are named
+/// `bevy-<session start in milliseconds>-<index>.log`, so they sort by age. Existing files are
+/// never overwritten: if a file of another App that started in the same millisecond exists, the
+/// session is moved to the next millisecond.
+#[derive(Debug, Clone)]
+pub struct LogFileWriter {
+    sender: Sender<Message>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl LogFileWriter {
+    pub const QUEUE_CAPACITY: usize = 16 * 1024;
+    pub const FILE_PREFIX: &'static str = "bevy-";
+    pub const FILE_EXTENSION: &'static str = ".log";
+
+    /// Starts a new log file in `directory`, and removes the oldest log files so that at most
+    /// `max_files` remain. A `max_files` of 0 keeps all files.
+    pub fn new(
+        directory: impl Into<PathBuf>,
+        rotation: LogRotation,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        let session = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        Self::with_session(directory.into(), rotation, max_files, session)
+    }
+
+    fn with_session(
+        directory: PathBuf,
+        rotation: LogRotation,
+        max_files: usize,
+        session: u128,
+    ) -> io::Result<Self> {
+        let mut files = LogFiles {
+            directory,
+            rotation,
+            max_files,
+            session,
+            index: 0,
+            file: None,
+            size: 0,
+        };
+        fs::create_dir_all(&files.directory)?;
+        files.open()?;
+
+        let (sender, receiver) = crossbeam_channel::bounded(Self::QUEUE_CAPACITY);
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let thread_dropped = dropped.clone();
+        thread::Builder::new()
+            .name("log file writer".to_string())
+            .spawn(move || files.run(receiver, thread_dropped))?;
+        Ok(LogFileWriter { sender, dropped })
+    }
+
+    /// Blocks until all lines logged so far were written to disk, or `timeout` passed. Returns
+    /// `false` on timeout.
+    pub fn flush_timeout(&self, timeout: Duration) -> bool {
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+        self.sender
+            .send_timeout(Message::Flush(sender), timeout)
+            .is_ok()
+            && receiver.recv_timeout(timeout).is_ok()
+    }
+}
+
+impl Write for LogFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.sender.try_send(Message::Line(buf.to_vec())) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            // the writer thread stopped after an I/O error it already reported
+            Err(TrySendError::Disconnected(_)) => {}
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Flushes the [LogFileWriter] when the App shuts down
+pub fn flush_log_file_system(writer: Res<LogFileWriter>) {
+    writer.flush_timeout(Duration::from_secs(1));
+}
+
+struct LogFiles {
+    directory: PathBuf,
+    rotation: LogRotation,
+    max_files: usize,
+    session: u128,
+    index: u32,
+    file: Option<BufWriter<File>>,
+    size: u64,
+}
+
+impl LogFiles {
+    fn run(mut self, receiver: Receiver<Message>, dropped: Arc<AtomicUsize>) {
+        while let Ok(message) = receiver.recv() {
+            let result = match message {
+                Message::Line(line) => {
+                    let dropped = dropped.swap(0, Ordering::Relaxed);
+                    let mut result = Ok(());
+                    if dropped > 0 {
+                        let notice = format!(
+                            "{} log lines were dropped because the log file could not keep up\n",
+                            dropped
+                        );
+                        result = self.write(notice.as_bytes());
+                    }
+                    result.and_then(|_| self.write(&line))
+                }
+                Message::Flush(done) => {
+                    let result = self.flush();
+                    let _ = done.send(());
+                    result
+                }
+            };
+            // flush whenever the queue is empty, so lines reach the disk soon after they are
+            // logged, e.g. before a crash
+            let result = result.and_then(|_| {
+                if receiver.is_empty() {
+                    self.flush()
+                } else {
+                    Ok(())
+                }
+            });
+            if let Err(err) = result {
+                // logging the error would send it back to this thread
+                eprintln!(
+                    "Failed to write log file in {}, logs will no longer be written to files: {}",
+                    self.directory.display(),
+                    err
+                );
+                return;
+            }
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        self.directory.join(format!(
+            "{}{:013}-{:06}{}",
+            LogFileWriter::FILE_PREFIX,
+            self.session,
+            self.index,
+            LogFileWriter::FILE_EXTENSION
+        ))
+    }
+
+    fn open(&mut self) -> io::Result<()> {
+        const MAX_ATTEMPTS: u32 = 100;
+
+        self.flush()?;
+        let mut attempt = 1;
+        let file = loop {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.path())
+            {
+                Ok(file) => break file,
+                Err(err)
+                    if err.kind() == io::ErrorKind::AlreadyExists && attempt < MAX_ATTEMPTS =>
+                {
+                    self.session += 1;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        self.file = Some(BufWriter::new(file));
+        self.size = 0;
+        remove_old_files(&self.directory, self.max_files)
+    }
+
+    fn write(&mut self, line: &[u8]) -> io::Result<()> {
+        if let LogRotation::Size(max_size) = self.rotation {
+            if self.size > 0 && self.size + line.len() as u64 > max_size {
+                self.index += 1;
+                self.open()?;
+            }
+        }
+        if let Some(file) = &mut self.file {
+            file.write_all(line)?;
+            self.size += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The log files in `directory`, oldest first
+fn log_files(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let is_log_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| {
+                name.starts_with(LogFileWriter::FILE_PREFIX)
+                    && name.ends_with(LogFileWriter::FILE_EXTENSION)
+            });
+        if is_log_file {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn remove_old_files(directory: &Path, max_files: usize) -> io::Result<()> {
+    if max_files == 0 {
+        return Ok(());
+    }
+    let files = log_files(directory)?;
+    if files.len() > max_files {
+        for path in &files[..files.len() - max_files] {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{log_files, LogFileWriter, LogRotation};
+    use std::{
+        fs,
+        io::Write,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    fn directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir()
+            .join(format!("bevy_log_{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn contents(directory: &Path) -> Vec<String> {
+        log_files(directory)
+            .unwrap()
+            .iter()
+            .map(|path| fs::read_to_string(path).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn session_rotation() {
+        let directory = directory("session");
+        for session in 0..3 {
+            // all sessions start in the same millisecond, so they must not overwrite each other
+            let mut writer =
+                LogFileWriter::with_session(directory.clone(), LogRotation::Session, 2, 1000)
+                    .unwrap();
+            writer
+                .write_all(format!("session {}\n", session).as_bytes())
+                .unwrap();
+            assert!(writer.flush_timeout(Duration::from_secs(5)));
+        }
+        assert_eq!(contents(&directory), vec!["session 1\n", "session 2\n"]);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn size_rotation() {
+        let directory = directory("size");
+        let mut writer = LogFileWriter::new(&directory, LogRotation::Size(20), 3).unwrap();
+        for line in 0..10 {
+            writer
+                .write_all(format!("line {}\n", line).as_bytes())
+                .unwrap();
+        }
+        assert!(writer.flush_timeout(Duration::from_secs(5)));
+        // each line is 7 bytes, so each file fits 2 lines
+        assert_eq!(
+            contents(&directory),
+            vec!["line 4\nline 5\n", "line 6\nline 7\n", "line 8\nline 9\n"]
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
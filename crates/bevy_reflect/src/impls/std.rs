@@ -4,7 +4,7 @@ use crate::{
 };
 
 use bevy_reflect_derive::impl_reflect_value;
-use bevy_utils::{Duration, HashMap, HashSet};
+use bevy_utils::{tracing::Level, Duration, HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use std::{
     any::Any,
@@ -33,6 +33,9 @@ impl_reflect_value!(Option<T: Serialize + Clone + for<'de> Deserialize<'de> + Re
 impl_reflect_value!(HashSet<T: Serialize + Hash + Eq + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
 impl_reflect_value!(Range<T: Serialize + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
 impl_reflect_value!(Duration);
+// Level does not implement Deserialize. Plugins that need to deserialize it register their own
+// ReflectDeserialize
+impl_reflect_value!(Level(Hash, PartialEq));
 
 impl<T: Reflect> List for Vec<T> {
     fn get(&self, index: usize) -> Option<&dyn Reflect> {
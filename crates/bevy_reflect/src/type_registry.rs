@@ -40,7 +40,10 @@ impl TypeRegistry {
 
     pub fn add_registration(&mut self, registration: TypeRegistration) {
         let short_name = registration.short_name.to_string();
-        if self.short_name_to_id.contains_key(&short_name)
+        if self
+            .short_name_to_id
+            .get(&short_name)
+            .map_or(false, |type_id| *type_id != registration.type_id)
             || self.ambiguous_names.contains(&short_name)
         {
             // name is ambiguous. fall back to long names for all ambiguous types
@@ -253,7 +256,26 @@ impl<T: for<'a> Deserialize<'a> + Reflect> FromType<T> for ReflectDeserialize {
 
 #[cfg(test)]
 mod test {
-    use crate::TypeRegistration;
+    use crate::{TypeRegistration, TypeRegistry};
+
+    #[test]
+    fn register_again() {
+        #[derive(Clone)]
+        struct Marker;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry
+            .get_mut(std::any::TypeId::of::<u32>())
+            .unwrap()
+            .insert(Marker);
+
+        // registering a type again replaces its registration, but its short name is not
+        // ambiguous with itself
+        registry.register::<u32>();
+        let registration = registry.get_with_short_name("u32").unwrap();
+        assert!(registration.data::<Marker>().is_none());
+    }
 
     #[test]
     fn test_get_short_name() {
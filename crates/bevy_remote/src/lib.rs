@@ -0,0 +1,526 @@
+mod protocol;
+
+pub use protocol::{process_request, RemoteError};
+
+use protocol::check_type;
+
+use bevy_app::{prelude::*, Events};
+use bevy_ecs::{
+    component::Component,
+    system::{Commands, IntoExclusiveSystem, IntoSystem},
+    world::{FromWorld, World},
+};
+use bevy_log::{error, info, warn};
+use bevy_reflect::{GetTypeRegistration, Reflect, TypeRegistration};
+use crossbeam_channel::{Receiver, Sender};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// Starts a server that lets external tools inspect and modify the App over a local TCP socket.
+///
+/// Clients send one JSON request per line and receive one JSON response per line:
+///
+/// ```text
+/// > {"id": 1, "method": "get_component", "params": {"entity": 4, "component": "Health"}}
+/// < {"id":1,"result":{"type":"my_game::Health","struct":{"value":{"type":"f32","value":90.0}}}}
+/// ```
+///
+/// `id` is optional and is returned unchanged. A failed request returns `{"id": .., "error":
+/// "message"}` instead of a `result`. Requests are handled by an exclusive system in
+/// [CoreStage::PreUpdate], so a request waits for the next frame. The methods are:
+///
+/// * `list_entities`, with optional params `{"with": [component names]}`: a list of
+///   `{"entity", "components"}` objects, where `entity` is [Entity::to_bits](bevy_ecs::entity::Entity::to_bits)
+/// * `list_components`: the `name` and `short_name` of every type that reflects `Component`
+/// * `get_component`, with params `{"entity", "component"}`: the component, as written by
+///   [ReflectSerializer](bevy_reflect::serde::ReflectSerializer)
+/// * `set_component`, with params `{"entity", "component", "value"}`: applies `value`, in the
+///   format returned by `get_component`, to the component. The component is added if the entity
+///   does not have it.
+/// * `diagnostics`: the `id`, `name`, `value` and `average` of every
+///   [Diagnostic](bevy_diagnostic::Diagnostic)
+/// * `send_event`, with params `{"event", "value"}`: sends an event that was added with
+///   [AddRemoteEvent::add_remote_event]
+///
+/// Types can be named by their full or short type name. Anyone who can connect to the socket
+/// can change the App, so the server only listens on the loopback interface by default.
+///
+/// The server stops listening when the App shuts down, or when the [RemoteServer] resource is
+/// removed.
+pub struct RemotePlugin {
+    pub address: SocketAddr,
+}
+
+impl RemotePlugin {
+    pub const DEFAULT_PORT: u16 = 15702;
+}
+
+impl Default for RemotePlugin {
+    fn default() -> Self {
+        RemotePlugin {
+            address: ([127, 0, 0, 1], Self::DEFAULT_PORT).into(),
+        }
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<RemoteEvents>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                remote_request_system.exclusive_system(),
+            )
+            .add_shutdown_system(stop_remote_server_system.system());
+
+        let listener = match TcpListener::bind(self.address) {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("Failed to start remote server on {}: {}", self.address, err);
+                return;
+            }
+        };
+        let local_addr = match listener.local_addr() {
+            Ok(local_addr) => local_addr,
+            Err(err) => {
+                error!("Failed to start remote server on {}: {}", self.address, err);
+                return;
+            }
+        };
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let listener_shutdown = shutdown.clone();
+        let listener_thread = thread::Builder::new()
+            .name("remote listener".to_string())
+            .spawn(move || listen(listener, sender, listener_shutdown))
+            .expect("failed to spawn the remote listener thread");
+        info!("Remote server listening on {}", local_addr);
+
+        app.insert_resource(RemoteServer {
+            local_addr,
+            requests: receiver,
+            shutdown,
+            listener_thread: Some(listener_thread),
+        });
+    }
+}
+
+/// The server started by the [RemotePlugin]. This resource does not exist if the server failed
+/// to start. Dropping it stops the server from accepting new connections.
+pub struct RemoteServer {
+    local_addr: SocketAddr,
+    requests: Receiver<RemoteRequest>,
+    shutdown: Arc<AtomicBool>,
+    listener_thread: Option<JoinHandle<()>>,
+}
+
+impl RemoteServer {
+    /// The address the server listens on. This is useful if the [RemotePlugin] was configured
+    /// with port 0.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // the listener thread blocks until a connection arrives, so connect to wake it up
+        let mut wake_addr = self.local_addr;
+        if wake_addr.ip().is_unspecified() {
+            wake_addr.set_ip(match wake_addr {
+                SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+                SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+            });
+        }
+        match TcpStream::connect(wake_addr) {
+            Ok(_) => {
+                if let Some(listener_thread) = self.listener_thread.take() {
+                    let _ = listener_thread.join();
+                }
+            }
+            Err(err) => warn!("Failed to stop the remote server on {}: {}", wake_addr, err),
+        }
+    }
+}
+
+struct RemoteRequest {
+    line: String,
+    response: Sender<String>,
+}
+
+/// Handles the requests received since the last frame
+pub fn remote_request_system(world: &mut World) {
+    let requests = match world.get_resource::<RemoteServer>() {
+        Some(server) => server.requests.clone(),
+        None => return,
+    };
+    for request in requests.try_iter() {
+        let response = process_request(world, &request.line);
+        // the client may have disconnected in the meantime
+        let _ = request.response.send(response);
+    }
+}
+
+/// Stops the [RemoteServer] when the App shuts down
+pub fn stop_remote_server_system(mut commands: Commands) {
+    commands.remove_resource::<RemoteServer>();
+}
+
+fn listen(listener: TcpListener, requests: Sender<RemoteRequest>, shutdown: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::Relaxed) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let requests = requests.clone();
+                let spawned = thread::Builder::new()
+                    .name("remote connection".to_string())
+                    .spawn(move || {
+                        if let Err(err) = handle_connection(stream, requests) {
+                            warn!("Remote connection failed: {}", err);
+                        }
+                    });
+                if let Err(err) = spawned {
+                    warn!("Failed to spawn a thread for a remote connection: {}", err);
+                }
+            }
+            Err(err) => warn!("Failed to accept a remote connection: {}", err),
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, requests: Sender<RemoteRequest>) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let (response_sender, responses) = crossbeam_channel::bounded(1);
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = RemoteRequest {
+            line,
+            response: response_sender.clone(),
+        };
+        // the channels disconnect when the App is dropped
+        if requests.send(request).is_err() {
+            break;
+        }
+        let response = match responses.recv() {
+            Ok(response) => response,
+            Err(_) => break,
+        };
+        writer.write_all(response.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Sends a reflected event, or fails if the value is not of the type of the event
+pub type SendRemoteEvent = fn(&mut World, &dyn Reflect) -> Result<(), RemoteError>;
+
+/// The events that can be sent with the `send_event` method of the [RemotePlugin]
+#[derive(Default)]
+pub struct RemoteEvents {
+    events: Vec<(&'static str, SendRemoteEvent)>,
+}
+
+impl RemoteEvents {
+    /// Adds the event `T`. [Events<T>] must exist when an event is sent.
+    pub fn add<T: Component + Reflect + FromWorld>(&mut self) {
+        let name = std::any::type_name::<T>();
+        if self
+            .events
+            .iter()
+            .any(|(event_name, _)| *event_name == name)
+        {
+            return;
+        }
+        self.events.push((name, |world, value| {
+            check_type(value, std::any::type_name::<T>())?;
+            let mut event = T::from_world(world);
+            event.apply(value);
+            world
+                .get_resource_mut::<Events<T>>()
+                .expect("remote events must be added with add_event")
+                .send(event);
+            Ok(())
+        }));
+    }
+
+    /// Gets the function that sends the event with the full or short type name `name`
+    pub fn get(&self, name: &str) -> Option<SendRemoteEvent> {
+        self.events
+            .iter()
+            .find(|(event_name, _)| {
+                *event_name == name || TypeRegistration::get_short_name(event_name) == name
+            })
+            .map(|(_, send_event)| *send_event)
+    }
+}
+
+pub trait AddRemoteEvent {
+    /// Adds the event `T`, registers its type, and lets [RemotePlugin] clients send it
+    fn add_remote_event<T>(&mut self) -> &mut Self
+    where
+        T: Component + Reflect + FromWorld + GetTypeRegistration;
+}
+
+impl AddRemoteEvent for AppBuilder {
+    fn add_remote_event<T>(&mut self) -> &mut Self
+    where
+        T: Component + Reflect + FromWorld + GetTypeRegistration,
+    {
+        self.add_event::<T>().register_type::<T>();
+        self.world_mut()
+            .get_resource_or_insert_with(RemoteEvents::default)
+            .add::<T>();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{process_request, AddRemoteEvent, RemotePlugin, RemoteServer};
+    use bevy_app::{prelude::*, EventReader};
+    use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin};
+    use bevy_ecs::{
+        entity::Entity,
+        reflect::ReflectComponent,
+        system::{IntoSystem, ResMut},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{TcpListener, TcpStream},
+        sync::mpsc,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        value: f32,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    struct Damage {
+        amount: f32,
+    }
+
+    #[derive(Default)]
+    struct ReceivedDamage(Vec<f32>);
+
+    const FPS: DiagnosticId = DiagnosticId::from_u128(1);
+
+    fn receive_damage(mut events: EventReader<Damage>, mut received: ResMut<ReceivedDamage>) {
+        received.0.extend(events.iter().map(|damage| damage.amount));
+    }
+
+    fn setup() -> (App, Entity) {
+        let mut builder = App::build();
+        builder
+            .add_plugin(DiagnosticsPlugin)
+            .add_plugin(RemotePlugin {
+                address: ([127, 0, 0, 1], 0).into(),
+            })
+            .register_type::<f32>()
+            .register_type::<Health>()
+            .add_remote_event::<Damage>()
+            .init_resource::<ReceivedDamage>()
+            .add_system(receive_damage.system());
+        let world = builder.world_mut();
+        let entity = world.spawn().insert(Health { value: 100.0 }).id();
+        let mut diagnostics = world.get_resource_mut::<Diagnostics>().unwrap();
+        diagnostics.add(Diagnostic::new(FPS, "fps", 10));
+        diagnostics.add_measurement(FPS, 60.0);
+        (builder.app, entity)
+    }
+
+    /// Sends `requests` over one connection, updating the App until all responses arrived
+    fn send(app: &mut App, requests: Vec<String>) -> Vec<serde_json::Value> {
+        let address = app
+            .world
+            .get_resource::<RemoteServer>()
+            .unwrap()
+            .local_addr();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut responses = Vec::new();
+            for request in requests {
+                writeln!(stream, "{}", request).unwrap();
+                let mut response = String::new();
+                reader.read_line(&mut response).unwrap();
+                responses.push(serde_json::from_str(&response).unwrap());
+            }
+            sender.send(responses).unwrap();
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            app.update();
+            if let Ok(responses) = receiver.try_recv() {
+                return responses;
+            }
+            assert!(Instant::now() < deadline, "timed out waiting for responses");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn get_health(world: &World, entity: Entity) -> f32 {
+        world.get::<Health>(entity).unwrap().value
+    }
+
+    #[test]
+    fn components() {
+        let (mut app, entity) = setup();
+        let bits = entity.to_bits();
+        let responses = send(
+            &mut app,
+            vec![
+                r#"{"id": 1, "method": "list_entities", "params": {"with": ["Health"]}}"#
+                    .to_string(),
+                r#"{"id": 2, "method": "list_components"}"#.to_string(),
+                format!(
+                    r#"{{"id": 3, "method": "get_component", "params": {{"entity": {}, "component": "Health"}}}}"#,
+                    bits
+                ),
+                format!(
+                    r#"{{"id": 4, "method": "set_component", "params": {{"entity": {}, "component": "Health", "value": {{"type": "{}", "struct": {{"value": {{"type": "f32", "value": 50.0}}}}}}}}}}"#,
+                    bits,
+                    std::any::type_name::<Health>()
+                ),
+            ],
+        );
+
+        let entities = responses[0]["result"].as_array().unwrap();
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0]["entity"], bits);
+        assert!(entities[0]["components"][0]
+            .as_str()
+            .unwrap()
+            .ends_with("Health"));
+
+        let components = responses[1]["result"].as_array().unwrap();
+        assert!(components
+            .iter()
+            .any(|component| component["short_name"] == "Health"));
+
+        assert_eq!(
+            responses[2]["result"]["struct"]["value"]["value"],
+            serde_json::json!(100.0)
+        );
+
+        assert_eq!(responses[3]["result"], serde_json::Value::Null);
+        assert!(responses[3].get("error").is_none(), "{}", responses[3]);
+        assert_eq!(get_health(&app.world, entity), 50.0);
+    }
+
+    #[test]
+    fn events_and_diagnostics() {
+        let (mut app, _) = setup();
+        let responses = send(
+            &mut app,
+            vec![
+                r#"{"method": "diagnostics"}"#.to_string(),
+                format!(
+                    r#"{{"method": "send_event", "params": {{"event": "Damage", "value": {{"type": "{}", "struct": {{"amount": {{"type": "f32", "value": 5.0}}}}}}}}}}"#,
+                    std::any::type_name::<Damage>()
+                ),
+            ],
+        );
+        assert_eq!(responses[0]["result"][0]["name"], "fps");
+        assert_eq!(responses[0]["result"][0]["value"], serde_json::json!(60.0));
+
+        let received = app.world.get_resource::<ReceivedDamage>().unwrap();
+        assert_eq!(received.0, vec![5.0]);
+    }
+
+    #[test]
+    fn errors() {
+        let (mut app, entity) = setup();
+        let responses = send(
+            &mut app,
+            vec![
+                "not json".to_string(),
+                r#"{"id": "a", "method": "explode"}"#.to_string(),
+                r#"{"method": "get_component", "params": {"entity": 12345678, "component": "Health"}}"#.to_string(),
+                format!(
+                    r#"{{"method": "get_component", "params": {{"entity": {}, "component": "Mana"}}}}"#,
+                    entity.to_bits()
+                ),
+            ],
+        );
+        assert!(responses[0]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid request"));
+        assert_eq!(responses[1]["id"], "a");
+        assert_eq!(responses[1]["error"], "unknown method 'explode'");
+        assert_eq!(responses[2]["error"], "entity 12345678 does not exist");
+        assert_eq!(responses[3]["error"], "type 'Mana' is not registered");
+
+        // values of the wrong type are rejected instead of panicking in `Reflect::apply`
+        let responses = send(
+            &mut app,
+            vec![
+                format!(
+                    r#"{{"method": "set_component", "params": {{"entity": {}, "component": "Health", "value": {{"type": "{}", "struct": {{"amount": {{"type": "f32", "value": 5.0}}}}}}}}}}"#,
+                    entity.to_bits(),
+                    std::any::type_name::<Damage>()
+                ),
+                format!(
+                    r#"{{"method": "send_event", "params": {{"event": "Damage", "value": {{"type": "{}", "struct": {{"value": {{"type": "f32", "value": 50.0}}}}}}}}}}"#,
+                    std::any::type_name::<Health>()
+                ),
+                r#"{"method": "send_event", "params": {"event": "Damage", "value": {"type": "f32", "value": 5.0}}}"#.to_string(),
+            ],
+        );
+        for response in &responses {
+            assert!(response["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("invalid value: expected a value of type"));
+        }
+        assert_eq!(get_health(&app.world, entity), 100.0);
+        assert!(app
+            .world
+            .get_resource::<ReceivedDamage>()
+            .unwrap()
+            .0
+            .is_empty());
+
+        let response = process_request(&mut World::new(), r#"{"method": "list_components"}"#);
+        assert_eq!(
+            response,
+            r#"{"id":null,"error":"the App has no TypeRegistry"}"#
+        );
+    }
+
+    #[test]
+    fn stops_on_shutdown() {
+        let (mut app, _) = setup();
+        let address = app
+            .world
+            .get_resource::<RemoteServer>()
+            .unwrap()
+            .local_addr();
+        app.update();
+        app.shutdown();
+        assert!(app.world.get_resource::<RemoteServer>().is_none());
+        // the listener was closed, so its port can be bound again
+        TcpListener::bind(address).unwrap();
+    }
+}
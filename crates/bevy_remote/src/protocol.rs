@@ -0,0 +1,312 @@
+use crate::RemoteEvents;
+use bevy_diagnostic::Diagnostics;
+use bevy_ecs::{archetype::ArchetypeId, entity::Entity, reflect::ReflectComponent, world::World};
+use bevy_reflect::{
+    serde::{ReflectDeserializer, ReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry, TypeRegistryArc,
+};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use serde_json::value::{to_raw_value, RawValue};
+use thiserror::Error;
+
+/// An error returned to the client instead of a result
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    #[error("invalid request: {0}")]
+    InvalidRequest(serde_json::Error),
+    #[error("unknown method '{0}'")]
+    UnknownMethod(String),
+    #[error("invalid params: {0}")]
+    InvalidParams(serde_json::Error),
+    #[error("entity {0} does not exist")]
+    EntityNotFound(u64),
+    #[error("type '{0}' is not registered")]
+    UnknownType(String),
+    #[error("type '{0}' does not reflect Component")]
+    NotAComponent(String),
+    #[error("entity {0} does not have component '{1}'")]
+    ComponentNotFound(u64, String),
+    #[error("event '{0}' was not added with add_remote_event")]
+    UnknownEvent(String),
+    #[error("invalid value: {0}")]
+    InvalidValue(serde_json::Error),
+    #[error("failed to serialize result: {0}")]
+    Serialize(serde_json::Error),
+    #[error("the App has no TypeRegistry")]
+    NoTypeRegistry,
+}
+
+#[derive(Deserialize)]
+struct Request<'a> {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    method: String,
+    #[serde(borrow, default)]
+    params: Option<&'a RawValue>,
+}
+
+#[derive(Serialize)]
+struct Response {
+    id: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Box<RawValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct ListEntitiesParams {
+    /// Only lists entities that have all of these components
+    #[serde(default)]
+    with: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GetComponentParams {
+    entity: u64,
+    component: String,
+}
+
+#[derive(Deserialize)]
+struct SetComponentParams<'a> {
+    entity: u64,
+    component: String,
+    #[serde(borrow)]
+    value: &'a RawValue,
+}
+
+#[derive(Deserialize)]
+struct SendEventParams<'a> {
+    event: String,
+    #[serde(borrow)]
+    value: &'a RawValue,
+}
+
+#[derive(Serialize)]
+struct EntityInfo {
+    entity: u64,
+    components: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ComponentTypeInfo<'a> {
+    name: &'a str,
+    short_name: &'a str,
+}
+
+#[derive(Serialize)]
+struct DiagnosticInfo<'a> {
+    id: String,
+    name: &'a str,
+    value: Option<f64>,
+    average: Option<f64>,
+}
+
+/// Handles one line of the protocol and returns the response line, see [crate::RemotePlugin]
+pub fn process_request(world: &mut World, line: &str) -> String {
+    let (id, result) = match serde_json::from_str::<Request>(line) {
+        Ok(request) => (
+            request.id,
+            handle_request(world, &request.method, request.params),
+        ),
+        Err(err) => (None, Err(RemoteError::InvalidRequest(err))),
+    };
+    let response = match result {
+        Ok(result) => Response {
+            id,
+            result: Some(result),
+            error: None,
+        },
+        Err(err) => Response {
+            id,
+            result: None,
+            error: Some(err.to_string()),
+        },
+    };
+    serde_json::to_string(&response).expect("responses are always valid JSON")
+}
+
+fn handle_request(
+    world: &mut World,
+    method: &str,
+    params: Option<&RawValue>,
+) -> Result<Box<RawValue>, RemoteError> {
+    let registry = world
+        .get_resource::<TypeRegistryArc>()
+        .ok_or(RemoteError::NoTypeRegistry)?
+        .clone();
+    let registry = registry.read();
+    match method {
+        "list_entities" => {
+            let params = match params {
+                Some(params) => parse_params::<ListEntitiesParams>(params)?,
+                None => ListEntitiesParams::default(),
+            };
+            to_result(&list_entities(world, &params.with))
+        }
+        "list_components" => {
+            let components = registry
+                .iter()
+                .filter(|registration| registration.data::<ReflectComponent>().is_some())
+                .map(|registration| ComponentTypeInfo {
+                    name: registration.name(),
+                    short_name: registration.short_name(),
+                })
+                .collect::<Vec<_>>();
+            to_result(&components)
+        }
+        "get_component" => {
+            let params = parse_params::<GetComponentParams>(required(params)?)?;
+            let entity = get_entity(world, params.entity)?;
+            let reflect_component = get_reflect_component(&registry, &params.component)?;
+            let component = reflect_component.reflect_component(world, entity).ok_or(
+                RemoteError::ComponentNotFound(params.entity, params.component),
+            )?;
+            to_result(&ReflectSerializer::new(component, &registry))
+        }
+        "set_component" => {
+            let params = parse_params::<SetComponentParams>(required(params)?)?;
+            let entity = get_entity(world, params.entity)?;
+            let registration = get_registration(&registry, &params.component)
+                .ok_or_else(|| RemoteError::UnknownType(params.component.clone()))?;
+            let reflect_component = registration
+                .data::<ReflectComponent>()
+                .ok_or_else(|| RemoteError::NotAComponent(params.component.clone()))?;
+            let value = deserialize_value(&registry, params.value)?;
+            check_type(&*value, registration.name())?;
+            if reflect_component.reflect_component(world, entity).is_some() {
+                reflect_component.apply_component(world, entity, &*value);
+            } else {
+                reflect_component.add_component(world, entity, &*value);
+            }
+            to_result(&())
+        }
+        "diagnostics" => {
+            let diagnostics = world
+                .get_resource::<Diagnostics>()
+                .map(|diagnostics| {
+                    diagnostics
+                        .iter()
+                        .map(|diagnostic| DiagnosticInfo {
+                            id: diagnostic.id.0.to_string(),
+                            name: &diagnostic.name,
+                            value: diagnostic.value(),
+                            average: diagnostic.average(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            to_result(&diagnostics)
+        }
+        "send_event" => {
+            let params = parse_params::<SendEventParams>(required(params)?)?;
+            let send_event = world
+                .get_resource::<RemoteEvents>()
+                .and_then(|events| events.get(&params.event))
+                .ok_or_else(|| RemoteError::UnknownEvent(params.event.clone()))?;
+            let value = deserialize_value(&registry, params.value)?;
+            send_event(world, &*value)?;
+            to_result(&())
+        }
+        _ => Err(RemoteError::UnknownMethod(method.to_string())),
+    }
+}
+
+fn list_entities(world: &World, with: &[String]) -> Vec<EntityInfo> {
+    let components = world.components();
+    let mut entities = Vec::new();
+    for archetype in world.archetypes().iter() {
+        if archetype.id() == ArchetypeId::resource() || archetype.is_empty() {
+            continue;
+        }
+        let names = archetype
+            .components()
+            .filter_map(|id| components.get_info(id))
+            .map(|info| info.name().to_string())
+            .collect::<Vec<_>>();
+        let matches = with.iter().all(|component| {
+            names.iter().any(|name| {
+                name == component || TypeRegistration::get_short_name(name) == *component
+            })
+        });
+        if !matches {
+            continue;
+        }
+        entities.extend(archetype.entities().iter().map(|entity| EntityInfo {
+            entity: entity.to_bits(),
+            components: names.clone(),
+        }));
+    }
+    entities
+}
+
+fn required(params: Option<&RawValue>) -> Result<&RawValue, RemoteError> {
+    params.ok_or_else(|| {
+        RemoteError::InvalidParams(serde::de::Error::custom("this method requires params"))
+    })
+}
+
+fn parse_params<'a, T: Deserialize<'a>>(params: &'a RawValue) -> Result<T, RemoteError> {
+    serde_json::from_str(params.get()).map_err(RemoteError::InvalidParams)
+}
+
+fn to_result<T: Serialize>(value: &T) -> Result<Box<RawValue>, RemoteError> {
+    to_raw_value(value).map_err(RemoteError::Serialize)
+}
+
+fn get_entity(world: &World, bits: u64) -> Result<Entity, RemoteError> {
+    let entity = Entity::from_bits(bits);
+    if world.get_entity(entity).is_some() {
+        Ok(entity)
+    } else {
+        Err(RemoteError::EntityNotFound(bits))
+    }
+}
+
+/// Looks up a type by its full name, or by its short name if that is unique
+pub(crate) fn get_registration<'a>(
+    registry: &'a TypeRegistry,
+    name: &str,
+) -> Option<&'a TypeRegistration> {
+    registry
+        .get_with_name(name)
+        .or_else(|| registry.get_with_short_name(name))
+}
+
+fn get_reflect_component<'a>(
+    registry: &'a TypeRegistry,
+    name: &str,
+) -> Result<&'a ReflectComponent, RemoteError> {
+    get_registration(registry, name)
+        .ok_or_else(|| RemoteError::UnknownType(name.to_string()))?
+        .data::<ReflectComponent>()
+        .ok_or_else(|| RemoteError::NotAComponent(name.to_string()))
+}
+
+/// Deserializes a value in the format written by [ReflectSerializer]
+fn deserialize_value(
+    registry: &TypeRegistry,
+    value: &RawValue,
+) -> Result<Box<dyn Reflect>, RemoteError> {
+    // the type field has to come before the value, so this can't go through `serde_json::Value`
+    let mut deserializer = serde_json::Deserializer::from_str(value.get());
+    ReflectDeserializer::new(registry)
+        .deserialize(&mut deserializer)
+        .map_err(RemoteError::InvalidValue)
+}
+
+/// Checks that a deserialized value has the type it is applied to, as [Reflect::apply] panics
+/// otherwise
+pub(crate) fn check_type(value: &dyn Reflect, expected: &str) -> Result<(), RemoteError> {
+    if value.type_name() == expected {
+        Ok(())
+    } else {
+        Err(RemoteError::InvalidValue(serde::de::Error::custom(
+            format!(
+                "expected a value of type '{}', found '{}'",
+                expected,
+                value.type_name()
+            ),
+        )))
+    }
+}
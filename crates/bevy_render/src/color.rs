@@ -5,7 +5,7 @@ use crate::{
     renderer::{RenderResource, RenderResourceType},
 };
 use bevy_asset::Handle;
-use bevy_core::Bytes;
+use bevy_core::{Bytes, Std140, Std430};
 use bevy_math::{Vec3, Vec4};
 use bevy_reflect::{Reflect, ReflectDeserialize};
 use serde::{Deserialize, Serialize};
@@ -1069,6 +1069,25 @@ impl Bytes for Color {
 
 impl_render_resource_bytes!(Color);
 
+// colors are written as a linear `vec4`
+impl Std140 for Color {
+    const STD140_ALIGN: usize = 16;
+    const STD140_SIZE: usize = 16;
+
+    fn write_std140(&self, buffer: &mut [u8]) {
+        self.write_bytes(buffer);
+    }
+}
+
+impl Std430 for Color {
+    const STD430_ALIGN: usize = 16;
+    const STD430_SIZE: usize = 16;
+
+    fn write_std430(&self, buffer: &mut [u8]) {
+        self.write_bytes(buffer);
+    }
+}
+
 #[derive(Debug)]
 pub enum HexColorError {
     Length,
@@ -56,8 +56,9 @@ use renderer::{AssetRenderResourceBindings, RenderResourceBindings};
 use shader::ShaderLoader;
 #[cfg(feature = "hdr")]
 use texture::HdrTextureLoader;
+use texture::{FilterMode, ImageTextureSettings};
 #[cfg(feature = "png")]
-use texture::ImageTextureLoader;
+use texture::{ImageTextureLoader, ImageTextureSaver};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderSystem {
@@ -97,7 +98,8 @@ impl Plugin for RenderPlugin {
     fn build(&self, app: &mut AppBuilder) {
         #[cfg(feature = "png")]
         {
-            app.init_asset_loader::<ImageTextureLoader>();
+            app.init_asset_loader::<ImageTextureLoader>()
+                .init_asset_saver::<ImageTextureSaver>();
         }
         #[cfg(feature = "hdr")]
         {
@@ -153,10 +155,13 @@ impl Plugin for RenderPlugin {
         .register_type::<ScalingMode>()
         .register_type::<VertexBufferLayout>()
         .register_type::<WindowOrigin>()
+        .register_type::<FilterMode>()
+        .register_type::<Option<FilterMode>>()
+        .register_type::<ImageTextureSettings>()
         .init_resource::<ClearColor>()
         .init_resource::<RenderGraph>()
         .init_resource::<PipelineCompiler>()
-        .init_resource::<Msaa>()
+        .init_settings::<Msaa>()
         .init_resource::<RenderResourceBindings>()
         .init_resource::<AssetRenderResourceBindings>()
         .init_resource::<ActiveCameras>()
@@ -10,7 +10,10 @@ use crate::{
     texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
     Color,
 };
-use bevy_ecs::{reflect::ReflectComponent, world::World};
+use bevy_ecs::{
+    reflect::{ReflectComponent, ReflectResource},
+    world::World,
+};
 use bevy_reflect::Reflect;
 use bevy_window::WindowId;
 
@@ -19,7 +22,8 @@ use bevy_window::WindowId;
 #[reflect(Component)]
 pub struct MainPass;
 
-#[derive(Debug)]
+#[derive(Debug, Reflect)]
+#[reflect(Resource)]
 pub struct Msaa {
     pub samples: u32,
 }
@@ -2,7 +2,7 @@ use super::{BufferId, SamplerId, TextureId};
 use crate::texture::Texture;
 use bevy_asset::Handle;
 
-use bevy_core::{Byteable, Bytes};
+use bevy_core::{Byteable, Bytes, Std140, Std430};
 pub use bevy_derive::{RenderResource, RenderResources};
 use bevy_math::{Mat4, Vec2, Vec3, Vec4};
 use bevy_transform::components::GlobalTransform;
@@ -180,6 +180,58 @@ where
     }
 }
 
+macro_rules! impl_std_layout_buffer {
+    ($name:ident, $layout:ident, $write:ident, $size:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Debug, Clone, Copy, Default, PartialEq)]
+        #[repr(transparent)]
+        pub struct $name<T>(pub T);
+
+        impl<T> $name<T> {
+            pub fn from_ref(value: &T) -> &Self {
+                // SAFETY: the wrapper is `repr(transparent)`, so it has the same layout as `T`
+                unsafe { &*(value as *const T as *const Self) }
+            }
+        }
+
+        impl<T> RenderResource for $name<T>
+        where
+            T: $layout,
+        {
+            fn resource_type(&self) -> Option<RenderResourceType> {
+                Some(RenderResourceType::Buffer)
+            }
+
+            fn write_buffer_bytes(&self, buffer: &mut [u8]) {
+                self.0.$write(buffer);
+            }
+
+            fn buffer_byte_len(&self) -> Option<usize> {
+                Some(T::$size)
+            }
+
+            fn texture(&self) -> Option<&Handle<Texture>> {
+                None
+            }
+        }
+    };
+}
+
+impl_std_layout_buffer!(
+    Std140Buffer,
+    Std140,
+    write_std140,
+    STD140_SIZE,
+    "Writes a value with the `std140` layout. Used for `#[render_resources(std140)]` fields."
+);
+impl_std_layout_buffer!(
+    Std430Buffer,
+    Std430,
+    write_std430,
+    STD430_SIZE,
+    "Writes a value with the `std430` layout. Used for `#[render_resources(std430)]` fields."
+);
+
 impl RenderResource for GlobalTransform {
     fn resource_type(&self) -> Option<RenderResourceType> {
         Some(RenderResourceType::Buffer)
@@ -1,6 +1,10 @@
-use super::texture::{ImageType, Texture, TextureError};
+use super::{
+    texture::{ImageType, Texture, TextureError},
+    FilterMode, SamplerDescriptor,
+};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_reflect::Reflect;
 use bevy_utils::BoxedFuture;
 use thiserror::Error;
 
@@ -8,6 +12,25 @@ use thiserror::Error;
 #[derive(Clone, Default)]
 pub struct ImageTextureLoader;
 
+/// The settings of [ImageTextureLoader], e.g. `(settings: (filter: Some(Nearest)))` in the
+/// `.meta` file of a texture to sample it without smoothing
+#[derive(Debug, Clone, Default, Reflect)]
+pub struct ImageTextureSettings {
+    /// The filter used to magnify, minify and blend between mipmaps of the texture, or the
+    /// filters of the default [SamplerDescriptor] if `None`
+    pub filter: Option<FilterMode>,
+}
+
+impl ImageTextureSettings {
+    pub fn apply_to(&self, sampler: &mut SamplerDescriptor) {
+        if let Some(filter) = self.filter {
+            sampler.mag_filter = filter;
+            sampler.min_filter = filter;
+            sampler.mipmap_filter = filter;
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &["png", "dds", "tga", "jpg", "jpeg", "bmp"];
 
 impl AssetLoader for ImageTextureLoader {
@@ -20,13 +43,16 @@ impl AssetLoader for ImageTextureLoader {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
 
-            let dyn_img =
+            let mut dyn_img =
                 Texture::from_buffer(bytes, ImageType::Extension(ext)).map_err(|err| {
                     FileTextureError {
                         error: err,
                         path: format!("{}", load_context.path().display()),
                     }
                 })?;
+            load_context
+                .settings::<ImageTextureSettings>()
+                .apply_to(&mut dyn_img.sampler);
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -36,6 +62,10 @@ impl AssetLoader for ImageTextureLoader {
     fn extensions(&self) -> &[&str] {
         FILE_EXTENSIONS
     }
+
+    fn default_settings(&self) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(ImageTextureSettings::default()))
+    }
 }
 
 /// An error that occurs when loading a texture from a file
@@ -64,4 +94,24 @@ mod tests {
             assert!(image::ImageFormat::from_extension(ext).is_some())
         }
     }
+
+    #[test]
+    fn settings() {
+        use bevy_reflect::TypeRegistry;
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Option<FilterMode>>();
+        let mut settings = ImageTextureSettings::default();
+        let mut deserializer = ron::Deserializer::from_str("(filter: Some(Nearest))").unwrap();
+        bevy_app::apply_deserialized(&mut settings, &registry, &mut deserializer).unwrap();
+        assert_eq!(settings.filter, Some(FilterMode::Nearest));
+
+        let mut sampler = SamplerDescriptor::default();
+        ImageTextureSettings::default().apply_to(&mut sampler);
+        assert_eq!(sampler.min_filter, FilterMode::Linear);
+        settings.apply_to(&mut sampler);
+        assert_eq!(sampler.mag_filter, FilterMode::Nearest);
+        assert_eq!(sampler.min_filter, FilterMode::Nearest);
+        assert_eq!(sampler.mipmap_filter, FilterMode::Nearest);
+    }
 }
@@ -0,0 +1,60 @@
+use super::{
+    image_texture_conversion::texture_to_image,
+    texture::{Texture, TextureError},
+};
+use anyhow::Result;
+use bevy_asset::{AssetSaver, SaveContext};
+
+/// Saves textures as PNG images. Only the formats that [Texture::convert] can read are supported.
+#[derive(Clone, Default)]
+pub struct ImageTextureSaver;
+
+impl AssetSaver for ImageTextureSaver {
+    type Asset = Texture;
+
+    fn save(&self, texture: &Texture, _save_context: &SaveContext) -> Result<Vec<u8>> {
+        let dyn_img = texture_to_image(texture)
+            .ok_or(TextureError::UnsupportedTextureFormat(texture.format))?;
+        // the PNG encoder has no BGRA color type
+        let dyn_img = match dyn_img {
+            image::DynamicImage::ImageBgra8(_) => {
+                image::DynamicImage::ImageRgba8(dyn_img.into_rgba8())
+            }
+            dyn_img => dyn_img,
+        };
+        let mut bytes = Vec::new();
+        dyn_img
+            .write_to(&mut bytes, image::ImageOutputFormat::Png)
+            .map_err(TextureError::from)?;
+        Ok(bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::{Extent3d, ImageType, TextureDimension, TextureFormat};
+    use std::path::Path;
+
+    #[test]
+    fn save_png() {
+        let texture = Texture::new(
+            Extent3d::new(2, 1, 1),
+            TextureDimension::D2,
+            vec![0, 64, 128, 255, 255, 128, 64, 0],
+            TextureFormat::Bgra8UnormSrgb,
+        );
+        let bytes = ImageTextureSaver
+            .save(&texture, &SaveContext::new(Path::new("texture.png")))
+            .unwrap();
+
+        let saved = Texture::from_buffer(&bytes, ImageType::Extension("png")).unwrap();
+        assert_eq!(saved.size, texture.size);
+        assert_eq!(saved.format, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(saved.data, vec![128, 64, 0, 255, 64, 128, 255, 0]);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::pipeline::CompareFunction;
+use bevy_reflect::{Reflect, ReflectDeserialize};
+use serde::{Deserialize, Serialize};
 use std::num::NonZeroU8;
 
 /// Describes a sampler
@@ -59,7 +61,8 @@ impl Default for AddressMode {
 }
 
 /// Texel mixing mode when sampling between texels.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect_value(Serialize, Deserialize, PartialEq, Hash)]
 pub enum FilterMode {
     Nearest = 0,
     Linear = 1,
@@ -289,6 +289,8 @@ pub enum TextureError {
     InvalidImageExtension(String),
     #[error("failed to load an image")]
     ImageError(#[from] image::ImageError),
+    #[error("unsupported texture format {0:?}")]
+    UnsupportedTextureFormat(TextureFormat),
 }
 
 /// Type of a raw image buffer
@@ -103,7 +103,6 @@ impl DynamicScene {
         Ok(())
     }
 
-    // TODO: move to AssetSaver when it is implemented
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
@@ -0,0 +1,32 @@
+use crate::DynamicScene;
+use anyhow::Result;
+use bevy_asset::{AssetSaver, SaveContext};
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+
+/// Saves [DynamicScene]s as RON, in the format read by [SceneLoader](crate::SceneLoader)
+#[derive(Debug)]
+pub struct SceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        SceneSaver {
+            type_registry: (&*type_registry).clone(),
+        }
+    }
+}
+
+impl AssetSaver for SceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene, _save_context: &SaveContext) -> Result<Vec<u8>> {
+        Ok(scene.serialize_ron(&self.type_registry)?.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn", "scn.ron"]
+    }
+}
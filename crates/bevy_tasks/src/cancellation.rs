@@ -0,0 +1,124 @@
+use event_listener::Event;
+use futures_lite::future;
+use std::{
+    fmt,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[derive(Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    event: Event,
+}
+
+/// Cooperatively cancels tasks. Clones of a token share its state, so a task can watch a token
+/// that another thread cancels.
+///
+/// Long running tasks can check [CancellationToken::is_cancelled] between steps, and async tasks
+/// can use [CancellationToken::run_until_cancelled] to stop at their next await point.
+///
+/// ```
+/// # use bevy_tasks::{CancellationToken, TaskPool};
+/// let pool = TaskPool::new();
+/// let token = CancellationToken::new();
+/// let task_token = token.clone();
+/// let task = pool.spawn(async move {
+///     let mut steps = 0;
+///     while !task_token.is_cancelled() {
+///         steps += 1;
+///         futures_lite::future::yield_now().await;
+///     }
+///     steps
+/// });
+/// token.cancel();
+/// futures_lite::future::block_on(task);
+/// ```
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and wakes all tasks waiting for [CancellationToken::cancelled]
+    pub fn cancel(&self) {
+        if !self.0.cancelled.swap(true, Ordering::SeqCst) {
+            self.0.event.notify(usize::MAX);
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes when the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            if self.is_cancelled() {
+                return;
+            }
+            let listener = self.0.event.listen();
+            // the token may have been cancelled before the listener was registered
+            if self.is_cancelled() {
+                return;
+            }
+            listener.await;
+        }
+    }
+
+    /// Runs `future` until it completes, or until the token is cancelled. Returns `None` if the
+    /// token was cancelled first, dropping `future`.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        future::or(
+            async {
+                self.cancelled().await;
+                None
+            },
+            async { Some(future.await) },
+        )
+        .await
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+    use futures_lite::future;
+
+    #[test]
+    fn cancel() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        assert_eq!(
+            future::block_on(token.run_until_cancelled(async { 1 })),
+            Some(1)
+        );
+
+        let waiter = std::thread::spawn(move || {
+            future::block_on(clone.run_until_cancelled(future::pending::<()>()))
+        });
+        token.cancel();
+        assert_eq!(waiter.join().unwrap(), None);
+        assert!(token.is_cancelled());
+        // cancelling is idempotent, and a cancelled token stops futures before they run
+        token.cancel();
+        assert_eq!(
+            future::block_on(token.run_until_cancelled(async { 1 })),
+            None
+        );
+    }
+}
@@ -233,3 +233,221 @@ where
         }
     }
 }
+
+/// A batch whose number of items is known before it is iterated. Batches with an exact
+/// `size_hint` are used as they are, others are collected first, on the thread that gets the
+/// batch rather than in the task that iterates it.
+#[derive(Debug)]
+pub enum CountedBatch<B: Iterator> {
+    Exact(B),
+    Collected(std::vec::IntoIter<B::Item>),
+}
+
+impl<B: Iterator> CountedBatch<B> {
+    /// Returns the number of items in `batch`, and the batch to iterate instead of it
+    pub(crate) fn new(batch: B) -> (usize, Self) {
+        match batch.size_hint() {
+            (lower, Some(upper)) if lower == upper => (lower, CountedBatch::Exact(batch)),
+            _ => {
+                let items = batch.collect::<Vec<_>>();
+                (items.len(), CountedBatch::Collected(items.into_iter()))
+            }
+        }
+    }
+}
+
+impl<B: Iterator> Iterator for CountedBatch<B> {
+    type Item = B::Item;
+
+    fn next(&mut self) -> Option<B::Item> {
+        match self {
+            CountedBatch::Exact(batch) => batch.next(),
+            CountedBatch::Collected(items) => items.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            CountedBatch::Exact(batch) => batch.size_hint(),
+            CountedBatch::Collected(items) => items.size_hint(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Enumerate<P> {
+    pub(crate) iter: P,
+    pub(crate) count: usize,
+}
+
+impl<B, P> ParallelIterator<std::iter::Zip<std::ops::RangeFrom<usize>, CountedBatch<B>>>
+    for Enumerate<P>
+where
+    B: Iterator + Send,
+    B::Item: Send,
+    P: ParallelIterator<B, Item = B::Item>,
+{
+    type Item = (usize, P::Item);
+
+    fn next_batch(
+        &mut self,
+    ) -> Option<std::iter::Zip<std::ops::RangeFrom<usize>, CountedBatch<B>>> {
+        let (len, batch) = CountedBatch::new(self.iter.next_batch()?);
+        let start = self.count;
+        self.count += len;
+        Some((start..).zip(batch))
+    }
+}
+
+#[derive(Debug)]
+pub struct StepBy<P> {
+    pub(crate) iter: P,
+    pub(crate) step: usize,
+    pub(crate) count: usize,
+}
+
+impl<B, P> ParallelIterator<std::iter::StepBy<std::iter::Skip<CountedBatch<B>>>> for StepBy<P>
+where
+    B: Iterator + Send,
+    B::Item: Send,
+    P: ParallelIterator<B, Item = B::Item>,
+{
+    type Item = P::Item;
+
+    fn next_batch(&mut self) -> Option<std::iter::StepBy<std::iter::Skip<CountedBatch<B>>>> {
+        let (len, batch) = CountedBatch::new(self.iter.next_batch()?);
+        // skip to the first item of this batch whose index is a multiple of step
+        let offset = (self.step - self.count % self.step) % self.step;
+        self.count += len;
+        Some(batch.skip(offset).step_by(self.step))
+    }
+}
+
+#[derive(Debug)]
+pub struct Take<P> {
+    pub(crate) iter: P,
+    pub(crate) n: usize,
+    pub(crate) count: usize,
+}
+
+impl<B, P> ParallelIterator<std::iter::Take<CountedBatch<B>>> for Take<P>
+where
+    B: Iterator + Send,
+    B::Item: Send,
+    P: ParallelIterator<B, Item = B::Item>,
+{
+    type Item = P::Item;
+
+    fn next_batch(&mut self) -> Option<std::iter::Take<CountedBatch<B>>> {
+        if self.count >= self.n {
+            return None;
+        }
+        let (len, batch) = CountedBatch::new(self.iter.next_batch()?);
+        let remaining = self.n - self.count;
+        self.count += len;
+        Some(batch.take(remaining))
+    }
+}
+
+#[derive(Debug)]
+pub struct Skip<P> {
+    pub(crate) iter: P,
+    pub(crate) n: usize,
+    pub(crate) count: usize,
+}
+
+impl<B, P> ParallelIterator<std::iter::Skip<CountedBatch<B>>> for Skip<P>
+where
+    B: Iterator + Send,
+    B::Item: Send,
+    P: ParallelIterator<B, Item = B::Item>,
+{
+    type Item = P::Item;
+
+    fn next_batch(&mut self) -> Option<std::iter::Skip<CountedBatch<B>>> {
+        loop {
+            let (len, batch) = CountedBatch::new(self.iter.next_batch()?);
+            let start = self.count;
+            self.count += len;
+            // batches that are skipped entirely are not returned
+            if self.count > self.n {
+                return Some(batch.skip(self.n.saturating_sub(start)));
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Zip<P, Q, T> {
+    pub(crate) left: P,
+    pub(crate) right: Q,
+    /// Items of `right` that were not paired with items of `left` yet
+    pub(crate) right_items: std::collections::VecDeque<T>,
+}
+
+/// A batch of [Zip]. The items of the right iterator are moved out of its batches, so they line
+/// up with the batches of the left iterator.
+pub struct ZipBatch<A: Iterator, B: Iterator> {
+    left: CountedBatch<A>,
+    right: std::vec::IntoIter<B::Item>,
+}
+
+// not derived, as the derive doesn't require the items of the left iterator to implement Debug
+impl<A, B> std::fmt::Debug for ZipBatch<A, B>
+where
+    A: Iterator + std::fmt::Debug,
+    A::Item: std::fmt::Debug,
+    B: Iterator,
+    B::Item: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZipBatch")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+impl<A: Iterator, B: Iterator> Iterator for ZipBatch<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.left.next()?, self.right.next()?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.left.size_hint().0.min(self.right.len());
+        (len, Some(len))
+    }
+}
+
+impl<A, B, P, Q> ParallelIterator<ZipBatch<A, B>> for Zip<P, Q, B::Item>
+where
+    A: Iterator + Send,
+    A::Item: Send,
+    B: Iterator + Send,
+    B::Item: Send,
+    P: ParallelIterator<A, Item = A::Item>,
+    Q: ParallelIterator<B, Item = B::Item>,
+{
+    type Item = (P::Item, Q::Item);
+
+    fn next_batch(&mut self) -> Option<ZipBatch<A, B>> {
+        let (len, left) = CountedBatch::new(self.left.next_batch()?);
+        while self.right_items.len() < len {
+            match self.right.next_batch() {
+                Some(batch) => self.right_items.extend(batch),
+                None => break,
+            }
+        }
+        if len > 0 && self.right_items.is_empty() {
+            return None;
+        }
+        let right_len = len.min(self.right_items.len());
+        let right = self.right_items.drain(..right_len).collect::<Vec<_>>();
+        Some(ZipBatch {
+            left,
+            right: right.into_iter(),
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::TaskPool;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 mod adapters;
 pub use adapters::*;
@@ -89,6 +90,89 @@ where
         }
     }
 
+    /// Takes two parallel iterators and returns a parallel iterator over
+    /// pairs of their items. The items of `other` are regrouped to line up
+    /// with the batches of this iterator.
+    ///
+    /// *Note that the batches of `other` are collected on the thread that
+    /// calls `next_batch`, e.g. the thread that calls `for_each`, so any
+    /// work done by adapters of `other` runs serially. Batches of this
+    /// iterator whose size_hint is not exact are collected there too.*
+    ///
+    /// See [`Iterator::zip()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.zip)
+    fn zip<U, C>(self, other: U) -> Zip<Self, U, U::Item>
+    where
+        U: ParallelIterator<C>,
+        C: Iterator<Item = U::Item> + Send,
+    {
+        Zip {
+            left: self,
+            right: other,
+            right_items: Default::default(),
+        }
+    }
+
+    /// Creates a parallel iterator which gives the index of each item
+    /// along with the item. Indices count across batches, so they can be
+    /// used to write into an output slice.
+    ///
+    /// *Note that batches whose size_hint is not exact, e.g. after filter,
+    /// are collected before they are returned, to count their items. This
+    /// runs the adapters before `enumerate` serially, on the thread that
+    /// calls `next_batch`.*
+    ///
+    /// See [`Iterator::enumerate()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.enumerate)
+    fn enumerate(self) -> Enumerate<Self> {
+        Enumerate {
+            iter: self,
+            count: 0,
+        }
+    }
+
+    /// Creates a parallel iterator which yields every `step`th item,
+    /// starting with the first one.
+    ///
+    /// *Note that, like [`ParallelIterator::enumerate`], this collects
+    /// batches whose size_hint is not exact on the calling thread.*
+    ///
+    /// See [`Iterator::step_by()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.step_by)
+    fn step_by(self, step: usize) -> StepBy<Self> {
+        assert!(step != 0, "step_by requires a non-zero step");
+        StepBy {
+            iter: self,
+            step,
+            count: 0,
+        }
+    }
+
+    /// Creates a parallel iterator which yields the first `n` items.
+    ///
+    /// *Note that, like [`ParallelIterator::enumerate`], this collects
+    /// batches whose size_hint is not exact on the calling thread.*
+    ///
+    /// See [`Iterator::take()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.take)
+    fn take(self, n: usize) -> Take<Self> {
+        Take {
+            iter: self,
+            n,
+            count: 0,
+        }
+    }
+
+    /// Creates a parallel iterator which skips the first `n` items.
+    ///
+    /// *Note that, like [`ParallelIterator::enumerate`], this collects
+    /// batches whose size_hint is not exact on the calling thread.*
+    ///
+    /// See [`Iterator::skip()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip)
+    fn skip(self, n: usize) -> Skip<Self> {
+        Skip {
+            iter: self,
+            n,
+            count: 0,
+        }
+    }
+
     /// Takes a closure and creates a parallel iterator which calls that
     /// closure on each item.
     ///
@@ -117,6 +201,42 @@ where
         });
     }
 
+    /// Calls a fallible closure on each item of a parallel iterator, and
+    /// returns an error if any call failed.
+    ///
+    /// *Note that batches stop early once any batch failed, but batches
+    /// that already ran are not undone. If several items fail, which of
+    /// their errors is returned is unspecified.*
+    ///
+    /// See [`Iterator::try_for_each()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.try_for_each)
+    fn try_for_each<E, F>(mut self, pool: &TaskPool, f: F) -> Result<(), E>
+    where
+        F: FnMut(Self::Item) -> Result<(), E> + Send + Clone + Sync,
+        E: Send + 'static,
+    {
+        let failed = AtomicBool::new(false);
+        let failed = &failed;
+        pool.scope(|s| {
+            while let Some(batch) = self.next_batch() {
+                let mut newf = f.clone();
+                s.spawn(async move {
+                    for item in batch {
+                        if failed.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        if let Err(err) = newf(item) {
+                            failed.store(true, Ordering::Relaxed);
+                            return Err(err);
+                        }
+                    }
+                    Ok(())
+                });
+            }
+        })
+        .into_iter()
+        .collect()
+    }
+
     /// Creates a parallel iterator which uses a closure to determine
     /// if an element should be yielded.
     ///
@@ -253,6 +373,30 @@ where
         })
     }
 
+    /// Reduces the items of a parallel iterator to a single one, by
+    /// repeatedly applying a function to the items of each batch, and then
+    /// to the results of the batches.
+    ///
+    /// See [`Iterator::reduce()`](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.reduce)
+    fn reduce<F>(mut self, pool: &TaskPool, f: F) -> Option<Self::Item>
+    where
+        F: FnMut(Self::Item, Self::Item) -> Self::Item + Send + Sync + Clone,
+        Self::Item: Send + 'static,
+    {
+        let results = pool.scope(|s| {
+            while let Some(mut batch) = self.next_batch() {
+                let newf = f.clone();
+                s.spawn(async move {
+                    let first = batch.next()?;
+                    Some(batch.fold(first, newf))
+                });
+            }
+        });
+        let mut results = results.into_iter().flatten();
+        let first = results.next()?;
+        Some(results.fold(first, f))
+    }
+
     /// Tests if every element of the parallel iterator matches a predicate.
     ///
     /// *Note that all is **not** short circuiting.*
@@ -510,3 +654,91 @@ where
         .product()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ParallelIterator;
+    use crate::TaskPool;
+
+    /// Iterates over `items` in batches of the given sizes
+    struct Batches<T>(std::vec::IntoIter<Vec<T>>);
+
+    impl<T: Send> ParallelIterator<std::vec::IntoIter<T>> for Batches<T> {
+        type Item = T;
+
+        fn next_batch(&mut self) -> Option<std::vec::IntoIter<T>> {
+            self.0.next().map(Vec::into_iter)
+        }
+    }
+
+    fn batches(sizes: &[usize]) -> Batches<usize> {
+        let mut start = 0;
+        let mut batches = Vec::new();
+        for size in sizes {
+            batches.push((start..start + size).collect());
+            start += size;
+        }
+        Batches(batches.into_iter())
+    }
+
+    #[test]
+    fn enumerate() {
+        let pool = TaskPool::new();
+        let items = batches(&[3, 0, 4, 2])
+            .map(|i| i * 10)
+            .enumerate()
+            .collect::<Vec<_>>(&pool);
+        assert_eq!(items, (0..9).map(|i| (i, i * 10)).collect::<Vec<_>>());
+
+        // indices are counted after filtering
+        let items = batches(&[3, 4, 2])
+            .filter(|i| i % 2 == 1)
+            .enumerate()
+            .collect::<Vec<_>>(&pool);
+        assert_eq!(items, vec![(0, 1), (1, 3), (2, 5), (3, 7)]);
+    }
+
+    #[test]
+    fn step_by_take_skip() {
+        let pool = TaskPool::new();
+        let sizes = [3, 4, 0, 5, 1];
+        let items = batches(&sizes).step_by(3).collect::<Vec<_>>(&pool);
+        assert_eq!(items, vec![0, 3, 6, 9, 12]);
+        let items = batches(&sizes).take(5).collect::<Vec<_>>(&pool);
+        assert_eq!(items, (0..5).collect::<Vec<_>>());
+        let items = batches(&sizes).skip(5).collect::<Vec<_>>(&pool);
+        assert_eq!(items, (5..13).collect::<Vec<_>>());
+        let items = batches(&sizes)
+            .skip(2)
+            .step_by(4)
+            .take(2)
+            .collect::<Vec<_>>(&pool);
+        assert_eq!(items, vec![2, 6]);
+        assert_eq!(batches(&sizes).skip(20).count(&pool), 0);
+    }
+
+    #[test]
+    fn zip() {
+        let pool = TaskPool::new();
+        let items = batches(&[2, 5, 0, 3])
+            .zip(batches(&[4, 1, 3]).map(|i| i * 10))
+            .collect::<Vec<_>>(&pool);
+        assert_eq!(items, (0..8).map(|i| (i, i * 10)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reduce_and_try_for_each() {
+        let pool = TaskPool::new();
+        assert_eq!(batches(&[3, 0, 4]).reduce(&pool, |a, b| a + b), Some(21));
+        assert_eq!(batches(&[0, 0]).reduce(&pool, |a, b| a + b), None);
+
+        assert_eq!(
+            batches(&[3, 4]).try_for_each(&pool, |i| if i < 7 { Ok(()) } else { Err(i) }),
+            Ok(())
+        );
+        assert_eq!(
+            batches(&[3, 4, 5]).try_for_each(&pool, |i| if i % 4 != 3 { Ok(()) } else { Err(i) }),
+            Err(3)
+        );
+    }
+}
@@ -2,14 +2,16 @@ mod slice;
 pub use slice::{ParallelSlice, ParallelSliceMut};
 
 mod task;
-pub use task::Task;
+pub use task::{Task, TaskPriority};
 
 #[cfg(not(target_arch = "wasm32"))]
 mod task_pool;
 #[cfg(not(target_arch = "wasm32"))]
 pub use task_pool::{Scope, TaskPool, TaskPoolBuilder};
 
-#[cfg(target_arch = "wasm32")]
+// also built for native tests, so the pool used on wasm is tested
+#[cfg(any(target_arch = "wasm32", test))]
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
 mod single_threaded_task_pool;
 #[cfg(target_arch = "wasm32")]
 pub use single_threaded_task_pool::{Scope, TaskPool, TaskPoolBuilder};
@@ -20,13 +22,23 @@ pub use usages::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool};
 mod countdown_event;
 pub use countdown_event::CountdownEvent;
 
+mod cancellation;
+pub use cancellation::CancellationToken;
+
+mod timeout;
+pub use timeout::{sleep, with_timeout, Sleep, TimedOut};
+
 mod iter;
 pub use iter::ParallelIterator;
 
+mod metrics;
+pub use metrics::{TaskPoolMetrics, TaskPoolStats};
+
 pub mod prelude {
     pub use crate::{
         iter::ParallelIterator,
         slice::{ParallelSlice, ParallelSliceMut},
+        task::TaskPriority,
         usages::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool},
     };
 }
@@ -38,3 +50,19 @@ pub fn logical_core_count() -> usize {
 pub fn physical_core_count() -> usize {
     num_cpus::get_physical()
 }
+
+/// The ids of the cores threads can be pinned to with [TaskPoolBuilder::core_ids]. This is empty
+/// if the platform doesn't support pinning threads.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn core_ids() -> Vec<usize> {
+    core_affinity::get_core_ids()
+        .map(|core_ids| core_ids.into_iter().map(|core_id| core_id.id).collect())
+        .unwrap_or_default()
+}
+
+/// The ids of the cores threads can be pinned to with [TaskPoolBuilder::core_ids]. This is empty
+/// if the platform doesn't support pinning threads.
+#[cfg(target_arch = "wasm32")]
+pub fn core_ids() -> Vec<usize> {
+    Vec::new()
+}
@@ -0,0 +1,219 @@
+use instant::Instant;
+use pin_project_lite::pin_project;
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// Counters a [TaskPool](crate::TaskPool) updates while it runs tasks, if it was built with
+/// [TaskPoolBuilder::metrics](crate::TaskPoolBuilder::metrics). Read them with
+/// [TaskPool::metrics](crate::TaskPool::metrics).
+///
+/// Only polls made by the threads of the pool count as busy time. Threads that call
+/// [TaskPool::scope](crate::TaskPool::scope) also poll tasks while they wait for them, which
+/// counts towards the number of finished tasks and their duration, but not towards
+/// [TaskPoolStats::running] or [TaskPoolStats::busy_time].
+#[derive(Debug)]
+pub struct TaskPoolMetrics {
+    created: Instant,
+    spawned: AtomicU64,
+    queued: AtomicUsize,
+    finished: AtomicU64,
+    running: AtomicUsize,
+    task_nanos: AtomicU64,
+    busy_nanos: AtomicU64,
+    threads: usize,
+}
+
+/// A snapshot of [TaskPoolMetrics]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskPoolStats {
+    /// The time since the pool was created
+    pub elapsed: Duration,
+    /// The number of threads of the pool
+    pub threads: usize,
+    /// The number of tasks spawned so far
+    pub spawned: u64,
+    /// The number of spawned tasks that have not been polled yet
+    pub queued: usize,
+    /// The number of tasks that completed
+    pub finished: u64,
+    /// The number of tasks that the threads of the pool are polling right now, which is the
+    /// number of threads that are busy. This is at most [TaskPoolStats::threads].
+    pub running: usize,
+    /// The sum of the durations of completed tasks, from their first poll until they completed
+    pub task_time: Duration,
+    /// The time the threads of the pool spent polling tasks, summed over all threads
+    pub busy_time: Duration,
+    /// The time threads were not polling tasks, summed over all threads
+    pub idle_time: Duration,
+}
+
+thread_local! {
+    /// The address of the [TaskPoolMetrics] of the pool that owns this thread, or 0
+    static WORKER_OF: Cell<usize> = const { Cell::new(0) };
+}
+
+impl TaskPoolMetrics {
+    pub(crate) fn new(threads: usize) -> Self {
+        TaskPoolMetrics {
+            created: Instant::now(),
+            spawned: Default::default(),
+            queued: Default::default(),
+            finished: Default::default(),
+            running: Default::default(),
+            task_nanos: Default::default(),
+            busy_nanos: Default::default(),
+            threads,
+        }
+    }
+
+    /// Returns the current values of the counters
+    pub fn stats(&self) -> TaskPoolStats {
+        let elapsed = self.created.elapsed();
+        let busy_time = Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed));
+        TaskPoolStats {
+            elapsed,
+            threads: self.threads,
+            spawned: self.spawned.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            finished: self.finished.load(Ordering::Relaxed),
+            running: self.running.load(Ordering::Relaxed),
+            task_time: Duration::from_nanos(self.task_nanos.load(Ordering::Relaxed)),
+            busy_time,
+            idle_time: (elapsed * self.threads as u32)
+                .checked_sub(busy_time)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Marks the current thread as a thread of the pool these metrics belong to
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn register_worker(self: &Arc<Self>) {
+        WORKER_OF.with(|worker_of| worker_of.set(Arc::as_ptr(self) as usize));
+    }
+
+    fn is_worker(&self) -> bool {
+        WORKER_OF.with(|worker_of| worker_of.get() == self as *const Self as usize)
+    }
+
+    /// Wraps `future` so that it updates the metrics when it is polled
+    pub(crate) fn instrument<F: Future>(self: &Arc<Self>, future: F) -> Instrumented<F> {
+        self.spawned.fetch_add(1, Ordering::Relaxed);
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        Instrumented {
+            future,
+            tracker: TaskTracker {
+                metrics: self.clone(),
+                started: None,
+            },
+        }
+    }
+}
+
+struct TaskTracker {
+    metrics: Arc<TaskPoolMetrics>,
+    started: Option<Instant>,
+}
+
+impl Drop for TaskTracker {
+    fn drop(&mut self) {
+        // tasks that are cancelled before they ran are no longer queued
+        if self.started.is_none() {
+            self.metrics.queued.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+pin_project! {
+    /// A future that updates [TaskPoolMetrics] when it is polled
+    pub(crate) struct Instrumented<F> {
+        #[pin]
+        future: F,
+        tracker: TaskTracker,
+    }
+}
+
+impl<F: Future> Future for Instrumented<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.project();
+        let metrics = &this.tracker.metrics;
+        let poll_start = Instant::now();
+        let started = *this.tracker.started.get_or_insert_with(|| {
+            metrics.queued.fetch_sub(1, Ordering::Relaxed);
+            poll_start
+        });
+
+        let is_worker = metrics.is_worker();
+        if is_worker {
+            metrics.running.fetch_add(1, Ordering::Relaxed);
+        }
+        let result = this.future.poll(cx);
+
+        let now = Instant::now();
+        if is_worker {
+            metrics.running.fetch_sub(1, Ordering::Relaxed);
+            metrics
+                .busy_nanos
+                .fetch_add((now - poll_start).as_nanos() as u64, Ordering::Relaxed);
+        }
+        if result.is_ready() {
+            metrics.finished.fetch_add(1, Ordering::Relaxed);
+            metrics
+                .task_nanos
+                .fetch_add((now - started).as_nanos() as u64, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskPoolMetrics;
+    use futures_lite::future;
+    use std::{sync::Arc, time::Duration};
+
+    #[test]
+    fn instrument() {
+        let metrics = Arc::new(TaskPoolMetrics::new(1));
+        let task = metrics.instrument(async {
+            future::yield_now().await;
+            std::thread::sleep(Duration::from_millis(5));
+        });
+        let cancelled = metrics.instrument(async {});
+        let stats = metrics.stats();
+        assert_eq!((stats.spawned, stats.queued, stats.finished), (2, 2, 0));
+
+        drop(cancelled);
+        let worker_metrics = metrics.clone();
+        std::thread::spawn(move || {
+            worker_metrics.register_worker();
+            future::block_on(task);
+        })
+        .join()
+        .unwrap();
+        let stats = metrics.stats();
+        assert_eq!((stats.spawned, stats.queued, stats.finished), (2, 0, 1));
+        assert_eq!(stats.running, 0);
+        assert!(stats.task_time >= Duration::from_millis(5));
+        assert!(stats.busy_time >= Duration::from_millis(5));
+        assert!(stats.idle_time + stats.busy_time <= stats.elapsed);
+
+        // polls on other threads, e.g. a thread waiting in `scope`, are not busy time
+        future::block_on(metrics.instrument(async {
+            std::thread::sleep(Duration::from_millis(5));
+        }));
+        let stats = metrics.stats();
+        assert_eq!(stats.finished, 2);
+        assert!(stats.busy_time < stats.task_time);
+    }
+}
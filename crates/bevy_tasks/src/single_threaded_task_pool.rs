@@ -1,12 +1,17 @@
 use std::{
     future::Future,
     mem,
+    rc::Rc,
     sync::{Arc, Mutex},
 };
 
+use crate::{metrics::TaskPoolMetrics, Task, TaskPriority};
+
 /// Used to create a TaskPool
 #[derive(Debug, Default, Clone)]
-pub struct TaskPoolBuilder {}
+pub struct TaskPoolBuilder {
+    metrics: bool,
+}
 
 impl TaskPoolBuilder {
     /// Creates a new TaskPoolBuilder instance
@@ -26,25 +31,61 @@ impl TaskPoolBuilder {
         self
     }
 
+    pub fn core_ids(self, _core_ids: Vec<usize>) -> Self {
+        self
+    }
+
+    /// Collect [TaskPoolMetrics] while the pool runs tasks. This wraps every task, so it is
+    /// disabled by default.
+    pub fn metrics(mut self, metrics: bool) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     pub fn build(self) -> TaskPool {
-        TaskPool::new_internal()
+        TaskPool::new_internal(self.metrics)
     }
 }
 
-/// A thread pool for executing tasks. Tasks are futures that are being automatically driven by
-/// the pool on threads owned by the pool. In this case - main thread only.
-#[derive(Debug, Default, Clone)]
-pub struct TaskPool {}
+/// A thread pool for executing tasks. In this case - main thread only: spawned tasks are driven
+/// by a future on the JS event loop, and also make progress when [TaskPool::tick] is called,
+/// which `CorePlugin` does once per frame.
+#[derive(Debug, Clone)]
+pub struct TaskPool {
+    metrics: Option<Arc<TaskPoolMetrics>>,
+}
+
+impl Default for TaskPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl TaskPool {
+    /// The maximum number of tasks [TaskPool::tick] polls
+    pub const MAX_TICK_POLLS: usize = 1024;
+
+    thread_local! {
+        static HIGH_PRIORITY_EXECUTOR: Rc<async_executor::LocalExecutor<'static>> = Default::default();
+        static EXECUTOR: Rc<async_executor::LocalExecutor<'static>> = Default::default();
+        #[cfg(target_arch = "wasm32")]
+        static DRIVER_RUNNING: std::cell::Cell<bool> = std::cell::Cell::new(false);
+    }
+
     /// Create a `TaskPool` with the default configuration.
     pub fn new() -> Self {
         TaskPoolBuilder::new().build()
     }
 
-    #[allow(unused_variables)]
-    fn new_internal() -> Self {
-        Self {}
+    fn new_internal(metrics: bool) -> Self {
+        Self {
+            // the pool has no threads of its own, so there is no busy or idle time to track
+            metrics: if metrics {
+                Some(Arc::new(TaskPoolMetrics::new(0)))
+            } else {
+                None
+            },
+        }
     }
 
     /// Return the number of threads owned by the task pool
@@ -52,6 +93,12 @@ impl TaskPool {
         1
     }
 
+    /// How many tasks the pool ran. This is `None` unless the pool was built with
+    /// [TaskPoolBuilder::metrics].
+    pub fn metrics(&self) -> Option<&TaskPoolMetrics> {
+        self.metrics.as_deref()
+    }
+
     /// Allows spawning non-`static futures on the thread pool. The function takes a callback,
     /// passing a scope object into it. The scope object provided to the callback can be used
     /// to spawn tasks. This function will await the completion of all tasks before returning.
@@ -66,15 +113,21 @@ impl TaskPool {
         let executor: &'scope async_executor::LocalExecutor<'scope> =
             unsafe { mem::transmute(executor) };
 
+        let high_priority_executor = &async_executor::LocalExecutor::new();
+        let high_priority_executor: &'scope async_executor::LocalExecutor<'scope> =
+            unsafe { mem::transmute(high_priority_executor) };
+
         let mut scope = Scope {
             executor,
+            high_priority_executor,
+            metrics: self.metrics.clone(),
             results: Vec::new(),
         };
 
         f(&mut scope);
 
-        // Loop until all tasks are done
-        while executor.try_tick() {}
+        // Loop until all tasks are done, running high priority tasks first
+        while scope.high_priority_executor.try_tick() || executor.try_tick() {}
 
         scope
             .results
@@ -83,44 +136,96 @@ impl TaskPool {
             .collect()
     }
 
-    // Spawns a static future onto the JS event loop. For now it is returning FakeTask
-    // instance with no-op detach method. Returning real Task is possible here, but tricky:
-    // future is running on JS event loop, Task is running on async_executor::LocalExecutor
-    // so some proxy future is needed. Moreover currently we don't have long-living
-    // LocalExecutor here (above `spawn` implementation creates temporary one)
-    // But for typical use cases it seems that current implementation should be sufficient:
-    // caller can spawn long-running future writing results to some channel / event queue
-    // and simply call detach on returned Task (like AssetServer does) - spawned future
-    // can write results to some channel / event queue.
+    /// Spawns a static future. The returned Task is a future. It can also be cancelled and
+    /// "detached" allowing it to continue running without having to be polled by the end-user.
+    ///
+    /// The task only makes progress on the JS event loop or while [TaskPool::tick] runs, so
+    /// blocking on it outside of a task never completes.
+    pub fn spawn<T>(&self, future: impl Future<Output = T> + 'static) -> Task<T>
+    where
+        T: 'static,
+    {
+        self.spawn_with_priority(TaskPriority::Normal, future)
+    }
 
-    pub fn spawn<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
+    /// Spawns a static future like [TaskPool::spawn]. Tasks with a higher priority that are
+    /// ready to run are run before tasks with a lower one.
+    pub fn spawn_with_priority<T>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = T> + 'static,
+    ) -> Task<T>
     where
         T: 'static,
     {
+        let executor = match priority {
+            TaskPriority::High => &Self::HIGH_PRIORITY_EXECUTOR,
+            TaskPriority::Normal => &Self::EXECUTOR,
+        };
+        let task = Task::new(executor.with(|executor| match &self.metrics {
+            Some(metrics) => executor.spawn(metrics.instrument(future)),
+            None => executor.spawn(future),
+        }));
+        #[cfg(target_arch = "wasm32")]
+        Self::start_driver();
+        task
+    }
+
+    /// Drives both executors from a future on the JS event loop, so tasks make progress when
+    /// they are woken, e.g. by a `fetch` that completed, without waiting for the next frame
+    #[cfg(target_arch = "wasm32")]
+    fn start_driver() {
+        use futures_lite::future;
+
+        if Self::DRIVER_RUNNING.with(|running| running.replace(true)) {
+            return;
+        }
+        let high_priority_executor = Self::HIGH_PRIORITY_EXECUTOR.with(Rc::clone);
+        let executor = Self::EXECUTOR.with(Rc::clone);
         wasm_bindgen_futures::spawn_local(async move {
-            future.await;
+            loop {
+                if high_priority_executor.try_tick() || executor.try_tick() {
+                    // let other futures on the JS event loop make progress between tasks
+                    future::yield_now().await;
+                    continue;
+                }
+                future::or(high_priority_executor.tick(), executor.tick()).await;
+            }
         });
-        FakeTask
     }
 
-    pub fn spawn_local<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
+    pub fn spawn_local<T>(&self, future: impl Future<Output = T> + 'static) -> Task<T>
     where
         T: 'static,
     {
         self.spawn(future)
     }
-}
 
-#[derive(Debug)]
-pub struct FakeTask;
-
-impl FakeTask {
-    pub fn detach(self) {}
+    /// Runs the spawned tasks that are ready, until none are left or [TaskPool::MAX_TICK_POLLS]
+    /// tasks were polled, so tasks that keep waking themselves don't stall the frame. Returns
+    /// the number of tasks that were polled.
+    ///
+    /// All pools share the same executors, so ticking one pool runs the tasks of all of them.
+    pub fn tick(&self) -> usize {
+        Self::HIGH_PRIORITY_EXECUTOR.with(|high_priority_executor| {
+            Self::EXECUTOR.with(|executor| {
+                let mut polls = 0;
+                while polls < Self::MAX_TICK_POLLS
+                    && (high_priority_executor.try_tick() || executor.try_tick())
+                {
+                    polls += 1;
+                }
+                polls
+            })
+        })
+    }
 }
 
 #[derive(Debug)]
 pub struct Scope<'scope, T> {
     executor: &'scope async_executor::LocalExecutor<'scope>,
+    high_priority_executor: &'scope async_executor::LocalExecutor<'scope>,
+    metrics: Option<Arc<TaskPoolMetrics>>,
     // Vector to gather results of all futures spawned during scope run
     results: Vec<Arc<Mutex<Option<T>>>>,
 }
@@ -131,11 +236,50 @@ impl<'scope, T: Send + 'scope> Scope<'scope, T> {
     }
 
     pub fn spawn_local<Fut: Future<Output = T> + 'scope>(&mut self, f: Fut) {
+        self.spawn_with_priority(TaskPriority::Normal, f);
+    }
+
+    pub fn spawn_with_priority<Fut: Future<Output = T> + 'scope>(
+        &mut self,
+        priority: TaskPriority,
+        f: Fut,
+    ) {
+        let executor = match priority {
+            TaskPriority::High => self.high_priority_executor,
+            TaskPriority::Normal => self.executor,
+        };
         let result = Arc::new(Mutex::new(None));
         self.results.push(result.clone());
         let f = async move {
             result.lock().unwrap().replace(f.await);
         };
-        self.executor.spawn(f).detach();
+        match &self.metrics {
+            Some(metrics) => executor.spawn(metrics.instrument(f)).detach(),
+            None => executor.spawn(f).detach(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TaskPool;
+    use futures_lite::future;
+
+    #[test]
+    fn spawn_and_tick() {
+        let pool = TaskPool::new();
+        let (sender, receiver) = async_channel::unbounded();
+        let task = pool.spawn(async move { receiver.recv().await.unwrap() * 2 });
+        assert_eq!(pool.tick(), 1);
+        sender.try_send(21).unwrap();
+        // all pools share the executors, so a pool of another usage runs the task
+        assert_eq!(TaskPool::new().tick(), 1);
+        assert_eq!(future::block_on(future::poll_once(task)), Some(42));
+
+        let results = pool.scope(|scope| {
+            scope.spawn(async { 1 });
+            scope.spawn(async { 2 });
+        });
+        assert_eq!(results, vec![1, 2]);
     }
 }
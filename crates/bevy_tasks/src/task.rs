@@ -49,3 +49,14 @@ impl<T> Future for Task<T> {
         Pin::new(&mut self.0).poll(cx)
     }
 }
+
+/// The priority of a spawned task. When several tasks are ready to run, tasks with a higher
+/// priority run first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TaskPriority {
+    /// The priority of tasks spawned with `spawn`
+    #[default]
+    Normal,
+    /// For tasks that are waited on soon, e.g. work the next frame depends on
+    High,
+}
@@ -8,7 +8,7 @@ use std::{
 
 use futures_lite::{future, pin};
 
-use crate::Task;
+use crate::{metrics::TaskPoolMetrics, Task, TaskPriority};
 
 /// Used to create a TaskPool
 #[derive(Debug, Default, Clone)]
@@ -21,6 +21,10 @@ pub struct TaskPoolBuilder {
     /// Allows customizing the name of the threads - helpful for debugging. If set, threads will
     /// be named <thread_name> (<thread_index>), i.e. "MyThreadPool (2)"
     thread_name: Option<String>,
+    /// If set, thread <thread_index> is pinned to core core_ids[thread_index % core_ids.len()]
+    core_ids: Option<Vec<usize>>,
+    /// If set, the pool collects [TaskPoolMetrics]
+    metrics: bool,
 }
 
 impl TaskPoolBuilder {
@@ -49,12 +53,28 @@ impl TaskPoolBuilder {
         self
     }
 
+    /// Pin the threads created for the pool to these cores, see [crate::core_ids]. Thread i is
+    /// pinned to core `core_ids[i % core_ids.len()]`.
+    pub fn core_ids(mut self, core_ids: Vec<usize>) -> Self {
+        self.core_ids = Some(core_ids).filter(|core_ids| !core_ids.is_empty());
+        self
+    }
+
+    /// Collect [TaskPoolMetrics] while the pool runs tasks. This wraps every task, so it is
+    /// disabled by default.
+    pub fn metrics(mut self, metrics: bool) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     /// Creates a new ThreadPoolBuilder based on the current options.
     pub fn build(self) -> TaskPool {
         TaskPool::new_internal(
             self.num_threads,
             self.stack_size,
             self.thread_name.as_deref(),
+            self.core_ids,
+            self.metrics,
         )
     }
 }
@@ -90,11 +110,20 @@ pub struct TaskPool {
     /// the Vec<Task<T>> contained within TaskPoolInner
     executor: Arc<async_executor::Executor<'static>>,
 
+    /// The executor for [TaskPriority::High] tasks. Threads run its tasks first whenever
+    /// `executor` yields.
+    high_priority_executor: Arc<async_executor::Executor<'static>>,
+
     /// Inner state of the pool
     inner: Arc<TaskPoolInner>,
+
+    metrics: Option<Arc<TaskPoolMetrics>>,
 }
 
 impl TaskPool {
+    /// The maximum number of tasks [TaskPool::tick] polls
+    pub const MAX_TICK_POLLS: usize = 1024;
+
     thread_local! {
         static LOCAL_EXECUTOR: async_executor::LocalExecutor<'static> = async_executor::LocalExecutor::new();
     }
@@ -108,17 +137,30 @@ impl TaskPool {
         num_threads: Option<usize>,
         stack_size: Option<usize>,
         thread_name: Option<&str>,
+        core_ids: Option<Vec<usize>>,
+        metrics: bool,
     ) -> Self {
         let (shutdown_tx, shutdown_rx) = async_channel::unbounded::<()>();
 
         let executor = Arc::new(async_executor::Executor::new());
+        let high_priority_executor = Arc::new(async_executor::Executor::new());
 
         let num_threads = num_threads.unwrap_or_else(num_cpus::get);
+        let metrics = if metrics {
+            Some(Arc::new(TaskPoolMetrics::new(num_threads)))
+        } else {
+            None
+        };
 
         let threads = (0..num_threads)
             .map(|i| {
                 let ex = Arc::clone(&executor);
+                let high_priority_ex = Arc::clone(&high_priority_executor);
                 let shutdown_rx = shutdown_rx.clone();
+                let metrics = metrics.clone();
+                let core_id = core_ids
+                    .as_ref()
+                    .map(|core_ids| core_ids[i % core_ids.len()]);
 
                 let thread_name = if let Some(thread_name) = thread_name {
                     format!("{} ({})", thread_name, i)
@@ -134,7 +176,15 @@ impl TaskPool {
 
                 thread_builder
                     .spawn(move || {
-                        let shutdown_future = ex.run(shutdown_rx.recv());
+                        if let Some(core_id) = core_id {
+                            core_affinity::set_for_current(core_affinity::CoreId { id: core_id });
+                        }
+                        if let Some(metrics) = metrics {
+                            metrics.register_worker();
+                        }
+                        // The normal executor yields whenever it has no task to run, and at least
+                        // every 200 tasks, and then the high priority executor runs first
+                        let shutdown_future = ex.run(high_priority_ex.run(shutdown_rx.recv()));
                         // Use unwrap_err because we expect a Closed error
                         future::block_on(shutdown_future).unwrap_err();
                     })
@@ -144,10 +194,12 @@ impl TaskPool {
 
         Self {
             executor,
+            high_priority_executor,
             inner: Arc::new(TaskPoolInner {
                 threads,
                 shutdown_tx,
             }),
+            metrics,
         }
     }
 
@@ -156,6 +208,12 @@ impl TaskPool {
         self.inner.threads.len()
     }
 
+    /// How many tasks the pool ran, and how busy its threads are. This is `None` unless the
+    /// pool was built with [TaskPoolBuilder::metrics].
+    pub fn metrics(&self) -> Option<&TaskPoolMetrics> {
+        self.metrics.as_deref()
+    }
+
     /// Allows spawning non-`static futures on the thread pool. The function takes a callback,
     /// passing a scope object into it. The scope object provided to the callback can be used
     /// to spawn tasks. This function will await the completion of all tasks before returning.
@@ -173,10 +231,15 @@ impl TaskPool {
             // validate safety.
             let executor: &async_executor::Executor = &*self.executor;
             let executor: &'scope async_executor::Executor = unsafe { mem::transmute(executor) };
+            let high_priority_executor: &async_executor::Executor = &self.high_priority_executor;
+            let high_priority_executor: &'scope async_executor::Executor =
+                unsafe { mem::transmute(high_priority_executor) };
             let local_executor: &'scope async_executor::LocalExecutor =
                 unsafe { mem::transmute(local_executor) };
             let mut scope = Scope {
                 executor,
+                high_priority_executor,
+                metrics: self.metrics.clone(),
                 local_executor,
                 spawned: Vec::new(),
             };
@@ -219,7 +282,9 @@ impl TaskPool {
                         break result;
                     };
 
-                    self.executor.try_tick();
+                    if !self.high_priority_executor.try_tick() {
+                        self.executor.try_tick();
+                    }
                     local_executor.try_tick();
                 }
             }
@@ -233,14 +298,59 @@ impl TaskPool {
     where
         T: Send + 'static,
     {
-        Task::new(self.executor.spawn(future))
+        self.spawn_with_priority(TaskPriority::Normal, future)
+    }
+
+    /// Spawns a static future onto the thread pool, like [TaskPool::spawn]. When a thread of the
+    /// pool looks for work, it starts tasks with a higher priority first. A thread that is
+    /// running a batch of ready tasks may finish up to 200 of them before it looks again.
+    pub fn spawn_with_priority<T>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Task<T>
+    where
+        T: Send + 'static,
+    {
+        let executor = match priority {
+            TaskPriority::High => &self.high_priority_executor,
+            TaskPriority::Normal => &self.executor,
+        };
+        match &self.metrics {
+            Some(metrics) => Task::new(executor.spawn(metrics.instrument(future))),
+            None => Task::new(executor.spawn(future)),
+        }
     }
 
+    /// Spawns a static future on the current thread. It makes progress while this thread runs
+    /// [TaskPool::tick] or [TaskPool::scope].
     pub fn spawn_local<T>(&self, future: impl Future<Output = T> + 'static) -> Task<T>
     where
         T: 'static,
     {
-        Task::new(TaskPool::LOCAL_EXECUTOR.with(|executor| executor.spawn(future)))
+        Task::new(
+            TaskPool::LOCAL_EXECUTOR.with(|executor| match &self.metrics {
+                Some(metrics) => executor.spawn(metrics.instrument(future)),
+                None => executor.spawn(future),
+            }),
+        )
+    }
+
+    /// Runs the tasks spawned with [TaskPool::spawn_local] on the current thread that are ready,
+    /// until none are left or [TaskPool::MAX_TICK_POLLS] tasks were polled, so tasks that keep
+    /// waking themselves don't stall the frame. Returns the number of tasks that were polled.
+    ///
+    /// Tasks spawned with [TaskPool::spawn] run on the pool's threads, and don't need this.
+    /// All pools share the local executor of a thread, so ticking one pool runs the local tasks
+    /// of all of them.
+    pub fn tick(&self) -> usize {
+        TaskPool::LOCAL_EXECUTOR.with(|local_executor| {
+            let mut polls = 0;
+            while polls < Self::MAX_TICK_POLLS && local_executor.try_tick() {
+                polls += 1;
+            }
+            polls
+        })
     }
 }
 
@@ -253,18 +363,38 @@ impl Default for TaskPool {
 #[derive(Debug)]
 pub struct Scope<'scope, T> {
     executor: &'scope async_executor::Executor<'scope>,
+    high_priority_executor: &'scope async_executor::Executor<'scope>,
+    metrics: Option<Arc<TaskPoolMetrics>>,
     local_executor: &'scope async_executor::LocalExecutor<'scope>,
     spawned: Vec<async_executor::Task<T>>,
 }
 
 impl<'scope, T: Send + 'scope> Scope<'scope, T> {
     pub fn spawn<Fut: Future<Output = T> + 'scope + Send>(&mut self, f: Fut) {
-        let task = self.executor.spawn(f);
+        self.spawn_with_priority(TaskPriority::Normal, f);
+    }
+
+    pub fn spawn_with_priority<Fut: Future<Output = T> + 'scope + Send>(
+        &mut self,
+        priority: TaskPriority,
+        f: Fut,
+    ) {
+        let executor = match priority {
+            TaskPriority::High => self.high_priority_executor,
+            TaskPriority::Normal => self.executor,
+        };
+        let task = match &self.metrics {
+            Some(metrics) => executor.spawn(metrics.instrument(f)),
+            None => executor.spawn(f),
+        };
         self.spawned.push(task);
     }
 
     pub fn spawn_local<Fut: Future<Output = T> + 'scope>(&mut self, f: Fut) {
-        let task = self.local_executor.spawn(f);
+        let task = match &self.metrics {
+            Some(metrics) => self.local_executor.spawn(metrics.instrument(f)),
+            None => self.local_executor.spawn(f),
+        };
         self.spawned.push(task);
     }
 }
@@ -275,7 +405,7 @@ mod tests {
     use super::*;
     use std::sync::{
         atomic::{AtomicBool, AtomicI32, Ordering},
-        Barrier,
+        Barrier, Mutex,
     };
 
     #[test]
@@ -390,4 +520,74 @@ mod tests {
         assert!(!thread_check_failed.load(Ordering::Acquire));
         assert_eq!(count.load(Ordering::Acquire), 200);
     }
+
+    #[test]
+    pub fn test_priority() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // keep the only thread busy until all tasks are queued
+        let (unblock_tx, unblock_rx) = std::sync::mpsc::channel::<()>();
+        let blocker = pool.spawn_with_priority(TaskPriority::High, async move {
+            unblock_rx.recv().unwrap();
+        });
+        let mut tasks = Vec::new();
+        for priority in [TaskPriority::Normal, TaskPriority::High].iter().copied() {
+            for _ in 0..3 {
+                let order = order.clone();
+                tasks.push(pool.spawn_with_priority(priority, async move {
+                    order.lock().unwrap().push(priority);
+                }));
+            }
+        }
+        unblock_tx.send(()).unwrap();
+        future::block_on(blocker);
+        for task in tasks {
+            future::block_on(task);
+        }
+
+        use TaskPriority::*;
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec![High, High, High, Normal, Normal, Normal]
+        );
+        assert!(High > Normal);
+    }
+
+    #[test]
+    pub fn test_metrics() {
+        assert!(TaskPool::new().metrics().is_none());
+
+        let pool = TaskPoolBuilder::new().num_threads(2).metrics(true).build();
+        let running = pool.scope(|scope| {
+            for _ in 0..20 {
+                let pool = &pool;
+                scope.spawn(async move { pool.metrics().unwrap().stats().running });
+            }
+        });
+        assert!(running.iter().all(|running| *running <= 2));
+        let stats = pool.metrics().unwrap().stats();
+        assert_eq!((stats.spawned, stats.queued, stats.finished), (20, 0, 20));
+        assert_eq!(stats.threads, 2);
+    }
+
+    #[test]
+    pub fn test_tick() {
+        let pool = TaskPool::new();
+        let (sender, receiver) = async_channel::unbounded();
+        let task = pool.spawn_local(async move { receiver.recv().await.unwrap() * 2 });
+        pool.tick();
+        sender.try_send(21).unwrap();
+        assert_eq!(pool.tick(), 1);
+        assert_eq!(future::block_on(future::poll_once(task)), Some(42));
+
+        // tasks that keep yielding are only polled a limited number of times per tick
+        pool.spawn_local(async {
+            loop {
+                future::yield_now().await;
+            }
+        })
+        .detach();
+        assert_eq!(pool.tick(), TaskPool::MAX_TICK_POLLS);
+    }
 }
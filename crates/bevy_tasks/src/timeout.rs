@@ -0,0 +1,334 @@
+use futures_lite::future;
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// The error returned by [with_timeout] when the future did not complete in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the future did not complete before its timeout")
+    }
+}
+
+impl Error for TimedOut {}
+
+/// Runs `future` until it completes, or until `duration` passed. On timeout, `future` is dropped,
+/// so a [Task](crate::Task) passed to this is cancelled.
+///
+/// ```
+/// # use bevy_tasks::{with_timeout, TaskPool, TimedOut};
+/// # use std::time::Duration;
+/// let pool = TaskPool::new();
+/// let task = pool.spawn(futures_lite::future::pending::<()>());
+/// let result = futures_lite::future::block_on(with_timeout(Duration::from_millis(10), task));
+/// assert_eq!(result, Err(TimedOut));
+/// ```
+pub async fn with_timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, TimedOut> {
+    future::or(async { Ok(future.await) }, async {
+        sleep(duration).await;
+        Err(TimedOut)
+    })
+    .await
+}
+
+/// Completes after `duration`. This works on any executor: on native targets a shared timer
+/// thread wakes the future, and on wasm it uses `setTimeout`.
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: instant::Instant::now() + duration,
+        timer: None,
+    }
+}
+
+/// The future returned by [sleep]
+#[derive(Debug)]
+pub struct Sleep {
+    deadline: instant::Instant,
+    timer: Option<timer::Timer>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let now = instant::Instant::now();
+        if now >= self.deadline {
+            return Poll::Ready(());
+        }
+        let deadline = self.deadline;
+        let timer = self
+            .timer
+            .get_or_insert_with(|| timer::Timer::new(deadline, now));
+        if timer.poll(cx).is_ready() {
+            // the timer may fire early, e.g. because `setTimeout` works in milliseconds
+            self.timer = None;
+            if instant::Instant::now() < deadline {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            return Poll::Ready(());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod timer {
+    use once_cell::sync::Lazy;
+    use std::{
+        cmp::Ordering,
+        collections::BinaryHeap,
+        sync::{
+            atomic::{self, AtomicBool},
+            Arc, Condvar, Mutex,
+        },
+        task::{Context, Poll, Waker},
+        thread,
+        time::Instant,
+    };
+
+    #[derive(Debug, Default)]
+    struct TimerState {
+        fired: AtomicBool,
+        waker: Mutex<Option<Waker>>,
+    }
+
+    impl TimerState {
+        fn fire(&self) {
+            self.fired.store(true, atomic::Ordering::SeqCst);
+            if let Some(waker) = self.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+
+    struct TimerEntry {
+        deadline: Instant,
+        state: Arc<TimerState>,
+    }
+
+    impl PartialEq for TimerEntry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for TimerEntry {}
+
+    impl PartialOrd for TimerEntry {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for TimerEntry {
+        // the earliest deadline is the greatest, so it is at the top of the heap
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    /// Fires the timers of all [Timer]s from one thread, which is started on first use
+    struct TimerThread {
+        timers: Mutex<BinaryHeap<TimerEntry>>,
+        condvar: Condvar,
+    }
+
+    static TIMER_THREAD: Lazy<Arc<TimerThread>> = Lazy::new(|| {
+        let timer_thread = Arc::new(TimerThread {
+            timers: Mutex::new(BinaryHeap::new()),
+            condvar: Condvar::new(),
+        });
+        let thread_timer_thread = timer_thread.clone();
+        thread::Builder::new()
+            .name("TaskPool timer".to_string())
+            .spawn(move || thread_timer_thread.run())
+            .expect("Failed to spawn thread.");
+        timer_thread
+    });
+
+    impl TimerThread {
+        fn run(&self) {
+            let mut timers = self.timers.lock().unwrap();
+            loop {
+                let now = Instant::now();
+                while timers.peek().is_some_and(|timer| timer.deadline <= now) {
+                    timers.pop().unwrap().state.fire();
+                }
+                timers = match timers.peek() {
+                    Some(timer) => {
+                        let timeout = timer.deadline - now;
+                        self.condvar.wait_timeout(timers, timeout).unwrap().0
+                    }
+                    None => self.condvar.wait(timers).unwrap(),
+                };
+            }
+        }
+
+        fn add(&self, deadline: Instant, state: Arc<TimerState>) {
+            self.timers
+                .lock()
+                .unwrap()
+                .push(TimerEntry { deadline, state });
+            self.condvar.notify_one();
+        }
+
+        fn remove(&self, state: &Arc<TimerState>) {
+            let mut timers = self.timers.lock().unwrap();
+            let mut entries = std::mem::take(&mut *timers).into_vec();
+            entries.retain(|entry| !Arc::ptr_eq(&entry.state, state));
+            *timers = entries.into();
+        }
+    }
+
+    #[derive(Debug)]
+    pub(super) struct Timer {
+        deadline: Instant,
+        state: Option<Arc<TimerState>>,
+    }
+
+    impl Timer {
+        pub(super) fn new(deadline: Instant, _now: Instant) -> Self {
+            Timer {
+                deadline,
+                state: None,
+            }
+        }
+
+        pub(super) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+            let deadline = self.deadline;
+            let state = self.state.get_or_insert_with(|| {
+                let state = Arc::new(TimerState::default());
+                TIMER_THREAD.add(deadline, state.clone());
+                state
+            });
+            *state.waker.lock().unwrap() = Some(cx.waker().clone());
+            // checked after storing the waker, so a timer that fires in between is not missed
+            if state.fired.load(atomic::Ordering::SeqCst) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl Drop for Timer {
+        fn drop(&mut self) {
+            // a cancelled sleep would otherwise keep its entry until the deadline passed
+            if let Some(state) = &self.state {
+                if !state.fired.load(atomic::Ordering::SeqCst) {
+                    TIMER_THREAD.remove(state);
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::TIMER_THREAD;
+        use futures_lite::future;
+        use std::{sync::Arc, time::Duration};
+
+        #[test]
+        fn drop_removes_timer() {
+            let mut sleep = super::super::sleep(Duration::from_secs(60));
+            assert!(future::block_on(future::poll_once(&mut sleep)).is_none());
+            let state = sleep.timer.as_ref().unwrap().state.clone().unwrap();
+            let is_pending = |state| {
+                TIMER_THREAD
+                    .timers
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .any(|entry| Arc::ptr_eq(&entry.state, state))
+            };
+            assert!(is_pending(&state));
+            drop(sleep);
+            assert!(!is_pending(&state));
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod timer {
+    use std::{
+        fmt,
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use wasm_bindgen_futures::JsFuture;
+
+    pub(super) struct Timer {
+        promise: JsFuture,
+    }
+
+    impl Timer {
+        pub(super) fn new(deadline: instant::Instant, now: instant::Instant) -> Self {
+            let millis = (deadline - now).as_millis().min(i32::MAX as u128) as i32;
+            let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+                web_sys::window()
+                    .expect("sleep requires a window")
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+                    .expect("failed to call setTimeout");
+            });
+            Timer {
+                promise: JsFuture::from(promise),
+            }
+        }
+
+        pub(super) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+            Pin::new(&mut self.promise).poll(cx).map(|_| ())
+        }
+    }
+
+    impl fmt::Debug for Timer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Timer").finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sleep, with_timeout, TimedOut};
+    use futures_lite::future;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn sleep_duration() {
+        let start = Instant::now();
+        future::block_on(sleep(Duration::from_millis(20)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn timeout() {
+        assert_eq!(
+            future::block_on(with_timeout(Duration::from_secs(10), async { 5 })),
+            Ok(5)
+        );
+        assert_eq!(
+            future::block_on(with_timeout(
+                Duration::from_millis(10),
+                future::pending::<()>()
+            )),
+            Err(TimedOut)
+        );
+
+        // a timer that fires before the timeout
+        let result = future::block_on(with_timeout(
+            Duration::from_millis(50),
+            sleep(Duration::from_millis(5)),
+        ));
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::components::{Children, Parent};
+use bevy_core::{EntityNames, Name};
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, Res, SystemParam},
+    world::World,
+};
+use std::fmt;
+
+/// A path to an entity through the [Name]s of its ancestors, e.g. `"Player/Arm/Hand"`.
+///
+/// A path is resolved one segment at a time: the first segment is looked up in [EntityNames],
+/// and each following segment must name a child of the entity matched by the previous one. The
+/// first segment can match any named entity, so a path does not have to start at the root of the
+/// hierarchy. If several entities match, the first one that fully resolves is returned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EntityPath {
+    parts: Vec<Name>,
+}
+
+impl EntityPath {
+    pub const SEPARATOR: char = '/';
+
+    /// Parses a path, ignoring empty segments
+    pub fn parse(path: &str) -> Self {
+        EntityPath {
+            parts: path
+                .split(Self::SEPARATOR)
+                .filter(|part| !part.is_empty())
+                .map(Name::from)
+                .collect(),
+        }
+    }
+
+    #[inline]
+    pub fn parts(&self) -> &[Name] {
+        &self.parts
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Appends a segment to the end of the path
+    pub fn push(&mut self, name: impl Into<Name>) {
+        self.parts.push(name.into());
+    }
+
+    /// The path from the root of `entity`'s hierarchy to `entity`, or `None` if `entity` or one of
+    /// its ancestors has no [Name]
+    pub fn from_entity(entity: Entity, world: &World) -> Option<EntityPath> {
+        path_of(entity, &|entity| world.get::<Name>(entity), &|entity| {
+            world.get::<Parent>(entity).map(|parent| parent.0)
+        })
+    }
+
+    /// Resolves the path, see [EntityPath]. Returns `None` if the [EntityNames] resource does not
+    /// exist.
+    pub fn resolve(&self, world: &World) -> Option<Entity> {
+        let entity_names = world.get_resource::<EntityNames>()?;
+        resolve(
+            &self.parts,
+            entity_names,
+            &|entity| world.get::<Name>(entity),
+            &|entity| world.get::<Children>(entity),
+        )
+    }
+
+    /// Resolves the path relative to `root`: the first segment names a child of `root`. An empty
+    /// path resolves to `root`.
+    pub fn resolve_from(&self, root: Entity, world: &World) -> Option<Entity> {
+        resolve_from(
+            &self.parts,
+            root,
+            &|entity| world.get::<Name>(entity),
+            &|entity| world.get::<Children>(entity),
+        )
+    }
+}
+
+impl From<&str> for EntityPath {
+    fn from(path: &str) -> Self {
+        EntityPath::parse(path)
+    }
+}
+
+impl From<String> for EntityPath {
+    fn from(path: String) -> Self {
+        EntityPath::parse(&path)
+    }
+}
+
+impl fmt::Display for EntityPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, part) in self.parts.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{}", Self::SEPARATOR)?;
+            }
+            write!(f, "{}", part.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves [EntityPath]s from systems
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::prelude::*;
+/// fn wave(paths: EntityPaths, mut transforms: Query<&mut Transform>) {
+///     if let Some(hand) = paths.resolve(&"Player/Arm/Hand".into()) {
+///         if let Ok(mut transform) = transforms.get_mut(hand) {
+///             transform.rotate(Default::default());
+///         }
+///     }
+/// }
+/// # wave.system();
+/// ```
+#[derive(SystemParam)]
+pub struct EntityPaths<'a> {
+    entity_names: Res<'a, EntityNames>,
+    names: Query<'a, &'static Name>,
+    children: Query<'a, &'static Children>,
+    parents: Query<'a, &'static Parent>,
+}
+
+impl<'a> EntityPaths<'a> {
+    /// Resolves `path`, see [EntityPath]
+    pub fn resolve(&self, path: &EntityPath) -> Option<Entity> {
+        resolve(
+            &path.parts,
+            &self.entity_names,
+            &|entity| self.names.get(entity).ok(),
+            &|entity| self.children.get(entity).ok(),
+        )
+    }
+
+    /// Resolves `path` relative to `root`, see [EntityPath::resolve_from]
+    pub fn resolve_from(&self, root: Entity, path: &EntityPath) -> Option<Entity> {
+        resolve_from(
+            &path.parts,
+            root,
+            &|entity| self.names.get(entity).ok(),
+            &|entity| self.children.get(entity).ok(),
+        )
+    }
+
+    /// The path of `entity`, see [EntityPath::from_entity]
+    pub fn path_of(&self, entity: Entity) -> Option<EntityPath> {
+        path_of(entity, &|entity| self.names.get(entity).ok(), &|entity| {
+            self.parents.get(entity).ok().map(|parent| parent.0)
+        })
+    }
+}
+
+fn resolve<'a>(
+    parts: &[Name],
+    entity_names: &EntityNames,
+    name: &dyn Fn(Entity) -> Option<&'a Name>,
+    children: &dyn Fn(Entity) -> Option<&'a Children>,
+) -> Option<Entity> {
+    let (first, rest) = parts.split_first()?;
+    entity_names
+        .get(first.as_str())
+        .iter()
+        .find_map(|entity| resolve_from(rest, *entity, name, children))
+}
+
+fn resolve_from<'a>(
+    parts: &[Name],
+    root: Entity,
+    name: &dyn Fn(Entity) -> Option<&'a Name>,
+    children: &dyn Fn(Entity) -> Option<&'a Children>,
+) -> Option<Entity> {
+    let (first, rest) = match parts.split_first() {
+        Some(split) => split,
+        None => return Some(root),
+    };
+    children(root)?
+        .iter()
+        .filter(|child| name(**child) == Some(first))
+        .find_map(|child| resolve_from(rest, *child, name, children))
+}
+
+fn path_of<'a>(
+    entity: Entity,
+    name: &dyn Fn(Entity) -> Option<&'a Name>,
+    parent: &dyn Fn(Entity) -> Option<Entity>,
+) -> Option<EntityPath> {
+    let mut parts = Vec::new();
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        parts.push(name(entity)?.clone());
+        current = parent(entity);
+    }
+    parts.reverse();
+    Some(EntityPath { parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EntityPath, EntityPaths};
+    use crate::{components::Parent, hierarchy::BuildWorldChildren};
+    use bevy_core::{entity_names_system, EntityNames, Name};
+    use bevy_ecs::{
+        entity::Entity,
+        schedule::{Schedule, Stage, SystemStage},
+        system::{IntoSystem, ResMut},
+        world::World,
+    };
+
+    struct Hands(Vec<Entity>);
+
+    fn setup() -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        let mut hands = Vec::new();
+        for player in ["Player", "Enemy"].iter() {
+            world
+                .spawn()
+                .insert(Name::new(*player))
+                .with_children(|parent| {
+                    parent.spawn().insert(Name::new("Leg"));
+                    parent.spawn().insert(Name::new("Arm"));
+                    parent
+                        .spawn()
+                        .insert(Name::new("Arm"))
+                        .with_children(|parent| {
+                            hands.push(parent.spawn().insert(Name::new("Hand")).id());
+                        });
+                });
+        }
+
+        world.insert_resource(EntityNames::default());
+        let mut schedule = Schedule::default();
+        schedule.add_stage("test", SystemStage::single_threaded());
+        schedule.add_system_to_stage("test", entity_names_system.system());
+        schedule.run(&mut world);
+        (world, hands)
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let path = EntityPath::parse("/Player//Arm/Hand");
+        assert_eq!(path.parts().len(), 3);
+        assert_eq!(path.to_string(), "Player/Arm/Hand");
+        assert_eq!(EntityPath::from("Player/Arm/Hand"), path);
+        assert!(EntityPath::parse("").is_empty());
+    }
+
+    #[test]
+    fn resolve() {
+        let (world, hands) = setup();
+        assert_eq!(
+            EntityPath::from("Player/Arm/Hand").resolve(&world),
+            Some(hands[0])
+        );
+        assert_eq!(
+            EntityPath::from("Enemy/Arm/Hand").resolve(&world),
+            Some(hands[1])
+        );
+        assert_eq!(EntityPath::from("Player/Leg/Hand").resolve(&world), None);
+        assert_eq!(EntityPath::from("Player/Hand").resolve(&world), None);
+    }
+
+    #[test]
+    fn resolve_from() {
+        let (world, hands) = setup();
+        let arm = world.get::<Parent>(hands[0]).unwrap().0;
+        assert_eq!(
+            EntityPath::from("Hand").resolve_from(arm, &world),
+            Some(hands[0])
+        );
+        assert_eq!(EntityPath::default().resolve_from(arm, &world), Some(arm));
+        assert_eq!(
+            EntityPath::from_entity(hands[1], &world),
+            Some(EntityPath::from("Enemy/Arm/Hand"))
+        );
+    }
+
+    #[test]
+    fn system_param() {
+        fn find_hands(paths: EntityPaths, mut hands: ResMut<Hands>) {
+            let path = EntityPath::from("Player/Arm/Hand");
+            let hand = paths.resolve(&path).unwrap();
+            assert_eq!(paths.path_of(hand), Some(path));
+            hands.0.push(hand);
+        }
+
+        let (mut world, expected) = setup();
+        world.insert_resource(Hands(Vec::new()));
+        let mut schedule = Schedule::default();
+        schedule.add_stage("test", SystemStage::single_threaded());
+        schedule.add_system_to_stage("test", find_hands.system());
+        schedule.run(&mut world);
+        assert_eq!(world.get_resource::<Hands>().unwrap().0, &expected[..1]);
+    }
+}
@@ -1,8 +1,10 @@
 mod child_builder;
+mod entity_path;
 #[allow(clippy::module_inception)]
 mod hierarchy;
 mod hierarchy_maintenance_system;
 
 pub use child_builder::*;
+pub use entity_path::*;
 pub use hierarchy::*;
 pub use hierarchy_maintenance_system::*;
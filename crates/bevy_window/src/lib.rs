@@ -48,14 +48,15 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
-            .init_resource::<Windows>();
+            .init_resource::<Windows>()
+            .register_type::<WindowResizeConstraints>()
+            .register_type::<WindowMode>()
+            .register_type::<Option<f64>>()
+            .init_settings::<WindowDescriptor>();
 
         if self.add_primary_window {
             let world = app.world_mut();
-            let window_descriptor = world
-                .get_resource::<WindowDescriptor>()
-                .map(|descriptor| (*descriptor).clone())
-                .unwrap_or_else(WindowDescriptor::default);
+            let window_descriptor = world.get_resource::<WindowDescriptor>().unwrap().clone();
             let mut create_window_event = world.get_resource_mut::<Events<CreateWindow>>().unwrap();
             create_window_event.send(CreateWindow {
                 id: WindowId::primary(),
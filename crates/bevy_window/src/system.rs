@@ -6,6 +6,6 @@ pub fn exit_on_window_close_system(
     mut window_close_requested_events: EventReader<WindowCloseRequested>,
 ) {
     if window_close_requested_events.iter().next().is_some() {
-        app_exit_events.send(AppExit);
+        app_exit_events.send(AppExit::success());
     }
 }
@@ -1,5 +1,8 @@
+use bevy_ecs::reflect::ReflectResource;
 use bevy_math::{IVec2, Vec2};
+use bevy_reflect::{Reflect, ReflectDeserialize};
 use bevy_utils::{tracing::warn, Uuid};
+use serde::{Deserialize, Serialize};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct WindowId(Uuid);
@@ -38,7 +41,7 @@ impl Default for WindowId {
 /// Please note that if the window is resizable, then when the window is
 /// maximized it may have a size outside of these limits. The functionality
 /// required to disable maximizing is not yet exposed by winit.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Reflect)]
 pub struct WindowResizeConstraints {
     pub min_width: f32,
     pub min_height: f32,
@@ -183,7 +186,8 @@ pub enum WindowCommand {
 /// defines whether a videomode is chosen that best fits the width and height
 /// in the Window structure, or if these are ignored.
 /// E.g. when use_size is set to false the best video mode possible is chosen.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
 pub enum WindowMode {
     Windowed,
     BorderlessFullscreen,
@@ -512,7 +516,11 @@ impl Window {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Describes the primary window, and the windows created with [CreateWindow](crate::CreateWindow).
+/// Its fields can be set from a settings file, see
+/// [SettingsPlugin](bevy_app::SettingsPlugin).
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Resource)]
 pub struct WindowDescriptor {
     pub width: f32,
     pub height: f32,
@@ -526,6 +534,7 @@ pub struct WindowDescriptor {
     pub cursor_locked: bool,
     pub mode: WindowMode,
     #[cfg(target_arch = "wasm32")]
+    #[reflect(ignore)]
     pub canvas: Option<String>,
 }
 
@@ -10,7 +10,10 @@ use bevy_input::{
 pub use winit_config::*;
 pub use winit_windows::*;
 
-use bevy_app::{App, AppBuilder, AppExit, CoreStage, Events, ManualEventReader, Plugin};
+use bevy_app::{
+    App, AppBuilder, AppExit, CoreStage, Events, FramePacer, FramePacingSettings, FramePacingStats,
+    ManualEventReader, Plugin,
+};
 use bevy_ecs::{system::IntoExclusiveSystem, world::World};
 use bevy_math::{ivec2, Vec2};
 use bevy_utils::tracing::{error, trace, warn};
@@ -19,6 +22,7 @@ use bevy_window::{
     WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowFocused,
     WindowMoved, WindowResized, WindowScaleFactorChanged, Windows,
 };
+use std::{cell::RefCell, rc::Rc};
 use winit::{
     dpi::PhysicalPosition,
     event::{self, DeviceEvent, Event, WindowEvent},
@@ -207,8 +211,8 @@ where
     panic!("Run return is not supported on this platform!")
 }
 
-pub fn winit_runner(app: App) {
-    winit_runner_with(app, EventLoop::new());
+pub fn winit_runner(app: App) -> AppExit {
+    winit_runner_with(app, EventLoop::new())
 }
 
 #[cfg(any(
@@ -218,11 +222,14 @@ pub fn winit_runner(app: App) {
     target_os = "netbsd",
     target_os = "openbsd"
 ))]
-pub fn winit_runner_any_thread(app: App) {
-    winit_runner_with(app, EventLoop::new_any_thread());
+pub fn winit_runner_any_thread(app: App) -> AppExit {
+    winit_runner_with(app, EventLoop::new_any_thread())
 }
 
-pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
+/// Runs `app` with `event_loop`. This only returns if [WinitConfig::return_from_run] is set.
+/// Otherwise, the process exits with the code of the last [AppExit] once the event loop stops.
+/// In both cases, [App::shutdown] is run before the app is dropped.
+pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) -> AppExit {
     let mut create_window_event_reader = ManualEventReader::<CreateWindow>::default();
     let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
     app.world.insert_non_send(event_loop.create_proxy());
@@ -234,17 +241,20 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
         .get_resource::<WinitConfig>()
         .map_or(false, |config| config.return_from_run);
 
+    // shared with the event handler, as `run` requires it to be 'static
+    let app_exit = Rc::new(RefCell::new(AppExit::success()));
+    let handler_app_exit = app_exit.clone();
+
+    let mut frame_pacer = FramePacer::default();
+
     let event_handler = move |event: Event<()>,
                               event_loop: &EventLoopWindowTarget<()>,
                               control_flow: &mut ControlFlow| {
         *control_flow = ControlFlow::Poll;
 
         if let Some(app_exit_events) = app.world.get_resource_mut::<Events<AppExit>>() {
-            if app_exit_event_reader
-                .iter(&app_exit_events)
-                .next_back()
-                .is_some()
-            {
+            if let Some(exit) = app_exit_event_reader.iter(&app_exit_events).next_back() {
+                *handler_app_exit.borrow_mut() = exit.clone();
                 *control_flow = ControlFlow::Exit;
             }
         }
@@ -483,14 +493,39 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
                     &mut create_window_event_reader,
                 );
                 app.update();
+                pace_frame(&mut app, &mut frame_pacer);
+            }
+            event::Event::LoopDestroyed => {
+                app.shutdown();
+                let app_exit = handler_app_exit.borrow();
+                // winit exits the process with code 0 once this handler returns
+                if !should_return_from_run && !app_exit.is_success() {
+                    std::process::exit(app_exit.code);
+                }
             }
             _ => (),
         }
     };
     if should_return_from_run {
         run_return(&mut event_loop, event_handler);
+        app_exit.replace(AppExit::success())
     } else {
-        run(event_loop, event_handler);
+        run(event_loop, event_handler)
+    }
+}
+
+/// Waits for the next frame if a [FramePacingSettings] resource exists. Browsers pace frames
+/// themselves, so this does nothing on the web.
+#[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+fn pace_frame(app: &mut App, frame_pacer: &mut FramePacer) {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(frame_pacing) = app.world.get_resource::<FramePacingSettings>().cloned() {
+        let focused = app.world.get_resource::<Windows>().map_or(true, |windows| {
+            windows.iter().any(|window| window.is_focused())
+        });
+        frame_pacer.pace(&frame_pacing, focused);
+        app.world
+            .insert_resource::<FramePacingStats>(frame_pacer.stats().clone());
     }
 }
 
@@ -5,7 +5,7 @@ struct Input(String);
 
 /// This example demonstrates you can create a custom runner (to update an app manually). It reads
 /// lines from stdin and prints them from within the ecs.
-fn my_runner(mut app: App) {
+fn my_runner(mut app: App) -> AppExit {
     println!("Type stuff into the console");
     for line in io::stdin().lock().lines() {
         {
@@ -14,6 +14,9 @@ fn my_runner(mut app: App) {
         }
         app.update();
     }
+    // runs the systems added with `add_shutdown_system`
+    app.shutdown();
+    AppExit::success()
 }
 
 fn print_system(input: Res<Input>) {
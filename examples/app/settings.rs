@@ -0,0 +1,21 @@
+use bevy::{app::SettingsPlugin, prelude::*};
+
+/// This example illustrates how to change settings resources like `WindowDescriptor` without
+/// recompiling. Settings are read from a RON file, then from environment variables, then from
+/// command-line arguments, so each of these overrides the ones before it. Try running:
+/// `cargo run --example settings -- --set WindowDescriptor.title="Hello from the command line"`
+/// or:
+/// `BEVY_SETTINGS__Msaa__samples=1 cargo run --example settings`
+fn main() {
+    App::build()
+        // SettingsPlugin must be added before the plugins that read the settings
+        .add_plugin(SettingsPlugin::new("assets/settings/example_settings.ron").with_args())
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(print_settings.system())
+        .run();
+}
+
+fn print_settings(window: Res<WindowDescriptor>, msaa: Res<Msaa>) {
+    info!("window title: {}", window.title);
+    info!("msaa samples: {}", msaa.samples);
+}
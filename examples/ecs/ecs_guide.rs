@@ -129,10 +129,10 @@ fn game_over_system(
 ) {
     if let Some(ref player) = game_state.winning_player {
         println!("{} won the game!", player);
-        app_exit_events.send(AppExit);
+        app_exit_events.send(AppExit::success());
     } else if game_state.current_round == game_rules.max_rounds {
         println!("Ran out of rounds. Nobody wins!");
-        app_exit_events.send(AppExit);
+        app_exit_events.send(AppExit::success());
     }
 
     println!();
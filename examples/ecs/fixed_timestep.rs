@@ -1,6 +1,7 @@
 use bevy::{
-    core::{FixedTimestep, FixedTimesteps},
+    core::{FixedTimestep, FixedTimestepStage, FixedTimesteps},
     prelude::*,
+    utils::Duration,
 };
 
 const LABEL: &str = "my_fixed_timestep";
@@ -8,6 +9,9 @@ const LABEL: &str = "my_fixed_timestep";
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
 struct FixedUpdateStage;
 
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+struct PhysicsStage;
+
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
@@ -26,6 +30,16 @@ fn main() {
                 )
                 .with_system(fixed_update.system()),
         )
+        // add a stage that runs every half second. Unlike the run criteria above, it replaces
+        // `Time` with a fixed view while its systems run, so their delta is always the step.
+        // At most 4 steps run each frame, so a long frame doesn't cause even longer ones.
+        .add_stage_after(
+            CoreStage::Update,
+            PhysicsStage,
+            FixedTimestepStage::new(Duration::from_millis(500))
+                .with_max_steps(4)
+                .with_stage(SystemStage::parallel().with_system(physics_update.system())),
+        )
         .run();
 }
 
@@ -48,3 +62,14 @@ fn fixed_update(mut last_time: Local<f64>, time: Res<Time>, fixed_timesteps: Res
 
     *last_time = time.seconds_since_startup();
 }
+
+fn physics_update(time: Res<Time>, fixed_timesteps: Res<FixedTimesteps>) {
+    let fixed_timestep = fixed_timesteps
+        .get(FixedTimestepStage::DEFAULT_LABEL)
+        .unwrap();
+    println!(
+        "physics_update: delta {:?}, overstep_percentage: {}",
+        time.delta(),
+        fixed_timestep.overstep_percentage()
+    );
+}
@@ -97,7 +97,8 @@ fn save_scene_system(world: &mut World) {
     // Scenes can be serialized like this:
     println!("{}", scene.serialize_ron(&type_registry).unwrap());
 
-    // TODO: save scene
+    // Scenes can be saved to a file by adding them to `Assets<DynamicScene>` and calling
+    // `AssetServer::save` with their handle and a path ending in `.scn.ron`.
 }
 
 // This is only necessary for the info message in the UI. See examples/ui/text.rs for a standalone